@@ -0,0 +1,5 @@
+-- Music config (per-guild music command channel)
+CREATE TABLE IF NOT EXISTS music_config (
+    guild_id BIGINT PRIMARY KEY,
+    music_channel_id BIGINT
+);
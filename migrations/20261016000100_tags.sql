@@ -0,0 +1,11 @@
+-- Custom tags / auto-responses
+CREATE TABLE IF NOT EXISTS tags (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    auto_trigger BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by BIGINT NOT NULL,
+    created_at BIGINT NOT NULL,
+    UNIQUE (guild_id, name)
+);
@@ -0,0 +1,3 @@
+-- Verification gate (button grants verify_role, removes unverified_role)
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS verify_role_id BIGINT;
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS unverified_role_id BIGINT;
@@ -0,0 +1,2 @@
+-- Per-guild forex impact filter; existing channels keep receiving everything
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS min_impact TEXT NOT NULL DEFAULT 'low';
@@ -0,0 +1,8 @@
+-- Custom welcome/goodbye messages
+CREATE TABLE IF NOT EXISTS welcome_config (
+    guild_id BIGINT PRIMARY KEY,
+    welcome_message TEXT,
+    leave_message TEXT,
+    color INTEGER,
+    show_avatar BOOLEAN NOT NULL DEFAULT TRUE
+);
@@ -0,0 +1,10 @@
+-- Per-guild include/exclude filters for forex news (currency or keyword)
+CREATE TABLE IF NOT EXISTS forex_filters (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    UNIQUE (guild_id, kind, value)
+);
+
+CREATE INDEX IF NOT EXISTS idx_forex_filters_guild ON forex_filters(guild_id);
@@ -0,0 +1,7 @@
+-- Per-user forex watchlists (not scoped to a guild so they work in DMs)
+CREATE TABLE IF NOT EXISTS user_watchlists (
+    user_id BIGINT NOT NULL,
+    symbol TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (user_id, symbol)
+);
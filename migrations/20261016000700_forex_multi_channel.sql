@@ -0,0 +1,7 @@
+-- Allow several forex channels per guild, each with an optional source/currency scope
+ALTER TABLE forex_channels DROP CONSTRAINT IF EXISTS forex_channels_guild_id_key;
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS source TEXT;
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS currency TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_forex_channels_guild_channel
+    ON forex_channels(guild_id, channel_id);
@@ -0,0 +1,2 @@
+-- Codes whose explicit expiry date has passed
+ALTER TABLE redeem_codes ADD COLUMN IF NOT EXISTS is_expired BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- Optional role pinged for forex news at or above ping_min_impact
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS ping_role_id BIGINT;
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS ping_min_impact TEXT NOT NULL DEFAULT 'high';
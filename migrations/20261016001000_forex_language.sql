@@ -0,0 +1,12 @@
+-- Per-guild translation language for forex news ('off' disables translation)
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS language TEXT NOT NULL DEFAULT 'id';
+
+-- Cached Gemini translations so repeated sends don't re-translate
+CREATE TABLE IF NOT EXISTS forex_translations (
+    news_id TEXT NOT NULL,
+    language TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    created_at BIGINT NOT NULL,
+    PRIMARY KEY (news_id, language)
+);
@@ -0,0 +1,4 @@
+-- Titles of sent news, used to skip near-duplicate stories across feeds
+ALTER TABLE forex_news_sent ADD COLUMN IF NOT EXISTS title TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_forex_news_sent_at ON forex_news_sent(sent_at);
@@ -0,0 +1,4 @@
+-- Keep enough of each sent item to show it again in /forex_latest
+ALTER TABLE forex_news_sent ADD COLUMN IF NOT EXISTS currency TEXT;
+ALTER TABLE forex_news_sent ADD COLUMN IF NOT EXISTS impact TEXT;
+ALTER TABLE forex_news_sent ADD COLUMN IF NOT EXISTS link TEXT;
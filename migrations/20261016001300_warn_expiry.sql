@@ -0,0 +1,2 @@
+-- Warnings older than this many days are ignored and cleaned up (NULL = never expire)
+ALTER TABLE mod_config ADD COLUMN IF NOT EXISTS warn_expiry_days INTEGER;
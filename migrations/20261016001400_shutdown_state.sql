@@ -0,0 +1,22 @@
+-- Price alerts saved on shutdown and restored on startup
+CREATE TABLE IF NOT EXISTS price_alerts (
+    id BIGINT PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    symbol TEXT NOT NULL,
+    condition TEXT NOT NULL,
+    target_price DOUBLE PRECISION NOT NULL,
+    rearm_pips DOUBLE PRECISION NOT NULL,
+    created_at BIGINT NOT NULL
+);
+
+-- Music queues saved on shutdown; `tracks` is a JSON list with the playing track first
+CREATE TABLE IF NOT EXISTS music_saved_queues (
+    guild_id BIGINT PRIMARY KEY,
+    voice_channel_id BIGINT NOT NULL,
+    text_channel_id BIGINT,
+    volume INTEGER NOT NULL,
+    tracks TEXT NOT NULL,
+    saved_at BIGINT NOT NULL
+);
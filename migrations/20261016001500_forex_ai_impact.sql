@@ -0,0 +1,2 @@
+-- Per-guild override for Gemini impact classification (NULL = follow FOREX_AI_IMPACT)
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS ai_impact BOOLEAN;
@@ -0,0 +1,9 @@
+-- Post forex news into one thread per currency under the channel
+ALTER TABLE forex_channels ADD COLUMN IF NOT EXISTS use_threads BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS forex_threads (
+    channel_id BIGINT NOT NULL,
+    currency TEXT NOT NULL,
+    thread_id BIGINT NOT NULL,
+    PRIMARY KEY (channel_id, currency)
+);
@@ -0,0 +1,4 @@
+-- Repeating alerts stay active after firing; expiring alerts are dropped at expires_at
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS repeat BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS expires_at BIGINT;
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS last_triggered BIGINT;
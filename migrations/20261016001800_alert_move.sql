@@ -0,0 +1,2 @@
+-- Rolling window for percentage-move alerts; NULL for price-level alerts
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS window_mins BIGINT;
@@ -0,0 +1,14 @@
+-- Daily market summary schedule per guild; post_time is HH:MM in UTC
+CREATE TABLE IF NOT EXISTS market_summary_config (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    post_time TEXT NOT NULL,
+    -- UTC date (YYYY-MM-DD) of the last scheduled post
+    last_sent_date TEXT
+);
+
+CREATE TABLE IF NOT EXISTS market_summary_symbols (
+    guild_id BIGINT NOT NULL,
+    symbol TEXT NOT NULL,
+    PRIMARY KEY (guild_id, symbol)
+);
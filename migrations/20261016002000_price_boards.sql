@@ -0,0 +1,7 @@
+-- Live price board message per guild, edited every minute; symbols are comma separated
+CREATE TABLE IF NOT EXISTS price_boards (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    message_id BIGINT NOT NULL,
+    symbols TEXT NOT NULL
+);
@@ -0,0 +1,16 @@
+-- Starboard channel per guild
+CREATE TABLE IF NOT EXISTS starboard (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    threshold INTEGER NOT NULL DEFAULT 3,
+    emoji TEXT NOT NULL DEFAULT '⭐'
+);
+
+-- Messages currently shown on the starboard
+CREATE TABLE IF NOT EXISTS star_messages (
+    guild_id BIGINT NOT NULL,
+    source_message_id BIGINT NOT NULL,
+    star_message_id BIGINT NOT NULL,
+    star_count INTEGER NOT NULL,
+    PRIMARY KEY (guild_id, source_message_id)
+);
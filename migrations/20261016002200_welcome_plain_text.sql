@@ -0,0 +1,9 @@
+-- Welcome/goodbye templates are now plain text unless wrapped in {embed:...};
+-- wrap existing templates so they keep rendering as embeds
+UPDATE welcome_config
+SET welcome_message = '{embed:' || welcome_message || '}'
+WHERE welcome_message IS NOT NULL;
+
+UPDATE welcome_config
+SET leave_message = '{embed:' || leave_message || '}'
+WHERE leave_message IS NOT NULL;
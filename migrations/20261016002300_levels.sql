@@ -0,0 +1,19 @@
+-- Message XP per member
+CREATE TABLE IF NOT EXISTS user_xp (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    xp INTEGER NOT NULL DEFAULT 0,
+    level INTEGER NOT NULL DEFAULT 0,
+    messages INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_xp_leaderboard ON user_xp (guild_id, xp DESC);
+
+-- Role granted when a member reaches a level
+CREATE TABLE IF NOT EXISTS level_roles (
+    guild_id BIGINT NOT NULL,
+    level INTEGER NOT NULL,
+    role_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, level)
+);
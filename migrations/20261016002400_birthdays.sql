@@ -0,0 +1,17 @@
+-- Birthdays are month/day only; the year (and so age) is never stored
+CREATE TABLE IF NOT EXISTS birthdays (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    month INTEGER NOT NULL CHECK (month BETWEEN 1 AND 12),
+    day INTEGER NOT NULL CHECK (day BETWEEN 1 AND 31),
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_birthdays_date ON birthdays(month, day);
+
+CREATE TABLE IF NOT EXISTS birthday_config (
+    guild_id BIGINT PRIMARY KEY,
+    channel_id BIGINT NOT NULL,
+    -- UTC date (YYYY-MM-DD) of the last announcement run
+    last_sent_date TEXT
+);
@@ -0,0 +1,2 @@
+-- Deliver the alert by DM instead of in the channel it was created in
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS notify_dm BOOLEAN NOT NULL DEFAULT FALSE;
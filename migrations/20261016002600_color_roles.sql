@@ -0,0 +1,6 @@
+-- Self-assignable color roles; members hold at most one at a time
+CREATE TABLE IF NOT EXISTS color_roles (
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, role_id)
+);
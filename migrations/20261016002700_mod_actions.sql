@@ -0,0 +1,14 @@
+-- Audit trail of moderation actions, kept until cleared with /modlog_clear
+CREATE TABLE IF NOT EXISTS mod_actions (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    action_type TEXT NOT NULL,
+    target_user_id BIGINT NOT NULL,
+    moderator_id BIGINT NOT NULL,
+    reason TEXT,
+    duration_secs INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_mod_actions_target ON mod_actions(guild_id, target_user_id);
+CREATE INDEX IF NOT EXISTS idx_mod_actions_moderator ON mod_actions(guild_id, moderator_id);
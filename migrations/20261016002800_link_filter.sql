@@ -0,0 +1,13 @@
+-- Delete messages with links outside the whitelist; whitelist_domains is a
+-- comma-separated list of domains (subdomains match too)
+CREATE TABLE IF NOT EXISTS link_filter_config (
+    guild_id BIGINT PRIMARY KEY,
+    is_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    whitelist_domains TEXT NOT NULL DEFAULT ''
+);
+
+CREATE TABLE IF NOT EXISTS link_filter_roles_exempt (
+    guild_id BIGINT NOT NULL,
+    role_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, role_id)
+);
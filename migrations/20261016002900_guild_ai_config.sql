@@ -0,0 +1,5 @@
+-- Gemini model per guild; model is a Gemini model ID from the allowlist
+CREATE TABLE IF NOT EXISTS guild_ai_config (
+    guild_id BIGINT PRIMARY KEY,
+    model TEXT NOT NULL
+);
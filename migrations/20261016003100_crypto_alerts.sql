@@ -0,0 +1,20 @@
+-- CoinGecko price alerts, same columns as price_alerts but written as they are
+-- created since the crypto poller reads them straight from the database
+CREATE TABLE IF NOT EXISTS crypto_alerts (
+    id BIGSERIAL PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    symbol TEXT NOT NULL,
+    condition TEXT NOT NULL,
+    target_price DOUBLE PRECISION NOT NULL,
+    rearm_pips DOUBLE PRECISION NOT NULL,
+    created_at BIGINT NOT NULL,
+    repeat BOOLEAN NOT NULL DEFAULT FALSE,
+    expires_at BIGINT,
+    last_triggered BIGINT,
+    window_mins BIGINT,
+    notify_dm BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX IF NOT EXISTS idx_crypto_alerts_user ON crypto_alerts(user_id);
@@ -0,0 +1,4 @@
+-- Codes are unique per game, so the same string announced for two games is
+-- stored (and sent) once for each
+ALTER TABLE redeem_codes DROP CONSTRAINT IF EXISTS redeem_codes_code_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_redeem_codes_game_code ON redeem_codes(game, code);
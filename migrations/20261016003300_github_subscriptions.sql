@@ -0,0 +1,9 @@
+-- Repos whose GitHub releases are posted to a channel; last_release_id is the
+-- latest release already seen, NULL until the repo has one
+CREATE TABLE IF NOT EXISTS github_subscriptions (
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    repo TEXT NOT NULL,
+    last_release_id TEXT,
+    PRIMARY KEY (guild_id, repo)
+);
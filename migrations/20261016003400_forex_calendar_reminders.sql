@@ -0,0 +1,8 @@
+-- Calendar reminders were tracked in forex_news_sent, which made them show up
+-- in /forex_latest and /forex_search. They get their own table instead
+CREATE TABLE IF NOT EXISTS forex_calendar_reminders (
+    reminder_id TEXT PRIMARY KEY,
+    sent_at BIGINT NOT NULL
+);
+
+DELETE FROM forex_news_sent WHERE news_id LIKE 'calendar_%';
@@ -0,0 +1,4 @@
+-- When a code was last returned by its source. Cleanup only removes codes the
+-- source stopped listing, so a long-lived code is never announced twice
+ALTER TABLE redeem_codes ADD COLUMN IF NOT EXISTS last_seen_at BIGINT NOT NULL DEFAULT 0;
+UPDATE redeem_codes SET last_seen_at = created_at WHERE last_seen_at = 0;
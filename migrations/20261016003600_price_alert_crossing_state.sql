@@ -0,0 +1,8 @@
+-- Crossing state, so restored alerts neither fire again nor miss a crossing.
+-- last_side: price was last seen at or above the target. armed: NULL for rows
+-- saved before this column, which restore with the condition's default
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS last_side BOOLEAN;
+ALTER TABLE price_alerts ADD COLUMN IF NOT EXISTS armed BOOLEAN;
+-- crypto_alerts shares the row type with price_alerts
+ALTER TABLE crypto_alerts ADD COLUMN IF NOT EXISTS last_side BOOLEAN;
+ALTER TABLE crypto_alerts ADD COLUMN IF NOT EXISTS armed BOOLEAN;
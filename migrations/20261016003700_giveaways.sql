@@ -0,0 +1,15 @@
+-- Giveaways keyed by their announcement message; winner_ids is a JSON array of
+-- every user id drawn so far, including rerolls
+CREATE TABLE IF NOT EXISTS giveaways (
+    message_id BIGINT PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    channel_id BIGINT NOT NULL,
+    host_id BIGINT NOT NULL,
+    prize TEXT NOT NULL,
+    winner_count INTEGER NOT NULL,
+    ends_at BIGINT NOT NULL,
+    ended BOOLEAN NOT NULL DEFAULT FALSE,
+    winner_ids TEXT NOT NULL DEFAULT '[]'
+);
+
+CREATE INDEX IF NOT EXISTS idx_giveaways_due ON giveaways (ended, ends_at);
@@ -1,8 +1,135 @@
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
-#[poise::command(prefix_command, guild_only, owners_only)]
-pub async fn everyone(ctx: Context<'_>) -> Result<(), Error> {
-    ctx.say("@everyone").await?;
+/// Discord's limit for an embed description
+const MAX_ANNOUNCEMENT_CHARS: usize = 4096;
+
+/// Post an announcement embed in this channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MENTION_EVERYONE"
+)]
+pub async fn announce(
+    ctx: Context<'_>,
+    #[description = "Ping @everyone (asks for confirmation first)"] ping_everyone: Option<bool>,
+    #[rest]
+    #[description = "Announcement text"]
+    message: String,
+) -> Result<(), Error> {
+    let length = message.chars().count();
+    if length > MAX_ANNOUNCEMENT_CHARS {
+        let embed = embed::error(
+            "Announcement Too Long",
+            &format!(
+                "Announcements can be at most {} characters, this one is {}",
+                MAX_ANNOUNCEMENT_CHARS, length
+            ),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let ping_everyone = ping_everyone.unwrap_or(false);
+    let embed = serenity::CreateEmbed::new()
+        .title("📢 Announcement")
+        .description(&message)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Posted by {}",
+            ctx.author().name
+        )))
+        .color(0x5865F2)
+        .timestamp(serenity::Timestamp::now());
+
+    if !ping_everyone {
+        post_announcement(ctx, embed, false).await?;
+        ctx.send(
+            poise::CreateReply::default()
+                .content("✅ Announcement posted")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let confirm_id = format!("{}_announce_confirm", ctx.id());
+    let cancel_id = format!("{}_announce_cancel", ctx.id());
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Ping everyone")
+            .style(serenity::ButtonStyle::Danger),
+        serenity::CreateButton::new(&cancel_id)
+            .label("Cancel")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content("This will ping **@everyone** in this channel. Post it?")
+            .embed(embed.clone())
+            .components(vec![buttons])
+            .ephemeral(true),
+    )
+    .await?;
+
+    let confirm_filter = confirm_id.clone();
+    let cancel_filter = cancel_id.clone();
+    let interaction = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(std::time::Duration::from_secs(60))
+        .filter(move |mci| {
+            mci.data.custom_id == confirm_filter || mci.data.custom_id == cancel_filter
+        })
+        .await;
+
+    let Some(interaction) = interaction else {
+        return Ok(());
+    };
+
+    let confirmed = interaction.data.custom_id == confirm_id;
+    if confirmed {
+        post_announcement(ctx, embed, true).await?;
+    }
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .content(if confirmed {
+                        "✅ Announcement posted"
+                    } else {
+                        "Cancelled, nothing was posted"
+                    })
+                    .embeds(vec![])
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Mentions are off unless `ping_everyone` is set, so text like "@everyone"
+/// inside the announcement never pings on its own
+async fn post_announcement(
+    ctx: Context<'_>,
+    embed: serenity::CreateEmbed,
+    ping_everyone: bool,
+) -> Result<(), Error> {
+    let mut message = serenity::CreateMessage::new()
+        .embed(embed)
+        .allowed_mentions(serenity::CreateAllowedMentions::new().everyone(ping_everyone));
+    if ping_everyone {
+        message = message.content("@everyone");
+    }
+    ctx.channel_id().send_message(ctx, message).await?;
     Ok(())
-}
\ No newline at end of file
+}
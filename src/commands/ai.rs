@@ -1,13 +1,85 @@
 use crate::config::Config;
-use crate::error::BotError;
+use crate::repository::{AiConfigRepository, ChatHistoryRepository};
 use crate::services::ai::Ai;
-use crate::services::gemini::GeminiService;
-use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use crate::services::gemini::{GeminiModel, GeminiService};
+use parking_lot::Mutex;
+use poise::serenity_prelude::{CreateAttachment, CreateEmbed, CreateEmbedFooter};
 use poise::CreateReply;
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+/// `/ai_stats` only spends a request on topics for histories longer than this
+const TOPIC_MIN_MESSAGES: usize = 20;
+/// Most recent characters of history sent when asking for topics
+const TOPIC_MAX_CHARS: usize = 8000;
+/// Messages of chat history kept per user and guild, older ones are pruned
+const CHAT_HISTORY_LIMIT: i64 = 200;
+
+/// Recent Gemini requests per guild (or user in DMs) and model
+type RequestLog = HashMap<(u64, GeminiModel), VecDeque<Instant>>;
+
+static GEMINI_REQUESTS: OnceLock<Mutex<RequestLog>> = OnceLock::new();
+
+/// Count a request against the model's per-minute limit. Returns how long to
+/// wait when the limit is reached
+fn take_request(scope: u64, model: GeminiModel) -> Result<(), Duration> {
+    let mut requests = GEMINI_REQUESTS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock();
+    let sent = requests.entry((scope, model)).or_default();
+    while sent.front().is_some_and(|t| t.elapsed() >= RATE_WINDOW) {
+        sent.pop_front();
+    }
+    if sent.len() >= model.requests_per_minute() {
+        let oldest = sent.front().copied().unwrap_or_else(Instant::now);
+        return Err(RATE_WINDOW.saturating_sub(oldest.elapsed()));
+    }
+    sent.push_back(Instant::now());
+    Ok(())
+}
+
+/// Rate limits apply per guild, or per user in DMs
+fn rate_scope(ctx: Context<'_>) -> u64 {
+    ctx.guild_id()
+        .map(|g| g.get())
+        .unwrap_or(ctx.author().id.get())
+}
+
+async fn guild_model(ctx: Context<'_>) -> Result<GeminiModel, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(GeminiModel::default());
+    };
+    let model = AiConfigRepository::get_model(ctx.data().db.as_ref(), guild_id.get())
+        .await?
+        .and_then(|id| GeminiModel::from_id(&id))
+        .unwrap_or_default();
+    Ok(model)
+}
+
+/// The guild's configured model, or `None` after telling the user they hit
+/// the rate limit
+async fn gemini_model(ctx: Context<'_>) -> Result<Option<GeminiModel>, Error> {
+    let model = guild_model(ctx).await?;
+
+    if let Err(wait) = take_request(rate_scope(ctx), model) {
+        ctx.say(format!(
+            "⏳ Batas {} request/menit untuk `{}` tercapai. Coba lagi dalam {} detik.",
+            model.requests_per_minute(),
+            model.id(),
+            wait.as_secs().max(1)
+        ))
+        .await?;
+        return Ok(None);
+    }
+
+    Ok(Some(model))
+}
+
 fn split_into_chunks(s: &str, max: usize) -> Vec<String> {
     let mut chunks = Vec::new();
     let mut start = 0;
@@ -50,8 +122,7 @@ pub async fn worm(
     #[description = "Pertanyaan untuk AI"]
     text: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     // Check if AI is enabled
     let api_key = match &config.api_key {
@@ -104,8 +175,7 @@ pub async fn gemini(
     #[description = "Pertanyaan untuk Gemini AI"]
     text: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -113,15 +183,15 @@ pub async fn gemini(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.prompt,
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, config.prompt);
 
     ctx.defer().await?;
 
-    match gemini.generate(&text).await {
+    match gemini.generate(model.id(), &text).await {
         Ok(response) => {
             send_ai_response(ctx, response).await?;
         }
@@ -133,6 +203,17 @@ pub async fn gemini(
     Ok(())
 }
 
+/// Store a prompt and its reply, keeping the history under `CHAT_HISTORY_LIMIT`
+async fn save_chat(ctx: Context<'_>, prompt: &str, response: &str) -> Result<(), sqlx::Error> {
+    let pool = ctx.data().db.as_ref();
+    let guild_id = ctx.guild_id().map(|g| g.get()).unwrap_or(0);
+    let author_id = ctx.author().id.get();
+    ChatHistoryRepository::add_message(pool, guild_id, author_id, "user", prompt).await?;
+    ChatHistoryRepository::add_message(pool, guild_id, author_id, "model", response).await?;
+    ChatHistoryRepository::prune(pool, guild_id, author_id, CHAT_HISTORY_LIMIT).await?;
+    Ok(())
+}
+
 /// Chat dengan Gemini dengan memory (ingat percakapan sebelumnya)
 #[poise::command(prefix_command, slash_command, aliases("gchat", "gc"))]
 pub async fn gemini_chat(
@@ -141,8 +222,7 @@ pub async fn gemini_chat(
     #[description = "Pesan untuk Gemini AI"]
     text: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -150,18 +230,22 @@ pub async fn gemini_chat(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.prompt,
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, config.prompt);
 
     ctx.defer().await?;
 
     let user_id = ctx.author().id.to_string();
     
-    match gemini.chat(&user_id, &text).await {
+    match gemini.chat(model.id(), &user_id, &text).await {
         Ok(response) => {
+            // A failed save shouldn't cost the user their reply
+            if let Err(e) = save_chat(ctx, &text, &response).await {
+                eprintln!("[AI] Failed to save chat history: {}", e);
+            }
             send_ai_response(ctx, response).await?;
         }
         Err(e) => {
@@ -175,22 +259,23 @@ pub async fn gemini_chat(
 /// Hapus history chat Gemini
 #[poise::command(prefix_command, slash_command, aliases("gclear"))]
 pub async fn gemini_clear(ctx: Context<'_>) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi.").await?;
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.prompt,
-    );
+    let gemini = GeminiService::new(config.gemini_api_key, config.prompt);
 
     let user_id = ctx.author().id.to_string();
     gemini.clear_history(&user_id).await;
+    ChatHistoryRepository::clear(
+        ctx.data().db.as_ref(),
+        ctx.guild_id().map(|g| g.get()).unwrap_or(0),
+        ctx.author().id.get(),
+    )
+    .await?;
 
     ctx.say("✅ History chat kamu telah dihapus!").await?;
     Ok(())
@@ -206,8 +291,7 @@ pub async fn gemini_vision(
     #[description = "Pertanyaan tentang gambar (opsional)"]
     prompt: Option<String>,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -215,15 +299,15 @@ pub async fn gemini_vision(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.prompt,
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, config.prompt);
 
     ctx.defer().await?;
 
-    match gemini.analyze_image(&image_url, prompt.as_deref()).await {
+    match gemini.analyze_image(model.id(), &image_url, prompt.as_deref()).await {
         Ok(response) => {
             let embed = CreateEmbed::default()
                 .title("🖼️ Analisis Gambar")
@@ -258,8 +342,7 @@ pub async fn analisa(
     #[description = "Konteks tambahan (opsional)"]
     context: Option<String>,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -303,15 +386,16 @@ pub async fn analisa(
         }
     };
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        config.gemini_prompt,
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, config.gemini_prompt);
 
     let loading_msg = ctx.say("📊 Menganalisis chart... Mohon tunggu sebentar.").await?;
 
     match gemini.analyze_market_image(
+        model.id(),
         &image_url, 
         symbol.as_deref(), 
         timeframe.as_deref(),
@@ -357,8 +441,7 @@ pub async fn gemini_summarize(
     #[description = "Teks yang ingin diringkas"]
     text: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -366,15 +449,15 @@ pub async fn gemini_summarize(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        String::new(),
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, String::new());
 
     ctx.defer().await?;
 
-    match gemini.summarize(&text).await {
+    match gemini.summarize(model.id(), &text).await {
         Ok(response) => {
             let embed = CreateEmbed::default()
                 .title("📝 Ringkasan")
@@ -406,8 +489,7 @@ pub async fn gemini_translate(
     #[description = "Teks yang ingin diterjemahkan"]
     text: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -415,15 +497,15 @@ pub async fn gemini_translate(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        String::new(),
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, String::new());
 
     ctx.defer().await?;
 
-    match gemini.translate(&text, &target_language).await {
+    match gemini.translate(model.id(), &text, &target_language).await {
         Ok(response) => {
             let embed = CreateEmbed::default()
                 .title(format!("🌐 Terjemahan ke {}", target_language))
@@ -456,8 +538,7 @@ pub async fn gemini_code(
     #[description = "Deskripsi kode yang ingin dibuat"]
     description: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -465,15 +546,15 @@ pub async fn gemini_code(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        String::new(),
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, String::new());
 
     ctx.defer().await?;
 
-    match gemini.generate_code(&description, &language).await {
+    match gemini.generate_code(model.id(), &description, &language).await {
         Ok(response) => {
             send_ai_response(ctx, format!("**💻 Code Generation ({}):**\n\n{}", language, response)).await?;
         }
@@ -493,8 +574,7 @@ pub async fn gemini_explain(
     #[description = "Code yang ingin dijelaskan"]
     code: String,
 ) -> Result<(), Error> {
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     if config.gemini_api_key == "api_key" {
         ctx.say("❌ Fitur Gemini AI belum dikonfigurasi. Harap set `GEMINI_API_KEY` di environment.")
@@ -502,15 +582,15 @@ pub async fn gemini_explain(
         return Ok(());
     }
 
-    let gemini = GeminiService::new(
-        config.gemini_api_key,
-        None,
-        String::new(),
-    );
+    let Some(model) = gemini_model(ctx).await? else {
+        return Ok(());
+    };
+
+    let gemini = GeminiService::new(config.gemini_api_key, String::new());
 
     ctx.defer().await?;
 
-    match gemini.explain_code(&code).await {
+    match gemini.explain_code(model.id(), &code).await {
         Ok(response) => {
             send_ai_response(ctx, format!("**📖 Code Explanation:**\n\n{}", response)).await?;
         }
@@ -522,3 +602,223 @@ pub async fn gemini_explain(
     Ok(())
 }
 
+
+/// Gemini model used by this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("ai_model_set", "ai_model_reset", "ai_model_show"),
+    subcommand_required
+)]
+pub async fn ai_model(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Pilih model Gemini untuk server ini
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "set"
+)]
+pub async fn ai_model_set(
+    ctx: Context<'_>,
+    #[description = "Model Gemini"] model: GeminiModel,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    AiConfigRepository::set_model(ctx.data().db.as_ref(), guild_id, model.id()).await?;
+
+    ctx.say(format!(
+        "✅ Server ini sekarang memakai `{}` (maks {} request/menit).",
+        model.id(),
+        model.requests_per_minute()
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Kembalikan model Gemini ke default
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "reset"
+)]
+pub async fn ai_model_reset(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    AiConfigRepository::reset_model(ctx.data().db.as_ref(), guild_id).await?;
+
+    ctx.say(format!(
+        "✅ Model dikembalikan ke default `{}`.",
+        GeminiModel::default().id()
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Lihat model Gemini yang dipakai server ini
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "show"
+)]
+pub async fn ai_model_show(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let model = AiConfigRepository::get_model(ctx.data().db.as_ref(), guild_id)
+        .await?
+        .and_then(|id| GeminiModel::from_id(&id))
+        .unwrap_or_default();
+
+    let available = GeminiModel::ALL
+        .iter()
+        .map(|m| format!("• `{}` — {} request/menit", m.id(), m.requests_per_minute()))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    ctx.say(format!(
+        "🤖 Model saat ini: `{}`\n\n**Model tersedia:**\n{}",
+        model.id(),
+        available
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Unduh riwayat chat Gemini kamu sebagai file Markdown
+#[poise::command(prefix_command, slash_command, aliases("gexport"))]
+pub async fn ai_export(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get()).unwrap_or(0);
+    let history =
+        ChatHistoryRepository::get_history(ctx.data().db.as_ref(), guild_id, ctx.author().id.get())
+            .await?;
+
+    if history.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("Belum ada riwayat chat. Mulai dengan `/gemini_chat`.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut markdown = String::new();
+    for message in &history {
+        if message.role == "user" {
+            markdown.push_str(&format!("**User:** {}\n\n", message.content));
+        } else {
+            markdown.push_str(&format!("**AI:** {}\n\n---\n", message.content));
+        }
+    }
+
+    let file_name = format!("conversation_{}.md", chrono::Utc::now().timestamp());
+    ctx.send(
+        CreateReply::default()
+            .content(format!("📄 {} pesan diekspor.", history.len()))
+            .attachment(CreateAttachment::bytes(markdown.into_bytes(), file_name))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Statistik riwayat chat Gemini kamu
+#[poise::command(prefix_command, slash_command, aliases("gstats"))]
+pub async fn ai_stats(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().map(|g| g.get()).unwrap_or(0);
+    let history =
+        ChatHistoryRepository::get_history(ctx.data().db.as_ref(), guild_id, ctx.author().id.get())
+            .await?;
+
+    let (Some(oldest), Some(newest)) = (history.first(), history.last()) else {
+        ctx.send(
+            CreateReply::default()
+                .content("Belum ada riwayat chat. Mulai dengan `/gemini_chat`.")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    ctx.defer_ephemeral().await?;
+
+    let chars: usize = history.iter().map(|m| m.content.chars().count()).sum();
+    let topics = if history.len() > TOPIC_MIN_MESSAGES {
+        Some(history_topics(ctx, &history).await)
+    } else {
+        None
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title("📊 Statistik Chat AI")
+        .field("Total pesan", history.len().to_string(), true)
+        .field("Perkiraan token", format!("~{}", chars / 4), true)
+        .field(
+            "Pesan pertama",
+            format!("<t:{}:R>", oldest.created_at.timestamp()),
+            true,
+        )
+        .field(
+            "Pesan terakhir",
+            format!("<t:{}:R>", newest.created_at.timestamp()),
+            true,
+        )
+        .color(0x4285F4)
+        .footer(CreateEmbedFooter::new(
+            "Token dihitung kasar: jumlah karakter / 4",
+        ));
+    if let Some(topics) = topics {
+        embed = embed.field("Topik utama", topics, false);
+    }
+
+    ctx.send(CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Five-word summary of a chat history, or why there isn't one
+async fn history_topics(ctx: Context<'_>, history: &[crate::repository::ChatMessage]) -> String {
+    let config = match Config::from_env() {
+        Ok(config) if config.gemini_api_key != "api_key" => config,
+        _ => return "Gemini belum dikonfigurasi".to_string(),
+    };
+    let model = match guild_model(ctx).await {
+        Ok(model) => model,
+        Err(e) => return format!("Gagal memuat model: {}", e),
+    };
+    if take_request(rate_scope(ctx), model).is_err() {
+        return "Batas request tercapai, coba lagi nanti".to_string();
+    }
+
+    let transcript = history
+        .iter()
+        .map(|m| format!("{}: {}", m.role, m.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let start = transcript
+        .char_indices()
+        .rev()
+        .nth(TOPIC_MAX_CHARS)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let prompt = format!(
+        "Summarize the main topics of this conversation in at most 5 words. \
+        Reply with the words only:\n\n{}",
+        &transcript[start..]
+    );
+
+    let gemini = GeminiService::new(config.gemini_api_key, String::new());
+    match gemini.generate(model.id(), &prompt).await {
+        Ok(topics) => topics.trim().to_string(),
+        Err(e) => format!("Gagal meringkas topik: {}", e),
+    }
+}
@@ -0,0 +1,216 @@
+use crate::repository::BirthdayRepository;
+use crate::services::birthday::{is_valid_date, next_celebration};
+use crate::utils::embed;
+use chrono::{Duration, Utc};
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const UPCOMING_DAYS: i64 = 30;
+
+/// Birthdays in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    subcommands("birthday_set", "birthday_remove", "birthday_list", "birthday_channel"),
+    subcommand_required
+)]
+pub async fn birthday(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set your birthday (month and day only)
+#[poise::command(slash_command, prefix_command, guild_only, rename = "set")]
+pub async fn birthday_set(
+    ctx: Context<'_>,
+    #[description = "Month (1-12)"]
+    #[min = 1]
+    #[max = 12]
+    month: u32,
+    #[description = "Day of the month"]
+    #[min = 1]
+    #[max = 31]
+    day: u32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    if !is_valid_date(month, day) {
+        let embed = embed::error(
+            "Invalid Date",
+            &format!("{}/{} is not a real date", month, day),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    BirthdayRepository::set_birthday(
+        ctx.data().db.as_ref(),
+        guild_id,
+        ctx.author().id.get(),
+        month as i32,
+        day as i32,
+    )
+    .await?;
+
+    let mut description = format!("Your birthday is set to **{}**", format_date(month, day));
+    if month == 2 && day == 29 {
+        description.push_str("\nOutside leap years it is celebrated on February 28");
+    }
+    let embed = embed::success("Birthday Set", &description);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Remove your birthday from this server
+#[poise::command(slash_command, prefix_command, guild_only, rename = "remove")]
+pub async fn birthday_remove(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let removed = BirthdayRepository::delete_birthday(
+        ctx.data().db.as_ref(),
+        guild_id,
+        ctx.author().id.get(),
+    )
+    .await?;
+    let embed = if removed {
+        embed::success("Birthday Removed", "Your birthday was removed")
+    } else {
+        embed::error("Not Found", "You haven't set a birthday in this server")
+    };
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Birthdays coming up in the next 30 days
+#[poise::command(slash_command, prefix_command, guild_only, rename = "list")]
+pub async fn birthday_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let today = Utc::now().date_naive();
+    let last_day = today + Duration::days(UPCOMING_DAYS);
+    let mut upcoming: Vec<_> =
+        BirthdayRepository::get_guild_birthdays(ctx.data().db.as_ref(), guild_id)
+            .await?
+            .into_iter()
+            .filter_map(|b| {
+                next_celebration(b.month as u32, b.day as u32, today)
+                    .filter(|date| *date <= last_day)
+                    .map(|date| (date, b))
+            })
+            .collect();
+    upcoming.sort_by_key(|(date, b)| (*date, b.user_id));
+
+    let description = if upcoming.is_empty() {
+        format!(
+            "No birthdays in the next {} days. Add yours with `/birthday set`",
+            UPCOMING_DAYS
+        )
+    } else {
+        upcoming
+            .iter()
+            .map(|(date, b)| {
+                let when = match (*date - today).num_days() {
+                    0 => "today 🎉".to_string(),
+                    1 => "tomorrow".to_string(),
+                    days => format!("in {} days", days),
+                };
+                format!(
+                    "**{}** • <@{}> • {}",
+                    format_date(b.month as u32, b.day as u32),
+                    b.user_id,
+                    when
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = embed::info("🎂 Upcoming Birthdays", &description);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Where birthday announcements are posted
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "channel",
+    subcommands("birthday_channel_set", "birthday_channel_disable"),
+    subcommand_required
+)]
+pub async fn birthday_channel(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Post birthday announcements in a channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "set"
+)]
+pub async fn birthday_channel_set(
+    ctx: Context<'_>,
+    #[description = "Channel for birthday announcements"] channel: serenity::GuildChannel,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    BirthdayRepository::set_channel(ctx.data().db.as_ref(), guild_id, channel.id.get()).await?;
+
+    let embed = embed::success(
+        "Birthday Channel Set",
+        &format!(
+            "Birthdays will be announced in <#{}> at midnight UTC",
+            channel.id
+        ),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop birthday announcements
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "disable"
+)]
+pub async fn birthday_channel_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let embed = if BirthdayRepository::delete_channel(ctx.data().db.as_ref(), guild_id).await? {
+        embed::success("Birthdays Disabled", "Birthday announcements are off")
+    } else {
+        embed::error("Not Set", "No birthday channel is configured")
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn format_date(month: u32, day: u32) -> String {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    let name = MONTHS.get(month as usize - 1).copied().unwrap_or("?");
+    format!("{} {}", name, day)
+}
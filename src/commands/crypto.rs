@@ -0,0 +1,203 @@
+use crate::repository::{CryptoAlertRepository, StoredAlert};
+use crate::services::crypto::{
+    CoinPrice, CryptoCondition, client, coin_id, coin_label, fetch_prices, format_idr,
+    format_market_cap, format_usd,
+};
+use crate::utils::embed;
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+/// Price for one coin, or `None` when CoinGecko doesn't know the id
+async fn coin_price(id: &str) -> Result<Option<CoinPrice>, Error> {
+    let mut prices = fetch_prices(&client(), &[id.to_string()]).await?;
+    Ok(prices.remove(id))
+}
+
+async fn unknown_coin(ctx: Context<'_>, coin: &str) -> Result<(), Error> {
+    let embed = embed::error(
+        "Unknown Coin",
+        &format!(
+            "`{}` isn't a known ticker or CoinGecko id. Try `btc`, `eth`, `sol` or an id like `bitcoin`",
+            coin
+        ),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Current crypto price in USD and IDR
+#[poise::command(slash_command, prefix_command)]
+pub async fn crypto_price(
+    ctx: Context<'_>,
+    #[description = "Ticker or CoinGecko id (e.g. btc, eth, solana)"] coin: String,
+) -> Result<(), Error> {
+    let id = coin_id(&coin);
+    let Some(price) = coin_price(&id).await? else {
+        return unknown_coin(ctx, &coin).await;
+    };
+
+    let change = price
+        .usd_24h_change
+        .map(|c| format!("{} {:+.2}%", if c >= 0.0 { "📈" } else { "📉" }, c))
+        .unwrap_or_else(|| "N/A".to_string());
+    let market_cap = price
+        .usd_market_cap
+        .filter(|cap| *cap > 0.0)
+        .map(format_market_cap)
+        .unwrap_or_else(|| "N/A".to_string());
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("🪙 {}", coin_label(&id)))
+        .field("USD", format_usd(price.usd), true)
+        .field(
+            "IDR",
+            price
+                .idr
+                .map(format_idr)
+                .unwrap_or_else(|| "N/A".to_string()),
+            true,
+        )
+        .field("24h Change", change, true)
+        .field("Market Cap", market_cap, true)
+        .footer(serenity::CreateEmbedFooter::new("Data from CoinGecko"))
+        .color(match price.usd_24h_change {
+            Some(c) if c < 0.0 => embed::COLOR_ERROR,
+            _ => embed::COLOR_SUCCESS,
+        })
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Get notified when a coin's USD price crosses a level
+#[poise::command(slash_command, prefix_command)]
+pub async fn crypto_alert(
+    ctx: Context<'_>,
+    #[description = "Ticker or CoinGecko id (e.g. btc, eth, solana)"] coin: String,
+    #[description = "Fire when the price is above or below the target"] condition: CryptoCondition,
+    #[description = "Target price in USD"] price: f64,
+) -> Result<(), Error> {
+    if !price.is_finite() || price <= 0.0 {
+        let embed = embed::error("Invalid Price", "The target price must be above zero");
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let id = coin_id(&coin);
+    let Some(current) = coin_price(&id).await? else {
+        return unknown_coin(ctx, &coin).await;
+    };
+
+    let alert = StoredAlert {
+        id: 0,
+        guild_id: ctx.guild_id().map(|g| g.get()).unwrap_or(0) as i64,
+        user_id: ctx.author().id.get() as i64,
+        channel_id: ctx.channel_id().get() as i64,
+        symbol: id.clone(),
+        condition: condition.name().to_string(),
+        target_price: price,
+        rearm_pips: 0.0,
+        created_at: Utc::now().timestamp(),
+        repeat: false,
+        expires_at: None,
+        last_triggered: None,
+        window_mins: None,
+        notify_dm: false,
+        last_side: None,
+        armed: None,
+    };
+    let alert_id = CryptoAlertRepository::add_alert(ctx.data().db.as_ref(), &alert).await?;
+
+    let mut current_price = format_usd(current.usd);
+    if let Some(idr) = current.idr {
+        current_price.push_str(&format!(" ({})", format_idr(idr)));
+    }
+    let embed = embed::success(
+        "Crypto Alert Created",
+        &format!(
+            "Alert **#{}** set!\n\n**{}** {} **{}**\n\nCurrent: {}",
+            alert_id,
+            coin_label(&id),
+            condition.name(),
+            format_usd(price),
+            current_price
+        ),
+    )
+    .footer(serenity::CreateEmbedFooter::new(
+        "Prices are checked every minute",
+    ));
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// List your crypto alerts
+#[poise::command(slash_command, prefix_command)]
+pub async fn crypto_alerts(ctx: Context<'_>) -> Result<(), Error> {
+    let alerts =
+        CryptoAlertRepository::get_user_alerts(ctx.data().db.as_ref(), ctx.author().id.get())
+            .await?;
+
+    if alerts.is_empty() {
+        let embed = serenity::CreateEmbed::new()
+            .title("Your Crypto Alerts")
+            .description(
+                "No active alerts.\n\nUse `/crypto_alert <coin> <above/below> <price>` to create one.",
+            )
+            .color(0x808080);
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let description: String = alerts
+        .iter()
+        .map(|alert| {
+            format!(
+                "**#{}** {} {} {}\n",
+                alert.id,
+                coin_label(&alert.symbol),
+                alert.condition,
+                format_usd(alert.target_price)
+            )
+        })
+        .collect();
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Your Crypto Alerts")
+        .description(description)
+        .footer(serenity::CreateEmbedFooter::new(
+            "Use /crypto_alert_remove <id> to remove",
+        ))
+        .color(0x1DB954);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Remove one of your crypto alerts
+#[poise::command(slash_command, prefix_command)]
+pub async fn crypto_alert_remove(
+    ctx: Context<'_>,
+    #[description = "Alert ID to remove"] id: i64,
+) -> Result<(), Error> {
+    let removed =
+        CryptoAlertRepository::delete_user_alert(ctx.data().db.as_ref(), id, ctx.author().id.get())
+            .await?;
+
+    let embed = if removed {
+        embed::success(
+            "Alert Removed",
+            &format!("Crypto alert **#{}** has been removed", id),
+        )
+    } else {
+        embed::error(
+            "Not Found",
+            &format!("Crypto alert #{} not found or doesn't belong to you", id),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
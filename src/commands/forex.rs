@@ -1,4 +1,8 @@
-use crate::repository::ForexRepository;
+use crate::repository::{ForexRepository, SentNews};
+use crate::services::forex::{Impact, MarketHours, feed_status};
+use crate::services::forex_calendar::cached_calendar;
+use crate::utils::health::format_ago;
+use crate::utils::pagination::paginate;
 use poise::serenity_prelude as serenity;
 use serenity::{CreateEmbed, CreateEmbedFooter, Timestamp};
 
@@ -15,14 +19,40 @@ type Context<'a> = poise::Context<'a, super::Data, Error>;
 pub async fn forex_setup(
     ctx: Context<'_>,
     #[description = "Channel for forex news"] channel: serenity::GuildChannel,
+    #[description = "Only send news from this source"] source: Option<SourceChoice>,
+    #[description = "Only send news mentioning this currency (e.g. XAU)"] currency: Option<String>,
+    #[description = "Minimum impact for this channel"] impact: Option<ImpactChoice>,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
     let channel_id = channel.id.get();
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    let impact = impact.map(Impact::from);
 
     let pool = ctx.data().db.as_ref();
-    ForexRepository::insert_channel(pool, guild_id, channel_id).await?;
+    ForexRepository::insert_channel(
+        pool,
+        guild_id,
+        channel_id,
+        source.map(|s| s.key()),
+        currency.as_deref(),
+        impact.map(|i| i.key()),
+    )
+    .await?;
 
-    let embed = CreateEmbed::default()
+    let mut scope = Vec::new();
+    if let Some(source) = source {
+        scope.push(format!("Source: `{}`", source.key()));
+    }
+    if let Some(currency) = &currency {
+        scope.push(format!("Currency: `{}`", currency));
+    }
+    if let Some(impact) = impact {
+        scope.push(format!("Min impact: `{}`", impact.label()));
+    }
+
+    let mut embed = CreateEmbed::default()
         .title("Forex News Setup Complete")
         .description(format!(
             "Real-time forex news will be sent to <#{}>\n\n\
@@ -39,6 +69,10 @@ pub async fn forex_setup(
         .footer(CreateEmbedFooter::new("Updates every 60 seconds"))
         .timestamp(Timestamp::now());
 
+    if !scope.is_empty() {
+        embed = embed.field("Channel Scope", scope.join("\n"), false);
+    }
+
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
@@ -50,17 +84,27 @@ pub async fn forex_setup(
     guild_only,
     required_permissions = "ADMINISTRATOR"
 )]
-pub async fn forex_disable(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn forex_disable(
+    ctx: Context<'_>,
+    #[description = "Channel to disable (default: all)"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let channel_id = channel.map(|c| c.id.get());
 
     let pool = ctx.data().db.as_ref();
-    ForexRepository::disable_channel(pool, guild_id).await?;
+    let changed = ForexRepository::disable_channel(pool, guild_id, channel_id).await?;
+
+    let target = match channel_id {
+        Some(id) => format!("<#{}>", id),
+        None => format!("all {} channel(s)", changed),
+    };
 
     let embed = CreateEmbed::default()
         .title("Forex News Disabled")
-        .description(
-            "Forex news notifications have been disabled.\n\nUse `/forex_setup` to enable again.",
-        )
+        .description(format!(
+            "Forex news notifications have been disabled for {}.\n\nUse `/forex_enable` to enable again.",
+            target
+        ))
         .color(serenity::Colour::from_rgb(158, 158, 158))
         .timestamp(Timestamp::now());
 
@@ -75,15 +119,27 @@ pub async fn forex_disable(ctx: Context<'_>) -> Result<(), Error> {
     guild_only,
     required_permissions = "ADMINISTRATOR"
 )]
-pub async fn forex_enable(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn forex_enable(
+    ctx: Context<'_>,
+    #[description = "Channel to enable (default: all)"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let channel_id = channel.map(|c| c.id.get());
 
     let pool = ctx.data().db.as_ref();
-    ForexRepository::enable_channel(pool, guild_id).await?;
+    let changed = ForexRepository::enable_channel(pool, guild_id, channel_id).await?;
+
+    let target = match channel_id {
+        Some(id) => format!("<#{}>", id),
+        None => format!("all {} channel(s)", changed),
+    };
 
     let embed = CreateEmbed::default()
         .title("Forex News Enabled")
-        .description("Forex news notifications have been re-enabled.")
+        .description(format!(
+            "Forex news notifications have been re-enabled for {}.",
+            target
+        ))
         .color(serenity::Colour::from_rgb(0, 150, 136))
         .timestamp(Timestamp::now());
 
@@ -91,44 +147,821 @@ pub async fn forex_enable(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-/// Check forex news status
-#[poise::command(slash_command, prefix_command, guild_only)]
-pub async fn forex_status(ctx: Context<'_>) -> Result<(), Error> {
+/// Ping a role for important forex news
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn forex_ping(
+    ctx: Context<'_>,
+    #[description = "Role to ping (leave empty to turn pings off)"] role: Option<serenity::Role>,
+    #[description = "Minimum impact that pings (default: high)"] impact: Option<ImpactChoice>,
+    #[description = "Forex channel (default: all)"] channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let impact = impact.map(Impact::from).unwrap_or(Impact::High);
+    let channel_id = channel.map(|c| c.id.get());
 
     let pool = ctx.data().db.as_ref();
-    let channel = ForexRepository::get_channel(pool, guild_id).await?;
+    let changed = ForexRepository::set_ping_role(
+        pool,
+        guild_id,
+        channel_id,
+        role.as_ref().map(|r| r.id.get()),
+        impact.key(),
+    )
+    .await?;
 
-    let embed = match channel {
-        Some(ch) => {
-            let status = if ch.is_active { "Active" } else { "Disabled" };
-            let color = if ch.is_active {
-                serenity::Colour::from_rgb(0, 150, 136)
-            } else {
-                serenity::Colour::from_rgb(158, 158, 158)
-            };
+    if changed == 0 {
+        let embed = CreateEmbed::default()
+            .title("Forex Ping")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let description = match &role {
+        Some(role) => format!(
+            "<@&{}> will be pinged for **{}** news or higher in {} channel(s).",
+            role.id,
+            impact.label(),
+            changed
+        ),
+        None => format!("Role pings turned off for {} channel(s).", changed),
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex Ping Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Set the language DailyForex analysis is translated to
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn forex_language(
+    ctx: Context<'_>,
+    #[description = "Language code (e.g. id, en, pt-BR) or off"] language: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let Some(language) = normalize_language(&language) else {
+        ctx.say("Invalid language. Use a code like `id`, `en` or `pt-BR`, or `off`.")
+            .await?;
+        return Ok(());
+    };
 
+    let pool = ctx.data().db.as_ref();
+    if !ForexRepository::set_language(pool, guild_id, &language).await? {
+        let embed = CreateEmbed::default()
+            .title("Forex Language")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let description = if language == "off" {
+        "Translation turned off. News is sent in its original language.".to_string()
+    } else {
+        format!("DailyForex analysis will be translated to `{}`.", language)
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex Language Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Classify news impact with Gemini instead of keywords
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn forex_ai_impact(
+    ctx: Context<'_>,
+    #[description = "Use Gemini for impact levels (leave empty to follow the bot default)"]
+    enabled: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    if !ForexRepository::set_ai_impact(pool, guild_id, enabled).await? {
+        let embed = CreateEmbed::default()
+            .title("Forex AI Impact")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let description = match enabled {
+        Some(true) => {
+            "News impact is now rated by Gemini, with the reason shown in the footer. \
+            Keyword rules are used if Gemini is unavailable."
+        }
+        Some(false) => "News impact is now rated with keyword rules.",
+        None => "This server now follows the bot's default impact rating.",
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex AI Impact Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Post news into one thread per currency under each forex channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn forex_threads(
+    ctx: Context<'_>,
+    #[description = "Group news into per-currency threads"] enabled: bool,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    if !ForexRepository::set_use_threads(pool, guild_id, enabled).await? {
+        let embed = CreateEmbed::default()
+            .title("Forex Threads")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let description = if enabled {
+        "News will be posted in a thread per currency (e.g. `XAU/USD`). \
+        High impact news is also posted in the main channel. \
+        The bot needs the Create Public Threads permission."
+    } else {
+        "News will be posted directly in the forex channel."
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex Threads Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// "off" or a language code like "id" or "pt-BR"
+fn normalize_language(input: &str) -> Option<String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("off") {
+        return Some("off".to_string());
+    }
+
+    let mut parts = input.splitn(2, ['-', '_']);
+    let lang = parts.next()?;
+    if !(2..=3).contains(&lang.len()) || !lang.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    match parts.next() {
+        None => Some(lang.to_ascii_lowercase()),
+        Some(region)
+            if (2..=4).contains(&region.len())
+                && region.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            Some(format!(
+                "{}-{}",
+                lang.to_ascii_lowercase(),
+                region.to_ascii_uppercase()
+            ))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Show the most recently sent forex news
+#[poise::command(slash_command, prefix_command)]
+pub async fn forex_latest(
+    ctx: Context<'_>,
+    #[description = "Number of items (default 5, max 15)"]
+    #[min = 1]
+    #[max = 15]
+    count: Option<u8>,
+    #[description = "Only show news mentioning this currency (e.g. XAU)"] currency: Option<String>,
+) -> Result<(), Error> {
+    let count = count.unwrap_or(5).clamp(1, 15);
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+
+    let pool = ctx.data().db.as_ref();
+    let news = ForexRepository::get_recent_news(pool, count as i64, currency.as_deref()).await?;
+
+    let title = match &currency {
+        Some(currency) => format!("Latest Forex News • {}", currency),
+        None => "Latest Forex News".to_string(),
+    };
+
+    let mut pages: Vec<CreateEmbed> = news_pages(&news, news.len().max(1))
+        .into_iter()
+        .map(|description| {
+            CreateEmbed::default()
+                .title(&title)
+                .description(description)
+                .color(serenity::Colour::from_rgb(0, 150, 136))
+                .timestamp(Timestamp::now())
+        })
+        .collect();
+
+    match pages.len() {
+        0 => {
+            let embed = CreateEmbed::default()
+                .title(title)
+                .description("No news has been sent yet.")
+                .color(serenity::Colour::from_rgb(0, 150, 136))
+                .timestamp(Timestamp::now());
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        }
+        // Long headlines only spill onto more pages when they don't fit in one embed
+        1 => {
+            ctx.send(poise::CreateReply::default().embed(pages.remove(0)))
+                .await?;
+        }
+        _ => paginate(ctx, pages, 120).await?,
+    }
+    Ok(())
+}
+
+const SEARCH_DAYS: i64 = 7;
+const SEARCH_LIMIT: i64 = 50;
+const SEARCH_PAGE_SIZE: usize = 10;
+/// Discord's limit for an embed description
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+/// Search forex news sent in the last 7 days
+#[poise::command(slash_command, prefix_command)]
+pub async fn forex_search(
+    ctx: Context<'_>,
+    #[description = "Word in the headline or currency (e.g. CPI, XAU)"] keyword: String,
+    #[description = "Only show this impact level"] impact: Option<ImpactChoice>,
+) -> Result<(), Error> {
+    let keyword = keyword.trim().to_string();
+    if keyword.is_empty() {
+        ctx.say("Please enter a keyword to search for.").await?;
+        return Ok(());
+    }
+
+    let impact = impact.map(Impact::from);
+    let pool = ctx.data().db.as_ref();
+    let news = ForexRepository::search_news(
+        pool,
+        &keyword,
+        impact.map(|i| i.key()),
+        SEARCH_DAYS,
+        SEARCH_LIMIT,
+    )
+    .await?;
+
+    let mut title = format!("Forex News Search • {}", keyword);
+    if let Some(impact) = impact {
+        title = format!("{} • {}", title, impact.label());
+    }
+
+    if news.is_empty() {
+        let embed = CreateEmbed::default()
+            .title(title)
+            .description(format!(
+                "No news matching `{}` was sent in the last {} days.",
+                keyword, SEARCH_DAYS
+            ))
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let title = format!("{} ({} found)", title, news.len());
+    let pages = news_pages(&news, SEARCH_PAGE_SIZE)
+        .into_iter()
+        .map(|description| {
             CreateEmbed::default()
-                .title("Forex News Status")
-                .field("Status", status, true)
-                .field("Channel", format!("<#{}>", ch.channel_id), true)
-                .color(color)
+                .title(&title)
+                .description(description)
+                .color(serenity::Colour::from_rgb(0, 150, 136))
                 .timestamp(Timestamp::now())
+        })
+        .collect();
+
+    paginate(ctx, pages, 120).await?;
+    Ok(())
+}
+
+/// News lines joined into page descriptions of at most `per_page` items,
+/// starting a new page early when the next line would pass the embed limit
+fn news_pages(news: &[SentNews], per_page: usize) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut items = 0;
+    for line in news.iter().map(news_line) {
+        let separator = if page.is_empty() { 0 } else { 2 };
+        let too_long =
+            page.chars().count() + separator + line.chars().count() > EMBED_DESCRIPTION_LIMIT;
+        if items > 0 && (items == per_page || too_long) {
+            pages.push(std::mem::take(&mut page));
+            items = 0;
+        }
+        if !page.is_empty() {
+            page.push_str("\n\n");
         }
-        None => CreateEmbed::default()
+        page.push_str(&line);
+        items += 1;
+    }
+    if !page.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+/// One sent news item as shown by `/forex_latest` and `/forex_search`
+fn news_line(item: &SentNews) -> String {
+    let impact = Impact::from_key(item.impact.as_deref().unwrap_or("low"));
+    let mut headline: String = item.title.clone().unwrap_or_default();
+    if headline.chars().count() > 120 {
+        headline = format!("{}...", headline.chars().take(117).collect::<String>());
+    }
+    let headline = match &item.link {
+        Some(link) => format!("[{}]({})", headline, link),
+        None => headline,
+    };
+    let currency = item
+        .currency
+        .as_deref()
+        .map(|c| format!("`{}` • ", c))
+        .unwrap_or_default();
+    format!(
+        "{} {}\n└ {}{} • <t:{}:f> (<t:{}:R>)",
+        impact.bar(),
+        headline,
+        currency,
+        item.source,
+        item.sent_at,
+        item.sent_at
+    )
+}
+
+/// Check forex news status
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn forex_status(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let channels = ForexRepository::get_channels(pool, guild_id).await?;
+
+    let embed = if channels.is_empty() {
+        CreateEmbed::default()
             .title("Forex News Status")
             .description("Not configured. Use `/forex_setup` to enable.")
             .color(serenity::Colour::from_rgb(158, 158, 158))
-            .timestamp(Timestamp::now()),
+            .timestamp(Timestamp::now())
+    } else {
+        let color = if channels.iter().any(|ch| ch.is_active) {
+            serenity::Colour::from_rgb(0, 150, 136)
+        } else {
+            serenity::Colour::from_rgb(158, 158, 158)
+        };
+
+        let mut embed = CreateEmbed::default()
+            .title("Forex News Status")
+            .field("Market", market_status(), false)
+            .color(color)
+            .timestamp(Timestamp::now());
+
+        for ch in channels.iter().take(20) {
+            let status = if ch.is_active { "Active" } else { "Disabled" };
+            let mut details = vec![
+                status.to_string(),
+                format!("Min impact: {}", Impact::from_key(&ch.min_impact).label()),
+                format!("Language: `{}`", ch.language),
+            ];
+            if ch.use_threads {
+                details.push("Threads: on".to_string());
+            }
+            match ch.ai_impact {
+                Some(true) => details.push("AI impact: on".to_string()),
+                Some(false) => details.push("AI impact: off".to_string()),
+                None => {}
+            }
+            if let Some(source) = &ch.source {
+                details.push(format!("Source: `{}`", source));
+            }
+            if let Some(currency) = &ch.currency {
+                details.push(format!("Currency: `{}`", currency));
+            }
+            if let Some(role_id) = ch.ping_role_id {
+                details.push(format!(
+                    "Ping: <@&{}> ({}+)",
+                    role_id,
+                    Impact::from_key(&ch.ping_min_impact).label()
+                ));
+            }
+            embed = embed.field(
+                format!("#{}", ch.id),
+                format!("<#{}>\n{}", ch.channel_id, details.join(" • ")),
+                false,
+            );
+        }
+
+        embed
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show per-feed fetch state for the forex news monitor
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn forex_debug(ctx: Context<'_>) -> Result<(), Error> {
+    let feeds = feed_status();
+
+    let mut embed = CreateEmbed::default()
+        .title("Forex Feed Debug")
+        .color(serenity::Colour::BLUE)
+        .timestamp(Timestamp::now());
+
+    if feeds.is_empty() {
+        embed = embed.description("No feed has been fetched yet");
+    }
+
+    for feed in &feeds {
+        let indicator = match feed.consecutive_failures {
+            0 => "🟢",
+            1..=2 => "🟡",
+            _ => "🔴",
+        };
+        let last_success = feed
+            .last_success
+            .map(|t| format_ago(t.elapsed()))
+            .unwrap_or_else(|| "never".to_string());
+        let last_status = feed
+            .last_status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "no response".to_string());
+        let backoff = match feed.retry_at {
+            Some(t) if t > std::time::Instant::now() => {
+                format!("retry in {}s", (t - std::time::Instant::now()).as_secs())
+            }
+            _ => "none".to_string(),
+        };
+
+        embed = embed.field(
+            format!("{} {}", indicator, feed.source),
+            format!(
+                "Last success: {}\nLast status: {}\nFailures in a row: {}\nBackoff: {}\n304 responses: {}",
+                last_success,
+                last_status,
+                feed.consecutive_failures,
+                backoff,
+                feed.not_modified_count
+            ),
+            false,
+        );
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Whether news fetching is paused for the weekend, and until when
+fn market_status() -> String {
+    let hours = MarketHours::from_env();
+    let now = chrono::Utc::now();
+    if hours.is_closed(now) {
+        format!("Closed, resumes <t:{}:R>", hours.next_open(now).timestamp())
+    } else {
+        "Open".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ImpactChoice {
+    #[name = "low"]
+    Low,
+    #[name = "medium"]
+    Medium,
+    #[name = "high"]
+    High,
+}
+
+impl From<ImpactChoice> for Impact {
+    fn from(choice: ImpactChoice) -> Self {
+        match choice {
+            ImpactChoice::Low => Impact::Low,
+            ImpactChoice::Medium => Impact::Medium,
+            ImpactChoice::High => Impact::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum SourceChoice {
+    #[name = "FXStreet"]
+    FxStreet,
+    #[name = "FXStreet Analysis"]
+    FxStreetAnalysis,
+    #[name = "DailyForex"]
+    DailyForex,
+    #[name = "WSJ World News"]
+    WsjWorld,
+    #[name = "WSJ Markets"]
+    WsjMarkets,
+}
+
+impl SourceChoice {
+    /// Matches `ForexNews::source_key`
+    pub fn key(&self) -> &'static str {
+        match self {
+            SourceChoice::FxStreet => "fxstreet",
+            SourceChoice::FxStreetAnalysis => "fxstreet_analysis",
+            SourceChoice::DailyForex => "dailyforex",
+            SourceChoice::WsjWorld => "wsj_world",
+            SourceChoice::WsjMarkets => "wsj_markets",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum FilterKind {
+    #[name = "include"]
+    Include,
+    #[name = "exclude"]
+    Exclude,
+}
+
+impl FilterKind {
+    fn key(&self) -> &'static str {
+        match self {
+            FilterKind::Include => "include",
+            FilterKind::Exclude => "exclude",
+        }
+    }
+}
+
+/// Configure which forex news this server receives
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands(
+        "forex_filter_impact",
+        "forex_filter_add",
+        "forex_filter_remove",
+        "forex_filter_list"
+    ),
+    subcommand_required
+)]
+pub async fn forex_filter(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Only receive forex news at or above an impact level
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "impact"
+)]
+pub async fn forex_filter_impact(
+    ctx: Context<'_>,
+    #[description = "Minimum impact to send"] impact: ImpactChoice,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let impact = Impact::from(impact);
+
+    let pool = ctx.data().db.as_ref();
+    let updated = ForexRepository::set_min_impact(pool, guild_id, impact.key()).await?;
+
+    let embed = if updated {
+        CreateEmbed::default()
+            .title("Forex Filter Updated")
+            .description(format!(
+                "Only news with **{}** or higher will be sent.",
+                impact.label()
+            ))
+            .color(impact.color())
+            .timestamp(Timestamp::now())
+    } else {
+        CreateEmbed::default()
+            .title("Forex Filter")
+            .description("Not configured. Use `/forex_setup` first.")
+            .color(serenity::Colour::from_rgb(158, 158, 158))
+            .timestamp(Timestamp::now())
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Include or exclude news by currency (XAU, EUR/USD) or keyword
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "add"
+)]
+pub async fn forex_filter_add(
+    ctx: Context<'_>,
+    #[description = "include or exclude"] kind: FilterKind,
+    #[description = "Currency or keyword"]
+    #[rest]
+    value: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let value = value.trim().to_uppercase();
+
+    let pool = ctx.data().db.as_ref();
+    let added = ForexRepository::add_filter(pool, guild_id, kind.key(), &value).await?;
+
+    let description = if added {
+        format!("Added `{}` to the **{}** list.", value, kind.key())
+    } else {
+        format!("`{}` is already in the **{}** list.", value, kind.key())
     };
 
+    let embed = CreateEmbed::default()
+        .title("Forex Filter Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
-/// Get current high impact forex events
+/// Remove an include/exclude filter
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn forex_filter_remove(
+    ctx: Context<'_>,
+    #[description = "include or exclude"] kind: FilterKind,
+    #[description = "Currency or keyword"]
+    #[rest]
+    value: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let value = value.trim().to_uppercase();
+
+    let pool = ctx.data().db.as_ref();
+    let removed = ForexRepository::remove_filter(pool, guild_id, kind.key(), &value).await?;
+
+    let description = if removed {
+        format!("Removed `{}` from the **{}** list.", value, kind.key())
+    } else {
+        format!("`{}` is not in the **{}** list.", value, kind.key())
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex Filter Updated")
+        .description(description)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show this server's forex filters
+#[poise::command(slash_command, prefix_command, guild_only, rename = "list")]
+pub async fn forex_filter_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let channels = ForexRepository::get_channels(pool, guild_id).await?;
+    let filters = ForexRepository::get_filters(pool, guild_id).await?;
+
+    let format_list = |kind: &str| {
+        let values: Vec<String> = filters
+            .iter()
+            .filter(|f| f.kind == kind)
+            .map(|f| format!("`{}`", f.value))
+            .collect();
+        if values.is_empty() {
+            "—".to_string()
+        } else {
+            values.join(" ")
+        }
+    };
+
+    let min_impact = if channels.is_empty() {
+        Impact::Low.label().to_string()
+    } else {
+        channels
+            .iter()
+            .map(|ch| {
+                format!(
+                    "<#{}> {}",
+                    ch.channel_id,
+                    Impact::from_key(&ch.min_impact).label()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = CreateEmbed::default()
+        .title("Forex Filters")
+        .field("Min Impact", min_impact, false)
+        .field("Include", format_list("include"), false)
+        .field("Exclude", format_list("exclude"), false)
+        .color(serenity::Colour::from_rgb(0, 150, 136))
+        .footer(CreateEmbedFooter::new(
+            "Excludes always win; an empty include list allows everything",
+        ))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Parse `today`, `tomorrow` or `YYYY-MM-DD` into a date and the phrase used in replies
+fn parse_calendar_day(
+    input: &str,
+    today: chrono::NaiveDate,
+) -> Option<(chrono::NaiveDate, String)> {
+    match input.trim().to_lowercase().as_str() {
+        "today" => Some((today, "today".to_string())),
+        "tomorrow" => Some((today.succ_opt()?, "tomorrow".to_string())),
+        other => {
+            let date = chrono::NaiveDate::parse_from_str(other, "%Y-%m-%d").ok()?;
+            Some((date, format!("on {}", date.format("%a %d %b %Y"))))
+        }
+    }
+}
+
+/// Get this week's forex events, filtered by day, impact and currency
 #[poise::command(slash_command, prefix_command, aliases("calendar"))]
-pub async fn forex_calendar(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn forex_calendar(
+    ctx: Context<'_>,
+    #[description = "Only show events for this currency (e.g. USD)"] currency: Option<String>,
+    #[description = "today, tomorrow or YYYY-MM-DD (default: this week)"] day: Option<String>,
+    #[description = "Impact level (default: high)"] impact: Option<ImpactChoice>,
+) -> Result<(), Error> {
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    let impact = impact.map(Impact::from).unwrap_or(Impact::High);
+
+    let today = chrono::Utc::now()
+        .with_timezone(&chrono_tz::Asia::Jakarta)
+        .date_naive();
+    let day = match day.as_deref().map(|d| parse_calendar_day(d, today)) {
+        Some(Some(day)) => Some(day),
+        Some(None) => {
+            let embed = CreateEmbed::default()
+                .title("Invalid Day")
+                .description("Use `today`, `tomorrow` or a date like `2024-12-25`")
+                .color(serenity::Colour::RED);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
+        }
+        None => None,
+    };
+
     ctx.defer().await?;
 
     let client = reqwest::Client::builder()
@@ -136,72 +969,87 @@ pub async fn forex_calendar(ctx: Context<'_>) -> Result<(), Error> {
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
         .build()?;
 
-    // Try multiple sources
-    let mut high_impact_events = Vec::new();
-
-    // Source 1: Forex Factory JSON feed
-    if let Ok(response) = client
-        .get("https://nfs.faireconomy.media/ff_calendar_thisweek.json")
-        .send()
-        .await
-    {
-        if let Ok(body) = response.text().await {
-            if let Ok(events) = serde_json::from_str::<serde_json::Value>(&body) {
-                if let Some(arr) = events.as_array() {
-                    for event in arr {
-                        let impact = event["impact"].as_str().unwrap_or_default();
-                        if impact.to_lowercase().contains("high") || impact.to_lowercase() == "red"
-                        {
-                            let title = event["title"].as_str().unwrap_or_default();
-                            let country = event["country"].as_str().unwrap_or_default();
-                            let date = event["date"].as_str().unwrap_or_default();
-                            let forecast = event["forecast"].as_str().unwrap_or_default();
-                            let previous = event["previous"].as_str().unwrap_or_default();
-
-                            let currency = match country.to_uppercase().as_str() {
-                                "USD" => "USD",
-                                "EUR" => "EUR",
-                                "GBP" => "GBP",
-                                "JPY" => "JPY",
-                                "CHF" => "CHF",
-                                "AUD" => "AUD",
-                                "NZD" => "NZD",
-                                "CAD" => "CAD",
-                                "CNY" => "CNY",
-                                _ => country,
-                            };
-
-                            high_impact_events.push(format!(
-                                "**{}**  `{}`\n{}\nForecast: `{}` | Previous: `{}`",
-                                currency,
-                                date,
-                                title,
-                                if forecast.is_empty() { "—" } else { forecast },
-                                if previous.is_empty() { "—" } else { previous }
-                            ));
-
-                            if high_impact_events.len() >= 10 {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+    let events = match cached_calendar(&ctx.data().calendar_cache, &client).await {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("[CALENDAR] Failed to fetch calendar: {}", e);
+            let embed = CreateEmbed::default()
+                .title("Calendar Unavailable")
+                .description(
+                    "Couldn't load the calendar from Forex Factory. Please try again later, or \
+                    visit [Forex Factory](https://www.forexfactory.com/calendar) directly.",
+                )
+                .color(serenity::Colour::RED);
+            ctx.send(poise::CreateReply::default().embed(embed)).await?;
+            return Ok(());
         }
-    }
+    };
+
+    let matching: Vec<String> = events
+        .iter()
+        .filter(|e| e.impact_level() == Some(impact))
+        .filter(|e| {
+            currency
+                .as_ref()
+                .is_none_or(|c| e.country.eq_ignore_ascii_case(c))
+        })
+        .filter(|e| day.as_ref().is_none_or(|(date, _)| e.date_wib() == *date))
+        .take(10)
+        .map(|event| {
+            format!(
+                "**{}**  `{}`\n{}\nForecast: `{}` | Previous: `{}`",
+                event.country.to_uppercase(),
+                event.time_wib(),
+                event.title,
+                if event.forecast.is_empty() {
+                    "—"
+                } else {
+                    &event.forecast
+                },
+                if event.previous.is_empty() {
+                    "—"
+                } else {
+                    &event.previous
+                }
+            )
+        })
+        .collect();
 
-    // If no events found, show message
-    let description = if high_impact_events.is_empty() {
-        "No high impact events scheduled.\n\nCheck back later or visit [Forex Factory](https://www.forexfactory.com/calendar) for the full calendar.".to_string()
+    let impact_name = match impact {
+        Impact::High => "high",
+        Impact::Medium => "medium",
+        Impact::Low => "low",
+    };
+    let day_label = day
+        .as_ref()
+        .map(|(_, label)| label.as_str())
+        .unwrap_or("this week");
+    let description = if matching.is_empty() {
+        let currency = currency
+            .as_ref()
+            .map(|c| format!("{} ", c))
+            .unwrap_or_default();
+        format!(
+            "No {} impact {}events {}.\n\nVisit [Forex Factory](https://www.forexfactory.com/calendar) for the full calendar.",
+            impact_name, currency, day_label
+        )
     } else {
-        high_impact_events.join("\n\n")
+        matching.join("\n\n")
     };
 
+    let mut title = format!("{} FOREX CALENDAR", impact.label());
+    if let Some(currency) = &currency {
+        title.push_str(&format!(" • {}", currency));
+    }
+    title.push_str(&format!(" • {}", day_label.to_uppercase()));
+
     let embed = CreateEmbed::default()
-        .title("HIGH IMPACT FOREX CALENDAR")
+        .title(title)
         .description(description)
-        .color(serenity::Colour::from_rgb(220, 53, 69))
-        .footer(CreateEmbedFooter::new("Source: Forex Factory"))
+        .color(impact.color())
+        .footer(CreateEmbedFooter::new(
+            "Source: Forex Factory • Times in WIB • Reminders are posted 30 minutes before each high impact event",
+        ))
         .timestamp(Timestamp::now());
 
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
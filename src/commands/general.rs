@@ -1,5 +1,17 @@
 use chrono::{Duration, Utc};
-use poise::serenity_prelude::{self as serenity, GetMessages};
+use crate::repository::{
+    BirthdayRepository, ChatHistoryRepository, CryptoAlertRepository, ExportRepository,
+    LevelRepository, ModerationRepository, PriceAlertRepository, ReminderRepository,
+    WatchlistRepository,
+};
+use crate::services::tiingo::get_global_tiingo;
+use crate::utils::time::parse_timezone;
+use base64::Engine;
+use parking_lot::Mutex;
+use poise::serenity_prelude::{self as serenity, CreateAttachment, GetMessages};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Instant;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
@@ -90,4 +102,1057 @@ pub async fn purge(
     let _ = embed_msg.delete(ctx).await;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Record a `/delete_my_data` run for the bot owners: in `ERROR_LOG_CHANNEL`
+/// when set, otherwise by DM. Only the user id is logged
+async fn log_data_deletion(ctx: Context<'_>, user_id: u64) {
+    println!("[PRIVACY] User {} deleted their stored data", user_id);
+
+    let embed = serenity::CreateEmbed::default()
+        .title("[PRIVACY] User Data Deleted")
+        .field("User ID", format!("`{}`", user_id), true)
+        .color(0xED4245)
+        .timestamp(serenity::Timestamp::now());
+
+    if let Some(channel_id) = ctx.data().error_log_channel {
+        if let Err(e) = channel_id
+            .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+            .await
+        {
+            eprintln!("[PRIVACY] Failed to log data deletion: {}", e);
+        }
+        return;
+    }
+
+    for owner in &ctx.data().owners {
+        if let Err(e) = owner
+            .direct_message(ctx.http(), serenity::CreateMessage::new().embed(embed.clone()))
+            .await
+        {
+            eprintln!("[PRIVACY] Failed to DM data deletion to owner {}: {}", owner, e);
+        }
+    }
+}
+
+/// Hapus semua data kamu yang disimpan bot
+#[poise::command(slash_command, prefix_command)]
+pub async fn delete_my_data(ctx: Context<'_>) -> Result<(), Error> {
+    let user_id = ctx.author().id.get();
+    let confirm_id = format!("{}_delete_confirm", ctx.id());
+    let cancel_id = format!("{}_delete_cancel", ctx.id());
+
+    let buttons = serenity::CreateActionRow::Buttons(vec![
+        serenity::CreateButton::new(&confirm_id)
+            .label("Hapus data saya")
+            .style(serenity::ButtonStyle::Danger),
+        serenity::CreateButton::new(&cancel_id)
+            .label("Batal")
+            .style(serenity::ButtonStyle::Secondary),
+    ]);
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(
+                serenity::CreateEmbed::default()
+                    .title("Hapus Data")
+                    .description(
+                        "Ini akan menghapus reminder, peringatan (warnings), watchlist, \
+                        price alert, XP, ulang tahun, dan riwayat chat AI milik kamu secara \
+                        permanen.\n\n\
+                        Lanjutkan?",
+                    )
+                    .color(0xED4245),
+            )
+            .components(vec![buttons])
+            .ephemeral(true),
+    )
+    .await?;
+
+    let confirm_filter = confirm_id.clone();
+    let cancel_filter = cancel_id.clone();
+    let interaction = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(std::time::Duration::from_secs(60))
+        .filter(move |mci| {
+            mci.data.custom_id == confirm_filter || mci.data.custom_id == cancel_filter
+        })
+        .await;
+
+    let Some(interaction) = interaction else {
+        return Ok(());
+    };
+
+    if interaction.data.custom_id == cancel_id {
+        interaction
+            .create_response(
+                ctx,
+                serenity::CreateInteractionResponse::UpdateMessage(
+                    serenity::CreateInteractionResponseMessage::new()
+                        .embed(
+                            serenity::CreateEmbed::default()
+                                .title("Dibatalkan")
+                                .description("Tidak ada data yang dihapus.")
+                                .color(0x808080),
+                        )
+                        .components(vec![]),
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // One transaction, so a failure can't leave the data half deleted
+    let mut tx = ctx.data().db.begin().await?;
+    let reminders = ReminderRepository::delete_user_reminders(&mut tx, user_id).await?;
+    let warnings = ModerationRepository::delete_user_warnings(&mut tx, user_id).await?;
+    let watchlist = WatchlistRepository::clear(&mut tx, user_id).await?;
+    let xp = LevelRepository::delete_user(&mut tx, user_id).await?;
+    let birthdays = BirthdayRepository::delete_user(&mut tx, user_id).await?;
+    let chat_messages = ChatHistoryRepository::delete_user(&mut tx, user_id).await?;
+    let crypto_alerts = CryptoAlertRepository::delete_user(&mut tx, user_id).await?;
+    let saved_alerts = PriceAlertRepository::delete_user(&mut tx, user_id).await?;
+    tx.commit().await?;
+
+    let alerts = saved_alerts as usize
+        + get_global_tiingo()
+            .map(|t| t.remove_user_alerts(user_id))
+            .unwrap_or(0);
+
+    log_data_deletion(ctx, user_id).await;
+
+    let embed = serenity::CreateEmbed::default()
+        .title("Data Dihapus")
+        .description("Data kamu telah dihapus dari bot.")
+        .field("Reminders", reminders.to_string(), true)
+        .field("Warnings", warnings.to_string(), true)
+        .field("Watchlist", watchlist.to_string(), true)
+        .field("Price Alerts", alerts.to_string(), true)
+        .field("Crypto Alerts", crypto_alerts.to_string(), true)
+        .field("XP", xp.to_string(), true)
+        .field("Birthdays", birthdays.to_string(), true)
+        .field("AI Chat", chat_messages.to_string(), true)
+        .color(0x57F287);
+
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Hapus pesan bot di channel ini
+#[poise::command(prefix_command, slash_command, guild_only, aliases("clean"))]
+pub async fn cleanup(
+    ctx: Context<'_>,
+    #[description = "Jumlah pesan terakhir yang dicek (1-100, default 50)"]
+    #[min = 1]
+    #[max = 100]
+    amount: Option<u8>,
+) -> Result<(), Error> {
+    let channel_id = ctx.channel_id();
+    let bot_id = ctx.framework().bot_id;
+
+    ctx.defer_ephemeral().await?;
+
+    let messages = channel_id
+        .messages(&ctx.http(), GetMessages::new().limit(amount.unwrap_or(50)))
+        .await?;
+
+    let fourteen_days_ago = Utc::now() - Duration::days(14);
+    let (recent_messages, old_messages): (Vec<_>, Vec<_>) = messages
+        .iter()
+        .filter(|msg| msg.author.id == bot_id)
+        .partition(|msg| msg.timestamp.to_utc() > fourteen_days_ago);
+
+    let mut removed = 0;
+
+    // Bulk delete needs Manage Messages; fall back to deleting one by one
+    let mut single_delete: Vec<serenity::MessageId> = old_messages.iter().map(|m| m.id).collect();
+    if recent_messages.len() > 1 {
+        match channel_id
+            .delete_messages(&ctx.http(), recent_messages.iter().map(|m| m.id))
+            .await
+        {
+            Ok(()) => removed += recent_messages.len(),
+            Err(_) => single_delete.extend(recent_messages.iter().map(|m| m.id)),
+        }
+    } else {
+        single_delete.extend(recent_messages.iter().map(|m| m.id));
+    }
+
+    for msg_id in single_delete {
+        match channel_id.delete_message(&ctx.http(), msg_id).await {
+            Ok(()) => removed += 1,
+            Err(e) => eprintln!("Gagal menghapus pesan {}: {}", msg_id, e),
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    }
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(
+                serenity::CreateEmbed::default()
+                    .title("Berhasil")
+                    .description(format!("✅ Berhasil menghapus {} pesan bot.", removed))
+                    .color(0x57F287),
+            )
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Discord's attachment size limit for bots without boosts
+const ATTACHMENT_LIMIT: usize = 8 * 1024 * 1024;
+/// Discord allows this many files per message
+const MAX_ATTACHMENTS: usize = 10;
+
+/// One file per table. Tables too big for one attachment are split into
+/// numbered parts by rows
+fn table_files(
+    guild_id: u64,
+    exported_at: &str,
+    tables: &[(&'static str, serde_json::Value)],
+) -> Result<Vec<(String, Vec<u8>)>, serde_json::Error> {
+    let part_json = |table: &str, rows: &[serde_json::Value]| {
+        serde_json::to_vec_pretty(&serde_json::json!({
+            "guild_id": guild_id.to_string(),
+            "exported_at": exported_at,
+            "table": table,
+            "rows": rows,
+        }))
+    };
+
+    let mut files = Vec::new();
+    for (table, rows) in tables {
+        let rows = rows.as_array().map(Vec::as_slice).unwrap_or_default();
+        let whole = part_json(table, rows)?;
+        if whole.len() <= ATTACHMENT_LIMIT {
+            files.push((format!("guild_data_{}.json", table), whole));
+            continue;
+        }
+
+        // Leave room for the wrapper object around the rows
+        let budget = ATTACHMENT_LIMIT - 1024;
+        let mut parts: Vec<&[serde_json::Value]> = Vec::new();
+        let (mut start, mut size) = (0, 0);
+        for (i, row) in rows.iter().enumerate() {
+            let row_size = serde_json::to_vec_pretty(row)?.len() + 8;
+            if i > start && size + row_size > budget {
+                parts.push(&rows[start..i]);
+                (start, size) = (i, 0);
+            }
+            size += row_size;
+        }
+        parts.push(&rows[start..]);
+
+        for (n, part) in parts.iter().enumerate() {
+            files.push((
+                format!("guild_data_{}_{}.json", table, n + 1),
+                part_json(table, part)?,
+            ));
+        }
+    }
+    Ok(files)
+}
+
+/// Group files into messages within the attachment count and size limits
+fn attachment_batches(files: Vec<(String, Vec<u8>)>) -> Vec<Vec<CreateAttachment>> {
+    let mut batches: Vec<Vec<CreateAttachment>> = Vec::new();
+    let mut batch_size = 0;
+    for (name, bytes) in files {
+        let full = batches.last().is_none_or(|batch| {
+            batch.len() >= MAX_ATTACHMENTS || batch_size + bytes.len() > ATTACHMENT_LIMIT
+        });
+        if full {
+            batches.push(Vec::new());
+            batch_size = 0;
+        }
+        batch_size += bytes.len();
+        if let Some(batch) = batches.last_mut() {
+            batch.push(CreateAttachment::bytes(bytes, name));
+        }
+    }
+    batches
+}
+
+/// Export everything the bot stores for this server as JSON
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn export_data(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    ctx.defer_ephemeral().await?;
+
+    let pool = ctx.data().db.as_ref();
+    let tables = ExportRepository::guild_data(pool, guild_id).await?;
+    let exported_at = Utc::now().to_rfc3339();
+
+    let export = serde_json::json!({
+        "guild_id": guild_id.to_string(),
+        "exported_at": exported_at,
+        "tables": tables
+            .iter()
+            .map(|(table, rows)| (table.to_string(), rows.clone()))
+            .collect::<serde_json::Map<_, _>>(),
+    });
+    let json = serde_json::to_string_pretty(&export)?;
+
+    // Too big for one file: send one file per table instead, over as many
+    // messages as the attachment limits need
+    let files = if json.len() <= ATTACHMENT_LIMIT {
+        vec![("guild_data.json".to_string(), json.into_bytes())]
+    } else {
+        table_files(guild_id, &exported_at, &tables)?
+    };
+    let mut batches = attachment_batches(files).into_iter();
+
+    let summary = tables
+        .iter()
+        .map(|(table, rows)| {
+            let count = rows.as_array().map(Vec::len).unwrap_or(0);
+            format!("`{}`: {}", table, count)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut reply = poise::CreateReply::default()
+        .embed(
+            serenity::CreateEmbed::default()
+                .title("Server Data Export")
+                .description(format!("Rows stored for this server:\n{}", summary))
+                .color(0x5865F2),
+        )
+        .ephemeral(true);
+    for attachment in batches.next().unwrap_or_default() {
+        reply = reply.attachment(attachment);
+    }
+    ctx.send(reply).await?;
+
+    for batch in batches {
+        let mut reply = poise::CreateReply::default().ephemeral(true);
+        for attachment in batch {
+            reply = reply.attachment(attachment);
+        }
+        ctx.send(reply).await?;
+    }
+
+    Ok(())
+}
+
+/// CSS named colors used for name lookups and the "closest color" field
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("black", 0x000000),
+    ("white", 0xFFFFFF),
+    ("red", 0xFF0000),
+    ("lime", 0x00FF00),
+    ("green", 0x008000),
+    ("blue", 0x0000FF),
+    ("yellow", 0xFFFF00),
+    ("cyan", 0x00FFFF),
+    ("magenta", 0xFF00FF),
+    ("silver", 0xC0C0C0),
+    ("gray", 0x808080),
+    ("maroon", 0x800000),
+    ("olive", 0x808000),
+    ("purple", 0x800080),
+    ("teal", 0x008080),
+    ("navy", 0x000080),
+    ("orange", 0xFFA500),
+    ("pink", 0xFFC0CB),
+    ("brown", 0xA52A2A),
+    ("gold", 0xFFD700),
+    ("indigo", 0x4B0082),
+    ("violet", 0xEE82EE),
+];
+
+/// Parse `#RRGGBB`, `RRGGBB`, `#RGB` or a color name like `red`
+fn parse_color(input: &str) -> Result<u32, String> {
+    let input = input.trim().to_lowercase();
+    if let Some((_, value)) = NAMED_COLORS.iter().find(|(name, _)| *name == input) {
+        return Ok(*value);
+    }
+
+    let clean_hex = input.trim_start_matches('#');
+    let expanded: String = match clean_hex.len() {
+        3 => clean_hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => clean_hex.to_string(),
+        _ => {
+            return Err(format!(
+                "`{}` is not a valid color. Use a hex code like `#5865F2` or `F80`, \
+                or a name like `red`",
+                input
+            ));
+        }
+    };
+
+    u32::from_str_radix(&expanded, 16)
+        .map_err(|_| format!("`{}` contains characters that are not hex digits", input))
+}
+
+fn rgb(color: u32) -> (u8, u8, u8) {
+    ((color >> 16) as u8, (color >> 8) as u8, color as u8)
+}
+
+/// Hue in degrees, saturation and lightness in percent
+fn to_hsl(color: u32) -> (f64, f64, f64) {
+    let (r, g, b) = rgb(color);
+    let (r, g, b) = (r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness * 100.0);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, saturation * 100.0, lightness * 100.0)
+}
+
+fn closest_named_color(color: u32) -> &'static str {
+    let (r, g, b) = rgb(color);
+    NAMED_COLORS
+        .iter()
+        .min_by_key(|(_, value)| {
+            let (nr, ng, nb) = rgb(*value);
+            let dr = r as i32 - nr as i32;
+            let dg = g as i32 - ng as i32;
+            let db = b as i32 - nb as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(name, _)| *name)
+        .unwrap_or("black")
+}
+
+/// Preview a hex color or color name
+#[poise::command(slash_command, prefix_command)]
+pub async fn color(
+    ctx: Context<'_>,
+    #[description = "Hex code (#5865F2, F80) or color name (red, blue, ...)"] value: String,
+) -> Result<(), Error> {
+    let color = parse_color(&value)?;
+    let (r, g, b) = rgb(color);
+    let (h, s, l) = to_hsl(color);
+
+    ctx.send(
+        poise::CreateReply::default().embed(
+            serenity::CreateEmbed::default()
+                .title(format!("#{:06X}", color))
+                .field("RGB", format!("R: {}, G: {}, B: {}", r, g, b), true)
+                .field("HSL", format!("{:.0}°, {:.0}%, {:.0}%", h, s, l), true)
+                .field("Closest Named Color", closest_named_color(color), true)
+                .color(color),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Discord's limits for embed titles and descriptions
+const EMBED_TITLE_LIMIT: usize = 256;
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+/// Slash inputs can't hold line breaks, so this token stands in for one
+const NEWLINE_TOKEN: &str = "{newline}";
+
+fn validate_image_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some() => {
+            Ok(url.to_string())
+        }
+        _ => Err(format!(
+            "`{}` is not a valid image link. It must start with http:// or https://",
+            url
+        )),
+    }
+}
+
+/// Post a custom embed, e.g. for announcements
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn embed(
+    ctx: Context<'_>,
+    #[description = "Embed text, use {newline} for a line break"] description: String,
+    #[description = "Embed title"] title: Option<String>,
+    #[description = "Hex code (#5865F2) or color name"] color: Option<String>,
+    #[description = "Image link (http:// or https://)"] image: Option<String>,
+    #[description = "Channel to post in (default: this one)"]
+    channel: Option<serenity::GuildChannel>,
+) -> Result<(), Error> {
+    let description = description.replace(NEWLINE_TOKEN, "\n");
+    if description.trim().is_empty() {
+        return Err("The description can't be empty".into());
+    }
+    if description.chars().count() > EMBED_DESCRIPTION_LIMIT {
+        return Err(format!(
+            "The description is limited to {} characters",
+            EMBED_DESCRIPTION_LIMIT
+        )
+        .into());
+    }
+    if title
+        .as_ref()
+        .is_some_and(|t| t.chars().count() > EMBED_TITLE_LIMIT)
+    {
+        return Err(format!("The title is limited to {} characters", EMBED_TITLE_LIMIT).into());
+    }
+    let color = color.as_deref().map(parse_color).transpose()?;
+    let image = image.as_deref().map(validate_image_url).transpose()?;
+
+    let mut embed = serenity::CreateEmbed::default().description(description);
+    if let Some(title) = title {
+        embed = embed.title(title);
+    }
+    if let Some(color) = color {
+        embed = embed.color(color);
+    }
+    if let Some(image) = image {
+        embed = embed.image(image);
+    }
+
+    // The command's own permission check only covers the channel it's run in
+    if let Some(channel) = &channel {
+        let member = ctx
+            .author_member()
+            .await
+            .ok_or("Cannot get member info")?
+            .into_owned();
+        let permissions = {
+            let guild = ctx.guild().ok_or("Cannot get server info")?;
+            guild.user_permissions_in(channel, &member)
+        };
+        let required =
+            serenity::Permissions::SEND_MESSAGES | serenity::Permissions::MANAGE_MESSAGES;
+        if !permissions.contains(required) {
+            return Err(format!(
+                "You need Send Messages and Manage Messages in <#{}> to post there",
+                channel.id
+            )
+            .into());
+        }
+    }
+
+    let channel_id = channel.map(|c| c.id).unwrap_or_else(|| ctx.channel_id());
+    let message = channel_id
+        .send_message(&ctx.http(), serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    ctx.send(
+        poise::CreateReply::default()
+            .content(format!("Embed posted in <#{}>: {}", channel_id, message.link()))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Longest input accepted by `/encode` and `/decode`
+const BASE64_INPUT_LIMIT: usize = 1800;
+
+fn base64_engine(url_safe: bool) -> &'static base64::engine::GeneralPurpose {
+    if url_safe {
+        &base64::engine::general_purpose::URL_SAFE
+    } else {
+        &base64::engine::general_purpose::STANDARD
+    }
+}
+
+/// Encode text as Base64
+#[poise::command(slash_command, prefix_command)]
+pub async fn encode(
+    ctx: Context<'_>,
+    #[description = "Text to encode"] text: String,
+    #[description = "Use the URL-safe alphabet (- and _ instead of + and /)"]
+    url_safe: Option<bool>,
+) -> Result<(), Error> {
+    if text.chars().count() > BASE64_INPUT_LIMIT {
+        return Err(format!("Input is limited to {} characters", BASE64_INPUT_LIMIT).into());
+    }
+
+    let encoded = base64_engine(url_safe.unwrap_or(false)).encode(text.as_bytes());
+    let reply = if encoded.len() > BASE64_INPUT_LIMIT {
+        poise::CreateReply::default()
+            .content("Result is too long for a message, sent as a file.")
+            .attachment(CreateAttachment::bytes(encoded.into_bytes(), "encoded.txt"))
+    } else {
+        poise::CreateReply::default().content(format!("```\n{}\n```", encoded))
+    };
+    ctx.send(reply.ephemeral(true)).await?;
+
+    Ok(())
+}
+
+/// Decode a Base64 string
+#[poise::command(slash_command, prefix_command)]
+pub async fn decode(
+    ctx: Context<'_>,
+    #[description = "Base64 to decode"] data: String,
+    #[description = "Input uses the URL-safe alphabet (- and _ instead of + and /)"]
+    url_safe: Option<bool>,
+) -> Result<(), Error> {
+    if data.chars().count() > BASE64_INPUT_LIMIT {
+        return Err(format!("Input is limited to {} characters", BASE64_INPUT_LIMIT).into());
+    }
+
+    let bytes = base64_engine(url_safe.unwrap_or(false))
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid Base64: {}", e))?;
+
+    let content = match String::from_utf8(bytes) {
+        Ok(text) => format!("```\n{}\n```", text.replace("```", "'''")),
+        Err(e) => {
+            let bytes = e.into_bytes();
+            let preview = bytes
+                .iter()
+                .take(16)
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!(
+                "Binary data ({} bytes), not valid UTF-8.\nFirst bytes: `{}`",
+                bytes.len(),
+                preview
+            )
+        }
+    };
+    ctx.send(poise::CreateReply::default().content(content).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+const SHORTEN_API_URL: &str = "https://tinyurl.com/api-create.php";
+const SHORTEN_LIMIT_PER_HOUR: usize = 10;
+
+/// Recent `/shorten` calls per user, for the hourly limit
+static SHORTEN_USAGE: OnceLock<Mutex<HashMap<serenity::UserId, Vec<Instant>>>> = OnceLock::new();
+/// Original URL -> short URL, so the same link is only shortened once
+static SHORTEN_CACHE: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+/// Record a `/shorten` call, returning false if the user hit the hourly limit
+fn record_shorten_usage(user_id: serenity::UserId) -> bool {
+    let hour = std::time::Duration::from_secs(60 * 60);
+    let mut usage = SHORTEN_USAGE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock();
+    usage.retain(|_, calls| {
+        calls.retain(|t| t.elapsed() < hour);
+        !calls.is_empty()
+    });
+
+    let calls = usage.entry(user_id).or_default();
+    if calls.len() >= SHORTEN_LIMIT_PER_HOUR {
+        return false;
+    }
+    calls.push(Instant::now());
+    true
+}
+
+/// Shorten a link with TinyURL
+#[poise::command(slash_command, prefix_command)]
+pub async fn shorten(
+    ctx: Context<'_>,
+    #[description = "Link to shorten (http:// or https://)"] url: String,
+) -> Result<(), Error> {
+    let url = url.trim().to_string();
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err("The link must start with http:// or https://".into());
+    }
+
+    let cached = SHORTEN_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .get(&url)
+        .cloned();
+
+    let short = match cached {
+        Some(short) => short,
+        None => {
+            if !record_shorten_usage(ctx.author().id) {
+                return Err(format!(
+                    "You can shorten up to {} links per hour. Try again later.",
+                    SHORTEN_LIMIT_PER_HOUR
+                )
+                .into());
+            }
+
+            let client = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()?;
+            let short = client
+                .get(SHORTEN_API_URL)
+                .query(&[("url", &url)])
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?
+                .trim()
+                .to_string();
+            if !short.starts_with("http") {
+                return Err("The URL shortener returned an unexpected response".into());
+            }
+
+            SHORTEN_CACHE
+                .get_or_init(|| Mutex::new(HashMap::new()))
+                .lock()
+                .insert(url.clone(), short.clone());
+            short
+        }
+    };
+
+    let original = if url.chars().count() > 100 {
+        format!("{}…", url.chars().take(100).collect::<String>())
+    } else {
+        url
+    };
+
+    ctx.send(
+        poise::CreateReply::default().embed(
+            serenity::CreateEmbed::default()
+                .title("Link Shortened")
+                .field("Original", original, false)
+                .field("Short", short, false)
+                .footer(serenity::CreateEmbedFooter::new(
+                    "Short links may expire, use them promptly",
+                ))
+                .color(0x5865F2),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Clock time like `14:30` or `14:30:15`
+fn parse_clock_time(input: &str) -> Option<chrono::NaiveTime> {
+    let input = input.trim();
+    chrono::NaiveTime::parse_from_str(input, "%H:%M:%S")
+        .or_else(|_| chrono::NaiveTime::parse_from_str(input, "%H:%M"))
+        .ok()
+}
+
+/// Convert a time between timezones
+#[poise::command(slash_command, prefix_command)]
+pub async fn timezone(
+    ctx: Context<'_>,
+    #[description = "Time as HH:MM or HH:MM:SS (today in the source timezone)"] time: String,
+    #[description = "From timezone (e.g. WIB, EST, Europe/London)"] from_tz: String,
+    #[description = "To timezone (e.g. WIB, PST, Asia/Tokyo)"] to_tz: String,
+) -> Result<(), Error> {
+    use chrono::{Offset, TimeZone};
+
+    let Some(clock) = parse_clock_time(&time) else {
+        return Err("Time must be HH:MM or HH:MM:SS, e.g. 14:30".into());
+    };
+    let from = parse_timezone(&from_tz)
+        .ok_or_else(|| format!("Unknown timezone `{}`", from_tz.trim()))?;
+    let to =
+        parse_timezone(&to_tz).ok_or_else(|| format!("Unknown timezone `{}`", to_tz.trim()))?;
+
+    let today = Utc::now().with_timezone(&from).date_naive();
+    // A clock time skipped by a DST change has no mapping; take the earliest valid one
+    let source = from
+        .from_local_datetime(&today.and_time(clock))
+        .earliest()
+        .ok_or("That time doesn't exist in the source timezone today (DST change)")?;
+    let target = source.with_timezone(&to);
+
+    let day_shift = (target.date_naive() - source.date_naive()).num_days();
+    let shift = match day_shift {
+        0 => String::new(),
+        1 => " (+1 day)".to_string(),
+        -1 => " (-1 day)".to_string(),
+        d => format!(" ({:+} days)", d),
+    };
+
+    let clock_fmt = if time.trim().matches(':').count() == 2 {
+        ("%H:%M:%S", "%I:%M:%S %p")
+    } else {
+        ("%H:%M", "%I:%M %p")
+    };
+    let show = |dt: &chrono::DateTime<chrono_tz::Tz>| {
+        format!(
+            "**{}** • {}\n{}",
+            dt.format(clock_fmt.0),
+            dt.format(clock_fmt.1),
+            dt.format("%a %d %b %Y")
+        )
+    };
+    let offset = |dt: &chrono::DateTime<chrono_tz::Tz>| {
+        let secs = dt.offset().fix().local_minus_utc();
+        format!(
+            "UTC{}{:02}:{:02}",
+            if secs < 0 { '-' } else { '+' },
+            secs.abs() / 3600,
+            secs.abs() % 3600 / 60
+        )
+    };
+
+    ctx.send(
+        poise::CreateReply::default().embed(
+            serenity::CreateEmbed::default()
+                .title("Timezone Conversion")
+                .field(
+                    format!("{} ({})", from.name(), offset(&source)),
+                    show(&source),
+                    true,
+                )
+                .field(
+                    format!("{} ({}){}", to.name(), offset(&target), shift),
+                    show(&target),
+                    true,
+                )
+                .footer(serenity::CreateEmbedFooter::new(
+                    "Offsets are for today, including daylight saving time",
+                ))
+                .color(0x5865F2),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+const URBAN_API_URL: &str = "https://api.urbandictionary.com/v0/define";
+
+#[derive(serde::Deserialize)]
+struct UrbanResponse {
+    list: Vec<UrbanEntry>,
+}
+
+#[derive(serde::Deserialize)]
+struct UrbanEntry {
+    word: String,
+    definition: String,
+    #[serde(default)]
+    example: String,
+    thumbs_up: i64,
+    thumbs_down: i64,
+    author: String,
+    permalink: String,
+}
+
+/// Strip Urban Dictionary's `[word]` link markup and cap the length
+fn urban_text(text: &str, max: usize) -> String {
+    let text = text.replace(['[', ']'], "").replace("\r\n", "\n");
+    if text.chars().count() > max {
+        format!("{}…", text.chars().take(max - 1).collect::<String>())
+    } else {
+        text
+    }
+}
+
+/// Look up a term on Urban Dictionary (NSFW channels only)
+#[poise::command(slash_command, prefix_command)]
+pub async fn define(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "Term to look up"]
+    term: String,
+) -> Result<(), Error> {
+    // DMs are allowed; in servers the channel has to be marked NSFW
+    let nsfw = match ctx.guild_channel().await {
+        Some(channel) => channel.nsfw,
+        None => ctx.guild_id().is_none(),
+    };
+    if !nsfw {
+        ctx.send(
+            poise::CreateReply::default()
+                .embed(
+                    serenity::CreateEmbed::default()
+                        .title("NSFW: true")
+                        .description("Urban Dictionary results can only be shown in NSFW channels.")
+                        .color(0xED4245),
+                )
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+    let response: UrbanResponse = client
+        .get(URBAN_API_URL)
+        .query(&[("term", term.trim())])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let Some(entry) = response
+        .list
+        .into_iter()
+        .max_by_key(|e| e.thumbs_up - e.thumbs_down)
+    else {
+        ctx.send(
+            poise::CreateReply::default().embed(
+                serenity::CreateEmbed::default()
+                    .title("No Results")
+                    .description(format!("No definitions found for **{}**.", term.trim()))
+                    .color(0xED4245),
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    // Embed fields can't be empty
+    let author = match entry.author.trim() {
+        "" => "unknown",
+        author => author,
+    };
+    let mut embed = serenity::CreateEmbed::default()
+        .title(&entry.word)
+        .url(&entry.permalink)
+        .description(urban_text(&entry.definition, 1024))
+        .field(
+            "Votes",
+            format!("👍 {} • 👎 {}", entry.thumbs_up, entry.thumbs_down),
+            true,
+        )
+        .field("Author", author, true)
+        .footer(serenity::CreateEmbedFooter::new("Urban Dictionary"))
+        .color(0x1D2439);
+    if !entry.example.trim().is_empty() {
+        embed = embed.field("Example", urban_text(&entry.example, 512), false);
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// poise has no version constant; keep in sync with Cargo.toml
+const POISE_VERSION: &str = "0.6";
+
+/// Serenity version, taken from the user agent it sends to Discord
+fn serenity_version() -> &'static str {
+    serenity::constants::USER_AGENT
+        .rsplit(", ")
+        .next()
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or("unknown")
+}
+
+/// Show the bot's identity, reach and library versions
+#[poise::command(slash_command, prefix_command)]
+pub async fn botinfo(ctx: Context<'_>) -> Result<(), Error> {
+    let bot = ctx.cache().current_user().clone();
+    let guilds = ctx.cache().guilds().len();
+    let users = ctx.cache().user_count();
+    let uptime = ctx.data().started_at.elapsed().as_secs();
+
+    let embed = serenity::CreateEmbed::default()
+        .title(&bot.name)
+        .thumbnail(bot.face())
+        .field("Servers", guilds.to_string(), true)
+        .field("Cached Users", users.to_string(), true)
+        .field(
+            "Uptime",
+            format!(
+                "{}d {}h {}m",
+                uptime / 86400,
+                (uptime % 86400) / 3600,
+                (uptime % 3600) / 60
+            ),
+            true,
+        )
+        .field("Version", env!("CARGO_PKG_VERSION"), true)
+        .field("Serenity", serenity_version(), true)
+        .field("Poise", POISE_VERSION, true)
+        .footer(serenity::CreateEmbedFooter::new(format!("ID: {}", bot.id)))
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Member breakdown for this server
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn membercount(ctx: Context<'_>) -> Result<(), Error> {
+    let (name, icon, total, cached, humans, bots, online) = {
+        let guild = ctx.guild().ok_or("Cannot get server info")?;
+        let bots = guild.members.values().filter(|m| m.user.bot).count();
+        // Presences are only cached with the GUILD_PRESENCES intent, which
+        // would at least include the bot itself
+        let online = (!guild.presences.is_empty()).then(|| {
+            guild
+                .presences
+                .values()
+                .filter(|p| p.status != serenity::OnlineStatus::Offline)
+                .count()
+        });
+        (
+            guild.name.clone(),
+            guild.icon_url(),
+            guild.member_count,
+            guild.members.len(),
+            guild.members.len() - bots,
+            bots,
+            online,
+        )
+    };
+
+    let mut embed = serenity::CreateEmbed::default()
+        .title(format!("👥 {}", name))
+        .field("Total Members", total.to_string(), true)
+        .field("Humans", humans.to_string(), true)
+        .field("Bots", bots.to_string(), true)
+        .field(
+            "Online",
+            online.map_or_else(|| "Presence data unavailable".to_string(), |n| n.to_string()),
+            true,
+        )
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+    if let Some(icon) = icon {
+        embed = embed.thumbnail(icon);
+    }
+    if (cached as u64) < total {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(format!(
+            "Humans and bots are counted from {} cached members",
+            cached
+        )));
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
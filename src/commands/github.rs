@@ -0,0 +1,92 @@
+use crate::repository::GithubRepository;
+use crate::services::github::{client, latest_release, normalize_repo};
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+/// Post new GitHub releases of a repo in a channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn github_subscribe(
+    ctx: Context<'_>,
+    #[description = "Repository as owner/repo"] repo: String,
+    #[description = "Channel for release posts"]
+    #[channel_types("Text", "News")]
+    channel: serenity::GuildChannel,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let Some(repo) = normalize_repo(&repo) else {
+        let embed = embed::error(
+            "Invalid Repository",
+            "Use the `owner/repo` form, e.g. `rust-lang/rust`",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+    // Start from the current release so subscribing doesn't repost it
+    let latest = latest_release(&client(), &repo).await?;
+    let pool = ctx.data().db.as_ref();
+    GithubRepository::subscribe(
+        pool,
+        guild_id,
+        channel.id.get(),
+        &repo,
+        latest.as_ref().map(|r| r.id.to_string()).as_deref(),
+    )
+    .await?;
+
+    let current = match &latest {
+        Some(release) => format!("Latest release: `{}`", release.tag_name),
+        None => {
+            "No releases found yet. Check the spelling if the repo should have some".to_string()
+        }
+    };
+    let embed = embed::success(
+        "Subscribed",
+        &format!(
+            "New releases of **{}** will be posted in {}\n\n{}",
+            repo, channel, current
+        ),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop posting GitHub releases of a repo
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn github_unsubscribe(
+    ctx: Context<'_>,
+    #[description = "Repository as owner/repo"] repo: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let repo = normalize_repo(&repo).unwrap_or_else(|| repo.trim().to_lowercase());
+
+    let pool = ctx.data().db.as_ref();
+    let embed = if GithubRepository::unsubscribe(pool, guild_id, &repo).await? {
+        embed::success(
+            "Unsubscribed",
+            &format!("Releases of **{}** will no longer be posted", repo),
+        )
+    } else {
+        embed::error(
+            "Not Found",
+            &format!("This server isn't subscribed to **{}**", repo),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use crate::repository::GiveawayRepository;
+use crate::services::giveaway::{GIVEAWAY_EMOJI, announce_winners, draw, entrants};
+use crate::utils::embed;
+use crate::utils::time::parse_duration;
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::Mentionable;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const MAX_DURATION_SECS: u64 = 30 * 24 * 3600;
+/// Keeps the prize short enough for embed titles and announcements
+const MAX_PRIZE_CHARS: usize = 256;
+
+/// Run giveaways in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands("giveaway_start", "giveaway_reroll", "giveaway_winners"),
+    subcommand_required
+)]
+pub async fn giveaway(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Start a giveaway in this channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "start"
+)]
+pub async fn giveaway_start(
+    ctx: Context<'_>,
+    #[description = "Duration (e.g. 30m, 1h, 7d)"] duration: String,
+    #[description = "Number of winners (default 1)"]
+    #[min = 1]
+    #[max = 20]
+    winners: Option<i32>,
+    #[description = "What the winners get"]
+    #[rest]
+    prize: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    if prize.chars().count() > MAX_PRIZE_CHARS {
+        let embed = embed::error(
+            "Prize Too Long",
+            &format!("Keep the prize under {} characters", MAX_PRIZE_CHARS),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(dur) = parse_duration(&duration).filter(|d| d.as_secs() > 0) else {
+        let embed = embed::error("Invalid Duration", "Use a duration like 30m, 1h or 7d");
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+    if dur.as_secs() > MAX_DURATION_SECS {
+        let embed = embed::error("Invalid Duration", "Giveaways can run for at most 30 days");
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    let winner_count = winners.unwrap_or(1).clamp(1, 20);
+    let ends_at = Utc::now().timestamp() + dur.as_secs() as i64;
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("{} Giveaway", GIVEAWAY_EMOJI))
+        .description(format!(
+            "**{}**\n\nReact with {} to enter\nEnds <t:{}:R>\nWinners: **{}**\nHosted by {}",
+            prize,
+            GIVEAWAY_EMOJI,
+            ends_at,
+            winner_count,
+            ctx.author().id.mention()
+        ))
+        .color(0xF1C40F);
+
+    let message = ctx
+        .channel_id()
+        .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+        .await?;
+    message
+        .react(
+            ctx.http(),
+            serenity::ReactionType::Unicode(GIVEAWAY_EMOJI.to_string()),
+        )
+        .await?;
+
+    GiveawayRepository::create(
+        ctx.data().db.as_ref(),
+        message.id.get(),
+        guild_id,
+        ctx.channel_id().get(),
+        ctx.author().id.get(),
+        &prize,
+        winner_count,
+        ends_at,
+    )
+    .await?;
+
+    let embed = embed::success(
+        "Giveaway Started",
+        &format!("Giveaway for **{}** ends <t:{}:R>", prize, ends_at),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
+
+/// Draw a new winner for an ended giveaway
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "reroll"
+)]
+pub async fn giveaway_reroll(
+    ctx: Context<'_>,
+    #[description = "Message ID of the giveaway"] message_id: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let message_id: u64 = message_id
+        .trim()
+        .parse()
+        .map_err(|_| "Message ID must be a number")?;
+
+    let db = ctx.data().db.as_ref();
+    let Some(giveaway) = GiveawayRepository::get(db, guild_id, message_id).await? else {
+        let embed = embed::error(
+            "Not Found",
+            "No giveaway with that message ID in this server",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+    if !giveaway.ended {
+        let embed = embed::error(
+            "Giveaway Running",
+            "Winners are drawn when the giveaway ends",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let entrants = entrants(
+        ctx.http(),
+        serenity::ChannelId::new(giveaway.channel_id as u64),
+        serenity::MessageId::new(message_id),
+    )
+    .await?;
+    let mut winners = giveaway.winners();
+    let Some(winner) = draw(&entrants, 1, &winners).pop() else {
+        let embed = embed::error("Reroll Failed", "No eligible participants remain.");
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    winners.push(winner);
+    GiveawayRepository::set_winners(db, message_id, &winners).await?;
+    announce_winners(ctx.http(), &giveaway, &[winner], true).await?;
+
+    let embed = embed::success(
+        "Giveaway Rerolled",
+        &format!("<@{}> is the new winner of **{}**", winner, giveaway.prize),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// List everyone who has won a giveaway, including rerolls
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "winners"
+)]
+pub async fn giveaway_winners(
+    ctx: Context<'_>,
+    #[description = "Message ID of the giveaway"] message_id: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let message_id: u64 = message_id
+        .trim()
+        .parse()
+        .map_err(|_| "Message ID must be a number")?;
+
+    let Some(giveaway) =
+        GiveawayRepository::get(ctx.data().db.as_ref(), guild_id, message_id).await?
+    else {
+        let embed = embed::error(
+            "Not Found",
+            "No giveaway with that message ID in this server",
+        );
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    };
+
+    let winners = giveaway.winners();
+    let description = if winners.is_empty() {
+        "No winners have been drawn yet".to_string()
+    } else {
+        winners
+            .iter()
+            .enumerate()
+            .map(|(i, id)| format!("{}. <@{}>", i + 1, id))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let embed = embed::info(&format!("Winners: {}", giveaway.prize), &description);
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,217 @@
+use crate::handlers::levels::xp_for_level;
+use crate::repository::LevelRepository;
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const LEADERBOARD_SIZE: i64 = 10;
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+fn progress_bar(current: i64, needed: i64) -> String {
+    let filled = if needed > 0 {
+        ((current as f64 / needed as f64) * PROGRESS_BAR_WIDTH as f64) as usize
+    } else {
+        0
+    }
+    .min(PROGRESS_BAR_WIDTH);
+    format!(
+        "{}{}",
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+/// Show XP, level and progress to the next level
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn rank(
+    ctx: Context<'_>,
+    #[description = "Member to look up (default: you)"] user: Option<serenity::User>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let user = user.as_ref().unwrap_or_else(|| ctx.author());
+
+    let pool = ctx.data().db.as_ref();
+    let Some(stats) = LevelRepository::get_user(pool, guild_id, user.id.get()).await? else {
+        let embed = embed::info(
+            "No XP Yet",
+            &format!("{} hasn't earned any XP in this server yet.", user.name),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+    let position = LevelRepository::get_rank(pool, guild_id, stats.xp).await?;
+
+    let level_start = xp_for_level(stats.level);
+    let next_level = xp_for_level(stats.level + 1);
+    let progress = stats.xp as i64 - level_start;
+    let needed = next_level - level_start;
+
+    let embed = serenity::CreateEmbed::new()
+        .title(format!("🏅 {}", user.display_name()))
+        .thumbnail(user.face())
+        .field("Level", stats.level.to_string(), true)
+        .field("XP", stats.xp.to_string(), true)
+        .field("Rank", format!("#{}", position), true)
+        .field(
+            format!("Progress to level {}", stats.level + 1),
+            format!(
+                "`{}` {}/{} XP",
+                progress_bar(progress, needed),
+                progress,
+                needed
+            ),
+            false,
+        )
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "{} messages",
+            stats.messages
+        )))
+        .color(serenity::Colour::BLUE);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Top members in this server by XP
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn leaderboard(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let users = LevelRepository::get_leaderboard(pool, guild_id, LEADERBOARD_SIZE).await?;
+
+    if users.is_empty() {
+        let embed = embed::info("Leaderboard", "Nobody has earned XP in this server yet.");
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let lines: Vec<String> = users
+        .iter()
+        .enumerate()
+        .map(|(i, u)| {
+            let place = match i {
+                0 => "🥇".to_string(),
+                1 => "🥈".to_string(),
+                2 => "🥉".to_string(),
+                _ => format!("`#{}`", i + 1),
+            };
+            format!(
+                "{} <@{}> • Level **{}** • {} XP",
+                place, u.user_id, u.level, u.xp
+            )
+        })
+        .collect();
+
+    let embed = serenity::CreateEmbed::new()
+        .title("🏆 Leaderboard")
+        .description(lines.join("\n"))
+        .color(serenity::Colour::GOLD)
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Roles granted when members reach a level
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("level_role_set", "level_role_remove", "level_role_list"),
+    subcommand_required
+)]
+pub async fn level_role(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Grant a role when members reach a level
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "set"
+)]
+pub async fn level_role_set(
+    ctx: Context<'_>,
+    #[description = "Level that earns the role"]
+    #[min = 1]
+    #[max = 100]
+    level: i32,
+    #[description = "Role to grant"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    LevelRepository::set_level_role(pool, guild_id, level, role.id.get()).await?;
+
+    let embed = embed::success(
+        "Level Role Set",
+        &format!("Members reaching level **{}** get {}", level, role),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop granting a role for a level
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn level_role_remove(
+    ctx: Context<'_>,
+    #[description = "Level to clear"] level: i32,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let embed = if LevelRepository::delete_level_role(pool, guild_id, level).await? {
+        embed::success(
+            "Level Role Removed",
+            &format!("Level **{}** no longer grants a role", level),
+        )
+    } else {
+        embed::error(
+            "Not Found",
+            &format!("No role is set for level **{}**", level),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// List the level reward roles
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "list"
+)]
+pub async fn level_role_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let roles = LevelRepository::get_level_roles(pool, guild_id).await?;
+
+    let description = if roles.is_empty() {
+        "No level roles yet. Add one with `/level_role set`".to_string()
+    } else {
+        roles
+            .iter()
+            .map(|r| format!("Level **{}** → <@&{}>", r.level, r.role_id))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = embed::info("Level Roles", &description);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,263 @@
+use crate::handlers::link_filter::normalize_domain;
+use crate::repository::LinkFilterRepository;
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const MAX_WHITELIST_DOMAINS: usize = 50;
+
+/// Delete messages with links outside a domain whitelist
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands(
+        "link_filter_enable",
+        "link_filter_disable",
+        "link_filter_whitelist",
+        "link_filter_exempt",
+        "link_filter_status"
+    ),
+    subcommand_required
+)]
+pub async fn link_filter(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Start removing links that aren't whitelisted
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "enable"
+)]
+pub async fn link_filter_enable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    LinkFilterRepository::set_enabled(ctx.data().db.as_ref(), guild_id, true).await?;
+
+    let embed = embed::success(
+        "Link Filter Enabled",
+        "Messages with links outside the whitelist will be deleted. \
+        Add allowed domains with `/link_filter whitelist add`",
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop removing links
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "disable"
+)]
+pub async fn link_filter_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    LinkFilterRepository::set_enabled(ctx.data().db.as_ref(), guild_id, false).await?;
+
+    let embed = embed::success(
+        "Link Filter Disabled",
+        "Links are allowed again. The whitelist is kept",
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Domains that are always allowed
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "whitelist",
+    subcommands("link_filter_whitelist_add", "link_filter_whitelist_remove"),
+    subcommand_required
+)]
+pub async fn link_filter_whitelist(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Allow links to a domain and its subdomains
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "add"
+)]
+pub async fn link_filter_whitelist_add(
+    ctx: Context<'_>,
+    #[description = "Domain (e.g., youtube.com)"] domain: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let Some(domain) = normalize_domain(&domain) else {
+        let embed = embed::error(
+            "Invalid Domain",
+            &format!("`{}` is not a domain like `youtube.com`", domain),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    };
+
+    let pool = ctx.data().db.as_ref();
+    let mut whitelist = LinkFilterRepository::get_config(pool, guild_id)
+        .await?
+        .map(|c| c.whitelist())
+        .unwrap_or_default();
+
+    let embed = if whitelist.contains(&domain) {
+        embed::info(
+            "Already Allowed",
+            &format!("**{}** is already whitelisted", domain),
+        )
+    } else if whitelist.len() >= MAX_WHITELIST_DOMAINS {
+        embed::error(
+            "Whitelist Full",
+            &format!(
+                "The whitelist holds at most {} domains",
+                MAX_WHITELIST_DOMAINS
+            ),
+        )
+    } else {
+        whitelist.push(domain.clone());
+        LinkFilterRepository::set_whitelist(pool, guild_id, &whitelist).await?;
+        embed::success(
+            "Domain Allowed",
+            &format!("Links to **{}** and its subdomains are allowed", domain),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Stop allowing a domain
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn link_filter_whitelist_remove(
+    ctx: Context<'_>,
+    #[description = "Domain to remove"] domain: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let domain = normalize_domain(&domain).unwrap_or_else(|| domain.trim().to_lowercase());
+
+    let pool = ctx.data().db.as_ref();
+    let mut whitelist = LinkFilterRepository::get_config(pool, guild_id)
+        .await?
+        .map(|c| c.whitelist())
+        .unwrap_or_default();
+
+    let embed = if let Some(pos) = whitelist.iter().position(|d| *d == domain) {
+        whitelist.remove(pos);
+        LinkFilterRepository::set_whitelist(pool, guild_id, &whitelist).await?;
+        embed::success(
+            "Domain Removed",
+            &format!("**{}** is no longer whitelisted", domain),
+        )
+    } else {
+        embed::error(
+            "Not Found",
+            &format!("**{}** is not on the whitelist", domain),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Let a role post any link, or take that back
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "exempt"
+)]
+pub async fn link_filter_exempt(
+    ctx: Context<'_>,
+    #[description = "Role to exempt (run again to remove)"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let embed = if LinkFilterRepository::add_exempt_role(pool, guild_id, role.id.get()).await? {
+        embed::success(
+            "Role Exempted",
+            &format!("Members with {} can post any link", role),
+        )
+    } else {
+        LinkFilterRepository::remove_exempt_role(pool, guild_id, role.id.get()).await?;
+        embed::success(
+            "Exemption Removed",
+            &format!("Links from members with {} are filtered again", role),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show the link filter settings
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "status"
+)]
+pub async fn link_filter_status(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let config = LinkFilterRepository::get_config(pool, guild_id).await?;
+    let exempt = LinkFilterRepository::get_exempt_roles(pool, guild_id).await?;
+
+    let enabled = config.as_ref().is_some_and(|c| c.is_enabled);
+    let whitelist = config.map(|c| c.whitelist()).unwrap_or_default();
+
+    let embed = serenity::CreateEmbed::new()
+        .title("🔗 Link Filter")
+        .field(
+            "Status",
+            if enabled {
+                "✅ Enabled"
+            } else {
+                "❌ Disabled"
+            },
+            true,
+        )
+        .field(
+            "Whitelist",
+            if whitelist.is_empty() {
+                "None, every link is removed".to_string()
+            } else {
+                whitelist.join(", ")
+            },
+            false,
+        )
+        .field(
+            "Exempt Roles",
+            if exempt.is_empty() {
+                "None".to_string()
+            } else {
+                exempt
+                    .iter()
+                    .map(|id| format!("<@&{}>", id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            false,
+        )
+        .color(serenity::Colour::BLUE);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
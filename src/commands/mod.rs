@@ -1,29 +1,70 @@
 pub mod admin;
 pub mod ai;
+pub mod birthday;
+pub mod crypto;
 pub mod forex;
 pub mod general;
+pub mod giveaway;
+pub mod github;
+pub mod levels;
+pub mod link_filter;
 pub mod moderation;
 pub mod music;
 pub mod ping;
 pub mod price;
 pub mod redeem;
+pub mod starboard;
 pub mod sys;
+pub mod tags;
+pub mod weather;
 
+use crate::handlers::gateway::GatewayMetrics;
 use crate::repository::DbPool;
+use crate::services::forex_calendar::CalendarCache;
 use crate::services::music::MusicPlayer;
+use crate::services::music::node::NodeStatusCache;
 use crate::services::youtube::YouTubeSearch;
-use poise::serenity_prelude::UserId;
+use crate::utils::health::HealthRegistry;
+use weather::WeatherCache;
+use parking_lot::RwLock;
+use poise::serenity_prelude::{ActivityData, ChannelId, UserId};
 use songbird::Songbird;
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Instant;
 
+/// Presence rotation, editable at runtime with `/activity`
+pub type ActivityList = Arc<RwLock<Vec<ActivityData>>>;
+
+/// Shared state available to every command through `ctx.data()`
 #[derive(Clone)]
 pub struct Data {
+    /// Users allowed to run `owners_only` commands
     pub owners: HashSet<UserId>,
+    /// Shared Postgres pool used by every repository
     pub db: DbPool,
+    /// `None` when Lavalink was unreachable at startup
     pub music_player: Option<MusicPlayer>,
+    /// Voice gateway manager used to join and leave channels
     pub songbird: Arc<Songbird>,
+    /// `None` without a `YOUTUBE_API_KEY`
     pub youtube_search: Option<YouTubeSearch>,
+    /// Status of the background services, shown by `/health`
+    pub health: HealthRegistry,
+    /// Last Lavalink node status, shown by `/lavalink` when the node is down
+    pub lavalink_status: NodeStatusCache,
+    /// Presence rotation, edited live by `/activity add` and `/activity remove`
+    pub activities: ActivityList,
+    /// Channel for detailed command error reports (`ERROR_LOG_CHANNEL`)
+    pub error_log_channel: Option<ChannelId>,
+    /// Recent `/weather` results, reused for 10 minutes
+    pub weather_cache: WeatherCache,
+    /// Last Forex Factory calendar fetch, reused by `/forex_calendar` for an hour
+    pub calendar_cache: CalendarCache,
+    /// When the framework finished setup, for `/botinfo` uptime
+    pub started_at: Instant,
+    /// Ready, resume and disconnect counts, shown by `/gateway`
+    pub gateway: Arc<GatewayMetrics>,
 }
 
 impl std::fmt::Debug for Data {
@@ -34,6 +75,75 @@ impl std::fmt::Debug for Data {
             .field("music_player", &self.music_player)
             .field("songbird", &"Arc<Songbird>")
             .field("youtube_search", &self.youtube_search.is_some())
+            .field("health", &self.health.read().len())
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    /// Functions marked `#[poise::command]` in a command module
+    fn command_names(source: &str) -> Vec<String> {
+        source
+            .split("#[poise::command")
+            .skip(1)
+            .filter_map(|rest| {
+                let rest = &rest[rest.find("pub async fn ")? + "pub async fn ".len()..];
+                Some(rest[..rest.find('(')?].trim().to_string())
+            })
+            .collect()
+    }
+
+    /// Names listed in the `subcommands(...)` of parent commands
+    fn subcommand_names(source: &str) -> Vec<String> {
+        source
+            .split("subcommands(")
+            .skip(1)
+            .flat_map(|rest| {
+                rest[..rest.find(')').unwrap_or(rest.len())]
+                    .split(',')
+                    .map(|name| name.trim().trim_matches('"').to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Every command module must be declared here and every command in it
+    /// registered in main.rs, either directly or as a subcommand
+    #[test]
+    fn test_every_command_is_registered() {
+        let main = include_str!("../main.rs");
+        let declared = include_str!("mod.rs");
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/commands");
+
+        let mut modules = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let module = path.file_stem().unwrap().to_string_lossy().to_string();
+            if module != "mod" {
+                modules.push((module, std::fs::read_to_string(&path).unwrap()));
+            }
+        }
+        let subcommands: Vec<String> =
+            modules.iter().flat_map(|(_, source)| subcommand_names(source)).collect();
+
+        let mut missing = Vec::new();
+        for (module, source) in &modules {
+            assert!(
+                declared.contains(&format!("pub mod {};", module)),
+                "commands/{}.rs is not declared in commands/mod.rs",
+                module
+            );
+            for name in command_names(source) {
+                let registered = main.contains(&format!("{}::{}()", module, name));
+                if !registered && !subcommands.contains(&name) {
+                    missing.push(format!("{}::{}", module, name));
+                }
+            }
+        }
+        assert!(missing.is_empty(), "commands not registered in main.rs: {:?}", missing);
+    }
+}
@@ -1,26 +1,168 @@
-use crate::repository::ModerationRepository;
+use crate::handlers::events::welcome_message;
+use crate::repository::{ModerationRepository, WelcomeRepository};
 use crate::utils::embed;
+use crate::utils::pagination::paginate;
+use crate::utils::time::{format_elapsed, parse_duration};
 use poise::serenity_prelude as serenity;
 use serenity::{Colour, CreateEmbed, CreateEmbedFooter, Member, Mentionable, Timestamp};
-use std::time::Duration;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
-fn parse_duration(input: &str) -> Option<Duration> {
-    let input = input.trim().to_lowercase();
-    let (num_str, unit) = input.split_at(input.len().saturating_sub(1));
-    let num: u64 = num_str.parse().ok()?;
+const WARNINGS_PAGE_SIZE: usize = 5;
+const MODLOG_PAGE_SIZE: usize = 8;
+/// Longer reasons are cut so a full page stays under the embed description limit
+const MODLOG_REASON_CHARS: usize = 300;
+/// Select menus hold 25 options, one is kept for "No color"
+const MAX_COLOR_ROLES: usize = 24;
+const NO_COLOR_VALUE: &str = "none";
 
-    match unit {
-        "s" => Some(Duration::from_secs(num)),
-        "m" => Some(Duration::from_secs(num * 60)),
-        "h" => Some(Duration::from_secs(num * 3600)),
-        "d" => Some(Duration::from_secs(num * 86400)),
-        _ => None,
+/// Fetch the invoker and the bot member, then let `rule` decide whether the
+/// action is allowed. `rule` gets the guild, a member's highest role position,
+/// the invoker and the bot. Replies with the returned reason and yields false
+/// when there is one
+async fn check_ranks(
+    ctx: Context<'_>,
+    title: &str,
+    rule: impl FnOnce(
+        &serenity::Guild,
+        &dyn Fn(&Member) -> u16,
+        &Member,
+        &Member,
+    ) -> Option<&'static str>,
+) -> Result<bool, Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let invoker = ctx
+        .author_member()
+        .await
+        .ok_or("Cannot get member info")?
+        .into_owned();
+    let bot_member = guild_id.member(&ctx.http(), ctx.framework().bot_id).await?;
+
+    let problem = {
+        let guild = ctx.guild().ok_or("Cannot get server info")?;
+        let top_position = |member: &Member| {
+            member
+                .roles
+                .iter()
+                .filter_map(|id| guild.roles.get(id))
+                .map(|role| role.position)
+                .max()
+                .unwrap_or(0)
+        };
+        rule(&guild, &top_position, &invoker, &bot_member)
+    };
+
+    match problem {
+        Some(reason) => {
+            ctx.send(poise::CreateReply::default().embed(embed::error(title, reason)))
+                .await?;
+            Ok(false)
+        }
+        None => Ok(true),
     }
 }
 
+/// Make sure both the invoker and the bot outrank the target, replying with an error if not
+async fn check_hierarchy(ctx: Context<'_>, target: &Member, title: &str) -> Result<bool, Error> {
+    check_ranks(ctx, title, |guild, top_position, invoker, bot_member| {
+        if target.user.id == guild.owner_id {
+            Some("You cannot act on the server owner.")
+        } else if invoker.user.id != guild.owner_id && top_position(target) >= top_position(invoker)
+        {
+            Some("Target has a higher or equal role than you.")
+        } else if top_position(target) >= top_position(bot_member) {
+            Some("I can't act on this member because my role is too low.")
+        } else {
+            None
+        }
+    })
+    .await
+}
+
+/// Make sure `role` sits below both the invoker's and the bot's highest role,
+/// replying with an error if not
+async fn check_role_hierarchy(
+    ctx: Context<'_>,
+    role: &serenity::Role,
+    title: &str,
+) -> Result<bool, Error> {
+    check_ranks(ctx, title, |guild, top_position, invoker, bot_member| {
+        if role.managed || role.id.get() == guild.id.get() {
+            Some("This role is managed automatically and can't be assigned.")
+        } else if invoker.user.id != guild.owner_id && role.position >= top_position(invoker) {
+            Some("This role is higher than or equal to your highest role.")
+        } else if role.position >= top_position(bot_member) {
+            Some("I can't manage this role because it's higher than or equal to my highest role.")
+        } else {
+            None
+        }
+    })
+    .await
+}
+
+/// Send a copy of a moderation embed to the guild's log channel, if one is set
+async fn log_mod_action(ctx: Context<'_>, embed: CreateEmbed) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let pool = ctx.data().db.as_ref();
+
+    if let Some(log_channel_id) = ModerationRepository::get_config(pool, guild_id.get())
+        .await?
+        .and_then(|c| c.log_channel_id)
+    {
+        let message = serenity::CreateMessage::new().embed(embed);
+        if let Err(e) = serenity::ChannelId::new(log_channel_id as u64)
+            .send_message(&ctx.http(), message)
+            .await
+        {
+            eprintln!(
+                "[MOD] Failed to send to log channel {}: {}",
+                log_channel_id, e
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Add an action to the audit log. A failed write is only logged, since the
+/// action itself already went through
+async fn record_action(
+    ctx: Context<'_>,
+    action_type: &str,
+    target: serenity::UserId,
+    reason: Option<&str>,
+    duration_secs: Option<i32>,
+) {
+    let Some(guild_id) = ctx.guild_id() else {
+        return;
+    };
+    if let Err(e) = ModerationRepository::log_action(
+        ctx.data().db.as_ref(),
+        guild_id.get(),
+        action_type,
+        target.get(),
+        ctx.author().id.get(),
+        reason,
+        duration_secs,
+    )
+    .await
+    {
+        eprintln!(
+            "[MOD] Failed to record {} in the audit log: {}",
+            action_type, e
+        );
+    }
+}
+
+fn parse_hex_color(input: &str) -> Option<u32> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
 #[poise::command(
     slash_command,
     prefix_command,
@@ -47,6 +189,9 @@ pub async fn warn(
             .await?;
         return Ok(());
     }
+    if !check_hierarchy(ctx, &user, "Cannot Warn").await? {
+        return Ok(());
+    }
 
     let pool = ctx.data().db.as_ref();
     ModerationRepository::add_warning(
@@ -57,6 +202,7 @@ pub async fn warn(
         &reason,
     )
     .await?;
+    record_action(ctx, "warn", user.user.id, Some(&reason), None).await;
     let warn_count =
         ModerationRepository::get_warning_count(pool, guild_id.get(), user.user.id.get()).await?;
 
@@ -105,28 +251,81 @@ pub async fn warnings(
         return Ok(());
     }
 
-    let warnings_list: String = warns
-        .iter()
+    let pages = warns
+        .chunks(WARNINGS_PAGE_SIZE)
         .enumerate()
-        .map(|(i, w)| {
-            format!(
-                "**{}. ID #{}** - {}\n└ <t:{}:R>",
-                i + 1,
-                w.id,
-                w.reason,
-                w.created_at.timestamp()
-            )
+        .map(|(page, chunk)| {
+            let warnings_list = chunk
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    format!(
+                        "**{}. ID #{}** - {}\n└ <t:{}:R>",
+                        page * WARNINGS_PAGE_SIZE + i + 1,
+                        w.id,
+                        w.reason,
+                        w.created_at.timestamp()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            CreateEmbed::new()
+                .title(format!(
+                    "⚠️ Warnings for {} ({})",
+                    user.user.name,
+                    warns.len()
+                ))
+                .description(warnings_list)
+                .color(Colour::ORANGE)
+                .timestamp(Timestamp::now())
         })
-        .collect::<Vec<_>>()
-        .join("\n\n");
+        .collect();
+
+    paginate(ctx, pages, 120).await?;
+    Ok(())
+}
+
+/// Delete a single warning by its ID
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MODERATE_MEMBERS"
+)]
+pub async fn delwarn(
+    ctx: Context<'_>,
+    #[description = "Warning ID (shown in /warnings)"] warning_id: i64,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let Some(user_id) =
+        ModerationRepository::delete_warning(pool, warning_id, guild_id.get()).await?
+    else {
+        let embed_err = embed::error(
+            "Warning Not Found",
+            &format!("No warning with ID #{} in this server.", warning_id),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    };
+
+    let remaining = ModerationRepository::get_warning_count(pool, guild_id.get(), user_id).await?;
 
     let embed = CreateEmbed::new()
-        .title(format!("⚠️ Warnings for {}", user.user.name))
-        .description(warnings_list)
-        .color(Colour::ORANGE)
+        .title("Warning Deleted")
+        .description(format!(
+            "Removed warning **#{}** from {}.\n**Remaining Warnings:** {}",
+            warning_id,
+            serenity::UserId::new(user_id).mention(),
+            remaining
+        ))
+        .color(Colour::DARK_GREEN)
         .footer(CreateEmbedFooter::new(format!(
-            "Total: {} warnings",
-            warns.len()
+            "Deleted by {}",
+            ctx.author().name
         )))
         .timestamp(Timestamp::now());
 
@@ -134,6 +333,44 @@ pub async fn warnings(
     Ok(())
 }
 
+/// Make warnings expire after a number of days (leave empty to keep them forever)
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn warn_expiry(
+    ctx: Context<'_>,
+    #[description = "Days until a warning expires (empty = never)"]
+    #[min = 1]
+    #[max = 3650]
+    days: Option<u32>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let days = days.map(|d| d.clamp(1, 3650) as i32);
+
+    let pool = ctx.data().db.as_ref();
+    ModerationRepository::set_warn_expiry(pool, guild_id.get(), days).await?;
+
+    let description = match days {
+        Some(days) => format!(
+            "Warnings older than **{}** days are no longer counted and will be deleted.",
+            days
+        ),
+        None => "Warnings no longer expire.".to_string(),
+    };
+
+    let embed = CreateEmbed::new()
+        .title("Warning Expiry Updated")
+        .description(description)
+        .color(Colour::DARK_GREEN)
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
 #[poise::command(
     slash_command,
     prefix_command,
@@ -191,12 +428,24 @@ pub async fn mute(
             .await?;
         return Ok(());
     }
+    if !check_hierarchy(ctx, &user, "Cannot Mute").await? {
+        return Ok(());
+    }
+
     let timeout_until = serenity::Timestamp::from_unix_timestamp(
         chrono::Utc::now().timestamp() + dur.as_secs() as i64,
     )?;
 
     user.disable_communication_until_datetime(&ctx.http(), timeout_until)
         .await?;
+    record_action(
+        ctx,
+        "mute",
+        user.user.id,
+        Some(&reason_text),
+        Some(dur.as_secs() as i32),
+    )
+    .await;
 
     let embed = CreateEmbed::new()
         .title("User Muted")
@@ -228,6 +477,7 @@ pub async fn unmute(
     #[description = "User to unmute"] mut user: Member,
 ) -> Result<(), Error> {
     user.enable_communication(&ctx.http()).await?;
+    record_action(ctx, "unmute", user.user.id, None, None).await;
 
     let embed = CreateEmbed::new()
         .title("User Unmuted")
@@ -256,7 +506,12 @@ pub async fn kick(
 ) -> Result<(), Error> {
     let reason_text = reason.unwrap_or_else(|| "No reason provided".to_string());
 
+    if !check_hierarchy(ctx, &user, "Cannot Kick").await? {
+        return Ok(());
+    }
+
     user.kick_with_reason(&ctx.http(), &reason_text).await?;
+    record_action(ctx, "kick", user.user.id, Some(&reason_text), None).await;
 
     let embed = CreateEmbed::new()
         .title("User Kicked")
@@ -290,8 +545,13 @@ pub async fn ban(
     let reason_text = reason.unwrap_or_else(|| "No reason provided".to_string());
     let del_days = delete_days.unwrap_or(0).min(7);
 
+    if !check_hierarchy(ctx, &user, "Cannot Ban").await? {
+        return Ok(());
+    }
+
     user.ban_with_reason(&ctx.http(), del_days, &reason_text)
         .await?;
+    record_action(ctx, "ban", user.user.id, Some(&reason_text), None).await;
 
     let embed = CreateEmbed::new()
         .title("🔨 User Banned")
@@ -326,6 +586,7 @@ pub async fn unban(
     let user_id_parsed = serenity::UserId::new(uid);
 
     guild_id.unban(&ctx.http(), user_id_parsed).await?;
+    record_action(ctx, "unban", user_id_parsed, None, None).await;
 
     let embed = CreateEmbed::new()
         .title("User Unbanned")
@@ -341,6 +602,618 @@ pub async fn unban(
     Ok(())
 }
 
+fn action_icon(action_type: &str) -> &'static str {
+    match action_type {
+        "warn" => "⚠️",
+        "mute" => "🔇",
+        "unmute" => "🔊",
+        "kick" => "👢",
+        "ban" => "🔨",
+        "unban" => "🕊️",
+        _ => "📝",
+    }
+}
+
+/// View or clear a user's moderation history
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MODERATE_MEMBERS",
+    subcommands("modlog_user", "modlog_clear"),
+    subcommand_required
+)]
+pub async fn modlog(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Every moderation action taken against a user, oldest first
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MODERATE_MEMBERS",
+    rename = "user"
+)]
+pub async fn modlog_user(
+    ctx: Context<'_>,
+    #[description = "User to look up"] user: serenity::User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let actions =
+        ModerationRepository::get_user_actions(pool, guild_id.get(), user.id.get()).await?;
+
+    if actions.is_empty() {
+        let embed = CreateEmbed::new()
+            .title("Clean Record")
+            .description(format!("No moderation actions against {}.", user.mention()))
+            .color(Colour::DARK_GREEN)
+            .timestamp(Timestamp::now());
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let pages = actions
+        .chunks(MODLOG_PAGE_SIZE)
+        .map(|chunk| {
+            let entries = chunk
+                .iter()
+                .map(|a| {
+                    let mut entry = format!(
+                        "{} **{}** by <@{}> • <t:{}:f>",
+                        action_icon(&a.action_type),
+                        a.action_type,
+                        a.moderator_id,
+                        a.created_at.timestamp()
+                    );
+                    if let Some(secs) = a.duration_secs {
+                        entry.push_str(&format!(
+                            "\n└ Duration: {}",
+                            format_elapsed(std::time::Duration::from_secs(secs as u64))
+                        ));
+                    }
+                    if let Some(reason) = &a.reason {
+                        let short: String = reason.chars().take(MODLOG_REASON_CHARS).collect();
+                        let cut = if short.len() < reason.len() {
+                            "…"
+                        } else {
+                            ""
+                        };
+                        entry.push_str(&format!("\n└ {}{}", short, cut));
+                    }
+                    entry
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            CreateEmbed::new()
+                .title(format!("📋 Mod Log for {} ({})", user.name, actions.len()))
+                .description(entries)
+                .color(Colour::BLUE)
+                .timestamp(Timestamp::now())
+        })
+        .collect();
+
+    paginate(ctx, pages, 120).await?;
+    Ok(())
+}
+
+/// Delete a user's moderation history
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "clear"
+)]
+pub async fn modlog_clear(
+    ctx: Context<'_>,
+    #[description = "User whose history to delete"] user: serenity::User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let removed =
+        ModerationRepository::clear_user_actions(pool, guild_id.get(), user.id.get()).await?;
+
+    let embed = if removed > 0 {
+        embed::success(
+            "Mod Log Cleared",
+            &format!("Deleted {} action(s) against {}.", removed, user.mention()),
+        )
+    } else {
+        embed::info(
+            "Mod Log Empty",
+            &format!("No moderation actions against {}.", user.mention()),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Action counts for a moderator
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MODERATE_MEMBERS"
+)]
+pub async fn moderator_stats(
+    ctx: Context<'_>,
+    #[description = "Moderator to look up (default: you)"] moderator: Option<serenity::User>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let moderator = moderator.as_ref().unwrap_or_else(|| ctx.author());
+
+    let pool = ctx.data().db.as_ref();
+    let counts =
+        ModerationRepository::get_moderator_stats(pool, guild_id.get(), moderator.id.get()).await?;
+
+    let total: i64 = counts.iter().map(|c| c.count).sum();
+    let mut embed = CreateEmbed::new()
+        .title(format!("🛡️ Moderator Stats: {}", moderator.name))
+        .thumbnail(moderator.face())
+        .color(Colour::BLUE)
+        .timestamp(Timestamp::now());
+    if counts.is_empty() {
+        embed = embed.description(format!(
+            "{} hasn't taken any moderation actions yet.",
+            moderator.mention()
+        ));
+    } else {
+        embed = embed.description(format!("**{}** action(s) in total", total));
+        for count in &counts {
+            embed = embed.field(
+                format!("{} {}", action_icon(&count.action_type), count.action_type),
+                count.count.to_string(),
+                true,
+            );
+        }
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Give a role to a member
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_ROLES"
+)]
+pub async fn role_add(
+    ctx: Context<'_>,
+    #[description = "Member to give the role to"] user: Member,
+    #[description = "Role to give"] role: serenity::Role,
+) -> Result<(), Error> {
+    if !check_role_hierarchy(ctx, &role, "Cannot Add Role").await? {
+        return Ok(());
+    }
+    if user.roles.contains(&role.id) {
+        let embed_err = embed::error(
+            "Cannot Add Role",
+            &format!("{} already has {}.", user.user.mention(), role.mention()),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    }
+
+    user.add_role(&ctx.http(), role.id).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Role Added")
+        .description(format!(
+            "**User:** {}\n**Role:** {}",
+            user.user.mention(),
+            role.mention()
+        ))
+        .color(Colour::DARK_GREEN)
+        .footer(CreateEmbedFooter::new(format!(
+            "Added by {}",
+            ctx.author().name
+        )))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed.clone()))
+        .await?;
+    log_mod_action(ctx, embed).await?;
+    Ok(())
+}
+
+/// Take a role from a member
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_ROLES"
+)]
+pub async fn role_remove(
+    ctx: Context<'_>,
+    #[description = "Member to take the role from"] user: Member,
+    #[description = "Role to remove"] role: serenity::Role,
+) -> Result<(), Error> {
+    if !check_role_hierarchy(ctx, &role, "Cannot Remove Role").await? {
+        return Ok(());
+    }
+    if !user.roles.contains(&role.id) {
+        let embed_err = embed::error(
+            "Cannot Remove Role",
+            &format!("{} doesn't have {}.", user.user.mention(), role.mention()),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    }
+
+    user.remove_role(&ctx.http(), role.id).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Role Removed")
+        .description(format!(
+            "**User:** {}\n**Role:** {}",
+            user.user.mention(),
+            role.mention()
+        ))
+        .color(Colour::ORANGE)
+        .footer(CreateEmbedFooter::new(format!(
+            "Removed by {}",
+            ctx.author().name
+        )))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed.clone()))
+        .await?;
+    log_mod_action(ctx, embed).await?;
+    Ok(())
+}
+
+/// Self-assignable color roles members pick with `/color_pick`
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("color_role_add", "color_role_remove", "color_role_list"),
+    subcommand_required
+)]
+pub async fn color_role(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Offer a role in the color picker
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "add"
+)]
+pub async fn color_role_add(
+    ctx: Context<'_>,
+    #[description = "Role members can pick"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    if !check_role_hierarchy(ctx, &role, "Cannot Add Color Role").await? {
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let configured = ModerationRepository::get_color_roles(pool, guild_id.get()).await?;
+    if configured.len() >= MAX_COLOR_ROLES && !configured.contains(&(role.id.get() as i64)) {
+        let embed_err = embed::error(
+            "Too Many Color Roles",
+            &format!("The picker holds at most {} color roles.", MAX_COLOR_ROLES),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed_err))
+            .await?;
+        return Ok(());
+    }
+
+    let added = ModerationRepository::add_color_role(pool, guild_id.get(), role.id.get()).await?;
+    let embed = if added {
+        embed::success(
+            "Color Role Added",
+            &format!(
+                "Members can now pick {} with `/color_pick`.",
+                role.mention()
+            ),
+        )
+    } else {
+        embed::info(
+            "Already Added",
+            &format!("{} is already a color role.", role.mention()),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Take a role out of the color picker
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn color_role_remove(
+    ctx: Context<'_>,
+    #[description = "Color role to remove"] role: serenity::Role,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let removed =
+        ModerationRepository::remove_color_role(pool, guild_id.get(), role.id.get()).await?;
+    let embed = if removed {
+        embed::success(
+            "Color Role Removed",
+            &format!(
+                "{} is no longer offered. Members who have it keep it.",
+                role.mention()
+            ),
+        )
+    } else {
+        embed::error(
+            "Not Found",
+            &format!("{} is not a color role.", role.mention()),
+        )
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// List the color roles members can pick
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "list"
+)]
+pub async fn color_role_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let roles = ModerationRepository::get_color_roles(pool, guild_id.get()).await?;
+
+    let description = if roles.is_empty() {
+        "No color roles yet. Add one with `/color_role add`".to_string()
+    } else {
+        roles
+            .iter()
+            .map(|id| format!("<@&{}>", id))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let embed = embed::info("Color Roles", &description);
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show a dropdown of color roles. Returns `None` on timeout and
+/// `Some(None)` when the member picked no color
+async fn pick_color_role<'a>(
+    ctx: Context<'a>,
+    roles: &[serenity::Role],
+) -> Result<Option<(poise::ReplyHandle<'a>, Option<serenity::RoleId>)>, Error> {
+    let custom_id = format!("{}_color_pick", ctx.id());
+
+    let mut options: Vec<serenity::CreateSelectMenuOption> = roles
+        .iter()
+        .map(|role| {
+            serenity::CreateSelectMenuOption::new(
+                role.name.chars().take(100).collect::<String>(),
+                role.id.to_string(),
+            )
+            .description(format!("#{:06X}", role.colour.0))
+        })
+        .collect();
+    options.push(
+        serenity::CreateSelectMenuOption::new("No color", NO_COLOR_VALUE)
+            .description("Remove your color role"),
+    );
+
+    let menu = serenity::CreateSelectMenu::new(
+        &custom_id,
+        serenity::CreateSelectMenuKind::String { options },
+    )
+    .placeholder("🎨 Pick a color");
+
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed::info(
+                    "Pick a Color",
+                    "Choose a color role below • Expires in 60s",
+                ))
+                .components(vec![serenity::CreateActionRow::SelectMenu(menu)])
+                .ephemeral(true),
+        )
+        .await?;
+
+    let filter_id = custom_id.clone();
+    let interaction = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .timeout(std::time::Duration::from_secs(60))
+        .filter(move |mci| mci.data.custom_id == filter_id)
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply
+            .edit(
+                ctx,
+                poise::CreateReply::default()
+                    .embed(embed::info("Pick a Color", "No color picked in time."))
+                    .components(vec![]),
+            )
+            .await?;
+        return Ok(None);
+    };
+    interaction
+        .create_response(ctx, serenity::CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    let choice = match &interaction.data.kind {
+        serenity::ComponentInteractionDataKind::StringSelect { values } => values
+            .first()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(serenity::RoleId::new),
+        _ => None,
+    };
+    Ok(Some((reply, choice)))
+}
+
+/// Pick your color role
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn color_pick(
+    ctx: Context<'_>,
+    #[description = "Color role to wear (leave empty for a picker)"] role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    let configured: Vec<serenity::RoleId> =
+        ModerationRepository::get_color_roles(pool, guild_id.get())
+            .await?
+            .into_iter()
+            .map(|id| serenity::RoleId::new(id as u64))
+            .collect();
+    // Skip roles deleted since they were added
+    let roles: Vec<serenity::Role> = {
+        let guild = ctx.guild().ok_or("Cannot get server info")?;
+        configured
+            .iter()
+            .filter_map(|id| guild.roles.get(id).cloned())
+            .collect()
+    };
+
+    if roles.is_empty() {
+        let embed_err = embed::error(
+            "No Color Roles",
+            "This server has no color roles yet. An admin can add some with `/color_role add`.",
+        );
+        ctx.send(
+            poise::CreateReply::default()
+                .embed(embed_err)
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (reply, choice) = match role {
+        Some(role) if !configured.contains(&role.id) => {
+            let embed_err = embed::error(
+                "Not a Color Role",
+                &format!(
+                    "{} can't be picked. See `/color_pick` for the options.",
+                    role.mention()
+                ),
+            );
+            ctx.send(
+                poise::CreateReply::default()
+                    .embed(embed_err)
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(role) => (None, Some(role.id)),
+        None => match pick_color_role(ctx, &roles).await? {
+            Some((reply, choice)) => (Some(reply), choice),
+            None => return Ok(()),
+        },
+    };
+
+    let embed = set_color_role(ctx, &roles, &configured, choice).await?;
+    match reply {
+        Some(reply) => {
+            reply
+                .edit(
+                    ctx,
+                    poise::CreateReply::default()
+                        .embed(embed)
+                        .components(vec![]),
+                )
+                .await?;
+        }
+        None => {
+            ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Swap the invoker's color role for `choice`, or drop it when `None`
+async fn set_color_role(
+    ctx: Context<'_>,
+    roles: &[serenity::Role],
+    configured: &[serenity::RoleId],
+    choice: Option<serenity::RoleId>,
+) -> Result<CreateEmbed, Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let member = ctx
+        .author_member()
+        .await
+        .ok_or("Cannot get member info")?
+        .into_owned();
+
+    let role = choice.and_then(|id| roles.iter().find(|r| r.id == id));
+    if let Some(role) = role {
+        let bot_member = guild_id.member(&ctx.http(), ctx.framework().bot_id).await?;
+        let bot_top = {
+            let guild = ctx.guild().ok_or("Cannot get server info")?;
+            bot_member
+                .roles
+                .iter()
+                .filter_map(|id| guild.roles.get(id))
+                .map(|r| r.position)
+                .max()
+                .unwrap_or(0)
+        };
+        if role.position >= bot_top {
+            return Ok(embed::error(
+                "Cannot Assign Color",
+                &format!(
+                    "I can't assign {} because it's higher than or equal to my highest role.",
+                    role.mention()
+                ),
+            ));
+        }
+    }
+
+    // At most one color role: drop any other the member holds
+    let held: Vec<serenity::RoleId> = member
+        .roles
+        .iter()
+        .filter(|id| configured.contains(id) && Some(**id) != choice)
+        .copied()
+        .collect();
+    if !held.is_empty() {
+        member.remove_roles(&ctx.http(), &held).await?;
+    }
+
+    Ok(match role {
+        Some(role) => {
+            if !member.roles.contains(&role.id) {
+                member.add_role(&ctx.http(), role.id).await?;
+            }
+            embed::success(
+                "Color Updated",
+                &format!("You now wear {}.", role.mention()),
+            )
+        }
+        None => embed::success("Color Removed", "Your color role was removed."),
+    })
+}
+
 #[poise::command(
     slash_command,
     prefix_command,
@@ -440,3 +1313,247 @@ pub async fn log_disable(ctx: Context<'_>) -> Result<(), Error> {
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn verify_setup(
+    ctx: Context<'_>,
+    #[description = "Role granted after verifying"] role: serenity::Role,
+    #[description = "Channel for the button"] channel: Option<serenity::GuildChannel>,
+    #[description = "Role given until verified"] unverified_role: Option<serenity::Role>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    let channel_id = channel.map(|c| c.id).unwrap_or_else(|| ctx.channel_id());
+
+    let pool = ctx.data().db.as_ref();
+    ModerationRepository::set_verification(
+        pool,
+        guild_id.get(),
+        role.id.get(),
+        unverified_role.as_ref().map(|r| r.id.get()),
+    )
+    .await?;
+
+    let gate = CreateEmbed::new()
+        .title("✅ Verification")
+        .description("Click the button below to verify and get access to the server.")
+        .color(Colour::DARK_GREEN);
+    let button = serenity::CreateButton::new(crate::handlers::events::VERIFY_BUTTON_ID)
+        .label("Verify")
+        .style(serenity::ButtonStyle::Success);
+    let message = serenity::CreateMessage::new()
+        .embed(gate)
+        .components(vec![serenity::CreateActionRow::Buttons(vec![button])]);
+    channel_id.send_message(&ctx.http(), message).await?;
+
+    let mut description = format!(
+        "Members verifying in {} will receive the {} role.",
+        channel_id.mention(),
+        role.mention()
+    );
+    if let Some(unverified) = &unverified_role {
+        description.push_str(&format!(
+            "\nNew members get {} until they verify.",
+            unverified.mention()
+        ));
+    }
+
+    let embed = CreateEmbed::new()
+        .title("Verification Enabled")
+        .description(description)
+        .color(Colour::DARK_GREEN)
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn verify_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    ModerationRepository::disable_verification(pool, guild_id.get()).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Verification Disabled")
+        .description("The verify button will no longer grant roles.")
+        .color(Colour::RED)
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn welcome_set(
+    ctx: Context<'_>,
+    #[description = "Use {user} {username} {server} {count} {created} {id}; {embed:...} for an embed"]
+    #[rest]
+    message: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    WelcomeRepository::set_welcome_message(pool, guild_id.get(), Some(&message)).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Welcome Message Set")
+        .description(format!("**Preview:**\n{}", preview_template(ctx, &message)))
+        .color(Colour::DARK_GREEN)
+        .footer(CreateEmbedFooter::new(
+            "Sent to the log channel when a member joins",
+        ))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Preview the welcome message in this channel, using yourself as the new member
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn welcome_test(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+    ctx.defer_ephemeral().await?;
+    let (server, count) = ctx
+        .guild()
+        .map(|g| (g.name.clone(), g.member_count))
+        .unwrap_or_else(|| ("Server".to_string(), 0));
+
+    let pool = ctx.data().db.as_ref();
+    let welcome = WelcomeRepository::get_config(pool, guild_id.get()).await?;
+    let message = welcome_message(welcome.as_ref(), ctx.author(), &server, count);
+
+    ctx.channel_id().send_message(ctx.http(), message).await?;
+    ctx.send(
+        poise::CreateReply::default()
+            .content("Preview sent above")
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn goodbye_set(
+    ctx: Context<'_>,
+    #[description = "Use {user} {username} {server} {count} {created} {id}; {embed:...} for an embed"]
+    #[rest]
+    message: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    WelcomeRepository::set_leave_message(pool, guild_id.get(), Some(&message)).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Goodbye Message Set")
+        .description(format!("**Preview:**\n{}", preview_template(ctx, &message)))
+        .color(Colour::DARK_GREEN)
+        .footer(CreateEmbedFooter::new(
+            "Sent to the log channel when a member leaves",
+        ))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn welcome_style(
+    ctx: Context<'_>,
+    #[description = "Embed color as hex (e.g. #5865F2)"] color: Option<String>,
+    #[description = "Show the member's avatar"] show_avatar: Option<bool>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let color = match color.as_deref().map(parse_hex_color) {
+        Some(None) => {
+            let embed_err = embed::error("Invalid Color", "Use a hex color like `#5865F2`.");
+            ctx.send(poise::CreateReply::default().embed(embed_err))
+                .await?;
+            return Ok(());
+        }
+        Some(parsed) => parsed,
+        None => None,
+    };
+    let show_avatar = show_avatar.unwrap_or(true);
+
+    let pool = ctx.data().db.as_ref();
+    WelcomeRepository::set_style(pool, guild_id.get(), color, show_avatar).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Welcome Style Updated")
+        .description(format!(
+            "**Color:** {}\n**Avatar:** {}",
+            color
+                .map(|c| format!("#{:06X}", c))
+                .unwrap_or_else(|| "Default".to_string()),
+            if show_avatar { "Shown" } else { "Hidden" }
+        ))
+        .color(color.unwrap_or(embed::COLOR_WELCOME))
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn welcome_reset(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?;
+
+    let pool = ctx.data().db.as_ref();
+    WelcomeRepository::reset(pool, guild_id.get()).await?;
+
+    let embed = CreateEmbed::new()
+        .title("Welcome Messages Reset")
+        .description("Welcome and goodbye messages are back to the defaults.")
+        .color(Colour::DARK_GREEN)
+        .timestamp(Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+fn preview_template(ctx: Context<'_>, template: &str) -> String {
+    let (server, count) = ctx
+        .guild()
+        .map(|g| (g.name.clone(), g.member_count))
+        .unwrap_or_else(|| ("Server".to_string(), 0));
+    let rendered = embed::render_member_template(template, ctx.author(), true, &server, count);
+    embed::split_member_template(&rendered).0.to_string()
+}
@@ -1,17 +1,51 @@
 use crate::commands::Data;
+use crate::handlers::music::{control_panel, refresh_control_panel, set_paused, skip_track};
+use crate::repository::MusicConfigRepository;
 use crate::services::music::queue::QueuedTrack;
+use crate::services::youtube::{SearchPage, YouTubeSearch};
 use crate::utils::embed;
+use crate::utils::pagination::paginate;
 use poise::serenity_prelude::{CreateEmbed, Mentionable};
 use std::time::Duration;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
+const QUEUE_PAGE_SIZE: usize = 10;
+/// The YouTube API returns at most 10 results per page
+const SEARCH_PAGE_SIZE: u32 = 10;
+const SEARCH_SELECT_ID: &str = "song_select";
+const SEARCH_MORE_ID: &str = "song_more";
+
 async fn send_embed(ctx: Context<'_>, embed: CreateEmbed) -> Result<(), Error> {
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
 }
 
+/// Returns false (after replying) when music commands are restricted to another channel
+async fn check_music_channel(ctx: Context<'_>) -> Result<bool, Error> {
+    let Some(guild_id) = ctx.guild_id() else {
+        return Ok(true);
+    };
+
+    let pool = ctx.data().db.as_ref();
+    match MusicConfigRepository::get_channel(pool, guild_id.get()).await? {
+        Some(channel_id) if channel_id as u64 != ctx.channel_id().get() => {
+            ctx.send(
+                poise::CreateReply::default()
+                    .embed(embed::warning(
+                        "Wrong Channel",
+                        &format!("Please use music commands in <#{}>", channel_id),
+                    ))
+                    .ephemeral(true),
+            )
+            .await?;
+            Ok(false)
+        }
+        _ => Ok(true),
+    }
+}
+
 fn extract_video_id(url: &str) -> Option<String> {
     if url.contains("youtu.be/") {
         return url
@@ -147,6 +181,10 @@ pub async fn play(
     #[rest]
     query: String,
 ) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let guild = ctx.guild().ok_or("Cannot get server info")?.clone();
 
@@ -236,9 +274,9 @@ pub async fn play(
     }
 
     if let Some(youtube) = &ctx.data().youtube_search {
-        match youtube.search(&query, 10).await {
-            Ok(videos) if !videos.is_empty() => {
-                return show_search_results(ctx, player, guild_id, videos, &query).await;
+        match youtube.search_page(&query, SEARCH_PAGE_SIZE, None).await {
+            Ok(page) if !page.videos.is_empty() => {
+                return show_search_results(ctx, player, guild_id, youtube, page, &query).await;
             }
             Ok(_) => {
                 send_embed(ctx, embed::error("Not Found", "No YouTube videos found")).await?;
@@ -418,7 +456,14 @@ async fn play_track(
             )
         };
 
-        send_embed(ctx, embed_msg).await?;
+        let reply = ctx
+            .send(poise::CreateReply::default().embed(embed_msg))
+            .await?;
+        if is_first_track {
+            let msg = reply.message().await?;
+            player.set_now_playing_message(guild_id, Some((msg.channel_id, msg.id)));
+        }
+        refresh_control_panel(ctx.http(), guild_id).await;
     } else {
         send_embed(ctx, embed::error("Error", "Player not connected")).await?;
     }
@@ -426,21 +471,18 @@ async fn play_track(
     Ok(())
 }
 
-async fn show_search_results(
-    ctx: Context<'_>,
-    player: &crate::services::music::MusicPlayer,
-    guild_id: poise::serenity_prelude::GuildId,
-    videos: Vec<crate::services::youtube::YouTubeVideo>,
+fn search_results_message(
     query: &str,
-) -> Result<(), Error> {
+    page: &SearchPage,
+    page_number: usize,
+) -> (CreateEmbed, Vec<poise::serenity_prelude::CreateActionRow>) {
     use poise::serenity_prelude::{
-        ComponentInteractionCollector, CreateActionRow, CreateInteractionResponse,
-        CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind,
+        ButtonStyle, CreateActionRow, CreateButton, CreateSelectMenu, CreateSelectMenuKind,
         CreateSelectMenuOption,
     };
-    use std::time::Duration;
 
-    let options: Vec<CreateSelectMenuOption> = videos
+    let options: Vec<CreateSelectMenuOption> = page
+        .videos
         .iter()
         .enumerate()
         .map(|(i, video)| {
@@ -455,80 +497,84 @@ async fn show_search_results(
         .collect();
 
     let select_menu =
-        CreateSelectMenu::new("song_select", CreateSelectMenuKind::String { options })
+        CreateSelectMenu::new(SEARCH_SELECT_ID, CreateSelectMenuKind::String { options })
             .placeholder("🎵 Select a song to play");
 
-    let action_row = CreateActionRow::SelectMenu(select_menu);
+    let mut components = vec![CreateActionRow::SelectMenu(select_menu)];
+    if page.next_page_token.is_some() {
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new(SEARCH_MORE_ID)
+                .label("More results")
+                .style(ButtonStyle::Secondary),
+        ]));
+    }
 
-    let description = videos
+    // Keep numbering running across pages so page 2 starts at 11
+    let offset = (page_number - 1) * SEARCH_PAGE_SIZE as usize;
+    let description = page
+        .videos
         .iter()
         .enumerate()
         .take(10)
-        .map(|(i, v)| format!("**{}. {}**\n└ {}", i + 1, v.title, v.channel))
+        .map(|(i, v)| format!("**{}. {}**\n└ {}", offset + i + 1, v.title, v.channel))
         .collect::<Vec<_>>()
         .join("\n\n");
 
+    let footer = if page.next_page_token.is_some() {
+        format!(
+            "Page {} • Select a song from the dropdown below • Expires in 60s",
+            page_number
+        )
+    } else {
+        format!(
+            "Page {} • No more results • Select a song from the dropdown below • Expires in 60s",
+            page_number
+        )
+    };
+
     let search_embed = CreateEmbed::new()
         .title(format!("🔍 Search: {}", query))
         .description(description)
-        .footer(poise::serenity_prelude::CreateEmbedFooter::new(
-            "Select a song from the dropdown below • Expires in 60s",
-        ))
+        .footer(poise::serenity_prelude::CreateEmbedFooter::new(footer))
         .color(embed::COLOR_MUSIC);
 
+    (search_embed, components)
+}
+
+async fn show_search_results(
+    ctx: Context<'_>,
+    player: &crate::services::music::MusicPlayer,
+    guild_id: poise::serenity_prelude::GuildId,
+    youtube: &YouTubeSearch,
+    mut page: SearchPage,
+    query: &str,
+) -> Result<(), Error> {
+    use poise::serenity_prelude::{
+        ComponentInteractionCollector, ComponentInteractionDataKind, CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+    };
+
+    let mut page_number = 1;
+    let (search_embed, components) = search_results_message(query, &page, page_number);
     let reply = ctx
         .send(
             poise::CreateReply::default()
                 .embed(search_embed)
-                .components(vec![action_row]),
+                .components(components),
         )
         .await?;
 
-    let msg = reply.message().await?;
+    let message_id = reply.message().await?.id;
 
-    let interaction = ComponentInteractionCollector::new(ctx.serenity_context().shard.clone())
-        .message_id(msg.id)
-        .author_id(ctx.author().id)
-        .timeout(Duration::from_secs(60))
-        .await;
-
-    match interaction {
-        Some(interaction) => {
-            use poise::serenity_prelude::ComponentInteractionDataKind;
-            let selected_idx: usize = match &interaction.data.kind {
-                ComponentInteractionDataKind::StringSelect { values } => values
-                    .first()
-                    .and_then(|v| v.parse::<usize>().ok())
-                    .unwrap_or(0),
-                _ => 0,
-            };
+    loop {
+        // Each page gets a fresh 60 seconds to pick a song
+        let interaction = ComponentInteractionCollector::new(ctx.serenity_context().shard.clone())
+            .message_id(message_id)
+            .author_id(ctx.author().id)
+            .timeout(Duration::from_secs(60))
+            .await;
 
-            if let Some(video) = videos.get(selected_idx) {
-                interaction
-                    .create_response(
-                        ctx.http(),
-                        CreateInteractionResponse::UpdateMessage(
-                            CreateInteractionResponseMessage::new()
-                                .content(format!("Loading **{}**...", video.title))
-                                .embeds(vec![])
-                                .components(vec![]),
-                        ),
-                    )
-                    .await?;
-
-                let tracks = player.search_tracks(guild_id, &video.url).await?;
-                if let Some(track) = tracks.first() {
-                    play_track(ctx, player, guild_id, track).await?;
-                } else {
-                    send_embed(
-                        ctx,
-                        embed::error("Error", "Failed to load the selected video"),
-                    )
-                    .await?;
-                }
-            }
-        }
-        None => {
+        let Some(interaction) = interaction else {
             let _ = reply
                 .edit(
                     ctx,
@@ -542,14 +588,96 @@ async fn show_search_results(
                         .components(vec![]),
                 )
                 .await;
+            return Ok(());
+        };
+
+        if interaction.data.custom_id == SEARCH_MORE_ID {
+            let next = match page.next_page_token.as_deref() {
+                Some(token) => youtube
+                    .search_page(query, SEARCH_PAGE_SIZE, Some(token))
+                    .await
+                    .map(Some),
+                None => Ok(None),
+            };
+
+            let response = match next {
+                Ok(Some(next)) if !next.videos.is_empty() => {
+                    page = next;
+                    page_number += 1;
+                    let (search_embed, components) =
+                        search_results_message(query, &page, page_number);
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .embed(search_embed)
+                            .components(components),
+                    )
+                }
+                // The token pointed at an empty page, so this one was really the last
+                Ok(_) => {
+                    page.next_page_token = None;
+                    let (search_embed, components) =
+                        search_results_message(query, &page, page_number);
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .embed(search_embed)
+                            .components(components),
+                    )
+                }
+                Err(e) => {
+                    println!("[WARN] YouTube API search failed: {}", e);
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content("Couldn't load more results, try again in a moment")
+                            .ephemeral(true),
+                    )
+                }
+            };
+            interaction.create_response(ctx.http(), response).await?;
+            continue;
         }
-    }
 
-    Ok(())
+        let selected_idx: usize = match &interaction.data.kind {
+            ComponentInteractionDataKind::StringSelect { values } => values
+                .first()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        if let Some(video) = page.videos.get(selected_idx) {
+            interaction
+                .create_response(
+                    ctx.http(),
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new()
+                            .content(format!("Loading **{}**...", video.title))
+                            .embeds(vec![])
+                            .components(vec![]),
+                    ),
+                )
+                .await?;
+
+            let tracks = player.search_tracks(guild_id, &video.url).await?;
+            if let Some(track) = tracks.first() {
+                play_track(ctx, player, guild_id, track).await?;
+            } else {
+                send_embed(
+                    ctx,
+                    embed::error("Error", "Failed to load the selected video"),
+                )
+                .await?;
+            }
+        }
+        return Ok(());
+    }
 }
 
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -558,8 +686,7 @@ pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
         .ok_or("Music player not available")?;
 
     if let Some(player_ctx) = player.get_player_context(guild_id) {
-        player_ctx.set_pause(true).await?;
-        player.set_paused(guild_id, true);
+        set_paused(player, &player_ctx, guild_id, true).await?;
         send_embed(ctx, embed::music("Paused", "Playback has been paused")).await?;
     } else {
         send_embed(
@@ -574,6 +701,10 @@ pub async fn pause(ctx: Context<'_>) -> Result<(), Error> {
 
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -582,8 +713,7 @@ pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
         .ok_or("Music player not available")?;
 
     if let Some(player_ctx) = player.get_player_context(guild_id) {
-        player_ctx.set_pause(false).await?;
-        player.set_paused(guild_id, false);
+        set_paused(player, &player_ctx, guild_id, false).await?;
         send_embed(ctx, embed::music("Resumed", "Playback has been resumed")).await?;
     } else {
         send_embed(
@@ -598,6 +728,10 @@ pub async fn resume(ctx: Context<'_>) -> Result<(), Error> {
 
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -606,84 +740,24 @@ pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
         .ok_or("Music player not available")?;
 
     if let Some(player_ctx) = player.get_player_context(guild_id) {
-        if let Some(next_track) = player.next_track(guild_id) {
-            // Save track title for autoplay
-            player.set_last_track_title(guild_id, Some(next_track.track.info.title.clone()));
-            player.set_current(guild_id, Some(next_track.clone()));
-            let _ = player_ctx.play(&next_track.track).await;
-            send_embed(
-                ctx,
-                embed::music(
-                    "Skipped",
-                    &format!("Now playing: **{}**", next_track.track.info.title),
-                ),
-            )
-            .await?;
-        } else {
-            // Queue is empty - check autoplay
-            let is_autoplay = player.is_autoplay(guild_id);
-
-            if is_autoplay {
-                // Trigger autoplay directly instead of relying on track_end
-                send_embed(
-                    ctx,
-                    embed::music("Skipped", "Queue empty - finding a new song..."),
-                )
-                .await?;
-
-                // Do autoplay search and play
-                if let Some(track) = search_autoplay_track(player, guild_id).await {
-                    // Stop current track first
-                    let _ = player_ctx.stop_now().await;
-
-                    player.set_last_track_title(guild_id, Some(track.info.title.clone()));
-                    let queued = crate::services::music::queue::QueuedTrack {
-                        track: track.clone(),
-                        requester_id: 0,
-                        requester_name: "Autoplay".to_string(),
-                    };
-                    player.set_current(guild_id, Some(queued));
-
-                    if let Err(e) = player_ctx.play(&track).await {
-                        eprintln!("[MUSIC] Skip autoplay failed: {}", e);
-                        send_embed(ctx, embed::error("Autoplay Error", "Failed to play track"))
-                            .await?;
-                    } else {
-                        let channel_id = ctx.channel_id();
-                        let mut embed_msg = CreateEmbed::new()
-                            .title("🔄 Autoplay")
-                            .description(format!(
-                                "**[{}]({})**\nby {}",
-                                track.info.title,
-                                track.info.uri.clone().unwrap_or_default(),
-                                track.info.author
-                            ))
-                            .color(0x1DB954)
-                            .footer(serenity::all::CreateEmbedFooter::new(
-                                "Use /autoplay to disable",
-                            ));
-
-                        // Add thumbnail if available
-                        if let Some(ref artwork) = track.info.artwork_url {
-                            embed_msg = embed_msg.thumbnail(artwork);
-                        }
-
-                        let message = serenity::all::CreateMessage::new().embed(embed_msg);
-                        let _ = channel_id.send_message(&ctx.http(), message).await;
-                    }
-                } else {
-                    player_ctx.stop_now().await?;
-                    send_embed(ctx, embed::info("Autoplay", "No related songs found")).await?;
-                }
-            } else {
-                player_ctx.stop_now().await?;
-                send_embed(
-                    ctx,
-                    embed::info("Queue Empty", "No more songs in queue, playback stopped"),
-                )
-                .await?;
+        // Finding an autoplay song can take a few seconds
+        ctx.defer().await?;
+        let autoplay = player.is_autoplay(guild_id);
+        let skipped_to = skip_track(player, &player_ctx, guild_id, Some(ctx.channel_id())).await?;
+        let embed = match skipped_to {
+            Some(next_track) => embed::music(
+                "Skipped",
+                &format!("Now playing: **{}**", next_track.track.info.title),
+            ),
+            // Autoplay announces the song it picked on its own
+            None if player.get_queue(guild_id).current.is_some() => {
+                embed::music("Skipped", "Queue empty - autoplay picked a related song")
             }
-        }
+            None if autoplay => embed::info("Autoplay", "No related songs found"),
+            None => embed::info("Queue Empty", "No more songs in queue, playback stopped"),
+        };
+        send_embed(ctx, embed).await?;
+        refresh_control_panel(ctx.http(), guild_id).await;
     } else {
         send_embed(
             ctx,
@@ -695,72 +769,12 @@ pub async fn skip(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-/// Search for autoplay track using YouTube API
-async fn search_autoplay_track(
-    player: &crate::services::music::MusicPlayer,
-    guild_id: serenity::all::GuildId,
-) -> Option<lavalink_rs::model::track::TrackData> {
-    use crate::services::youtube::get_global_youtube;
-
-    let last_title = player.get_last_track_title(guild_id)?;
-    let youtube = get_global_youtube()?;
-
-    // Simplify search query - take first 2 words + "mix"
-    let simplified: String = last_title
-        .split_whitespace()
-        .take(2)
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    let search_query = format!("{} mix", simplified);
-    println!("[MUSIC] Skip autoplay searching: {}", search_query);
-
-    let videos = youtube.search(&search_query, 10).await.ok()?;
-    if videos.is_empty() {
-        return None;
-    }
-
-    // Filter out similar titles
-    let last_title_lower = last_title.to_lowercase();
-    let simplified_lower = simplified.to_lowercase();
-
-    let filtered: Vec<_> = videos
-        .iter()
-        .filter(|v| {
-            let title_lower = v.title.to_lowercase();
-            !title_lower.contains(&simplified_lower) && !last_title_lower.contains(&title_lower)
-        })
-        .collect();
-
-    let selected = if !filtered.is_empty() {
-        &filtered[0]
-    } else if videos.len() > 1 {
-        &videos[1]
-    } else {
-        return None;
-    };
-
-    println!("[MUSIC] Skip autoplay found: {}", selected.title);
-
-    // Load track via Lavalink
-    let lavalink_guild_id = lavalink_rs::model::GuildId(guild_id.get());
-    let load_result = player
-        .lavalink
-        .load_tracks(lavalink_guild_id, &selected.url)
-        .await
-        .ok()?;
-
-    use lavalink_rs::model::track::TrackLoadData;
-    match load_result.data {
-        Some(TrackLoadData::Track(t)) => Some(t),
-        Some(TrackLoadData::Search(mut t)) if !t.is_empty() => Some(t.remove(0)),
-        Some(TrackLoadData::Playlist(mut p)) if !p.tracks.is_empty() => Some(p.tracks.remove(0)),
-        _ => None,
-    }
-}
-
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -780,12 +794,17 @@ pub async fn stop(ctx: Context<'_>) -> Result<(), Error> {
         player.clear_queue(guild_id);
         send_embed(ctx, embed::info("Queue Cleared", "Queue has been cleared")).await?;
     }
+    refresh_control_panel(ctx.http(), guild_id).await;
 
     Ok(())
 }
 
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -800,10 +819,10 @@ pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
         return Ok(());
     }
 
-    let mut description = String::new();
+    let mut header = String::new();
 
     if let Some(current) = &queue.current {
-        description.push_str(&format!(
+        header.push_str(&format!(
             "**Now Playing:**\n[{}]({}) - Requested by {}\n\n",
             current.track.info.title,
             current.track.info.uri.clone().unwrap_or_default(),
@@ -811,55 +830,87 @@ pub async fn queue(ctx: Context<'_>) -> Result<(), Error> {
         ));
     }
 
-    if !queue.is_empty() {
-        description.push_str("**Queue:**\n");
-        for (i, track) in queue.tracks.iter().take(10).enumerate() {
-            description.push_str(&format!(
-                "{}. [{}]({}) - {}\n",
-                i + 1,
-                track.track.info.title,
-                track.track.info.uri.clone().unwrap_or_default(),
-                format_duration(track.track.info.length)
-            ));
-        }
-        if queue.len() > 10 {
-            description.push_str(&format!("\n... and {} more songs", queue.len() - 10));
-        }
-    }
+    let total_songs = queue.len() + if queue.current.is_some() { 1 } else { 0 };
+    let loop_mode = queue.loop_mode.label();
 
-    let embed = CreateEmbed::new()
-        .title("Music Queue")
-        .description(description)
-        .field(
-            "Total Songs",
-            format!(
-                "{}",
-                queue.len() + if queue.current.is_some() { 1 } else { 0 }
-            ),
-            true,
-        )
-        .field(
-            "Loop",
-            {
-                use crate::services::music::queue::LoopMode;
-                match queue.loop_mode {
-                    LoopMode::Off => "Off",
-                    LoopMode::Track => "🔂 Track",
-                    LoopMode::Queue => "🔁 Queue",
+    let tracks: Vec<_> = queue.tracks.iter().enumerate().collect();
+    let chunks: Vec<_> = if tracks.is_empty() {
+        vec![&[][..]]
+    } else {
+        tracks.chunks(QUEUE_PAGE_SIZE).collect()
+    };
+
+    let pages = chunks
+        .into_iter()
+        .map(|chunk| {
+            let mut description = header.clone();
+            if !chunk.is_empty() {
+                description.push_str("**Queue:**\n");
+                for (i, track) in chunk {
+                    description.push_str(&format!(
+                        "{}. [{}]({}) - {}\n",
+                        i + 1,
+                        track.track.info.title,
+                        track.track.info.uri.clone().unwrap_or_default(),
+                        format_duration(track.track.info.length)
+                    ));
                 }
-            },
-            true,
-        )
-        .field("Volume", format!("{}%", queue.volume), true)
-        .color(embed::COLOR_MUSIC);
+            }
 
-    send_embed(ctx, embed).await?;
+            CreateEmbed::new()
+                .title("Music Queue")
+                .description(description)
+                .field("Total Songs", total_songs.to_string(), true)
+                .field("Loop", loop_mode, true)
+                .field("Volume", format!("{}%", queue.volume), true)
+                .color(embed::COLOR_MUSIC)
+        })
+        .collect();
+
+    paginate(ctx, pages, 120).await?;
+
+    Ok(())
+}
+
+/// Post a control panel with playback buttons
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn player(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let player = ctx
+        .data()
+        .music_player
+        .as_ref()
+        .ok_or("Music player not available")?;
+
+    // Only one panel per guild; the old one would stop updating anyway
+    if let Some((channel_id, message_id)) = player.get_control_message(guild_id) {
+        let _ = channel_id.delete_message(&ctx.http(), message_id).await;
+    }
+
+    let (embed, components) = control_panel(&player.get_queue(guild_id));
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed)
+                .components(components),
+        )
+        .await?;
+    let msg = reply.message().await?;
+    player.set_control_message(guild_id, Some((msg.channel_id, msg.id)));
 
     Ok(())
 }
 
 #[poise::command(slash_command, prefix_command, guild_only, aliases("np"))]
 pub async fn nowplaying(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -899,6 +950,173 @@ pub async fn nowplaying(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Where `/seek` should move playback to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SeekTarget {
+    Absolute(u64),
+    Forward(u64),
+    Back(u64),
+}
+
+/// Parse `+30`, `-15`, `1:30`, `1:02:03` or `90` (seconds) into milliseconds
+fn parse_seek(input: &str) -> Option<SeekTarget> {
+    let input = input.trim();
+    let (rest, kind): (&str, fn(u64) -> SeekTarget) = match input.chars().next()? {
+        '+' => (&input[1..], SeekTarget::Forward),
+        '-' => (&input[1..], SeekTarget::Back),
+        _ => (input, SeekTarget::Absolute),
+    };
+
+    let parts: Vec<u64> = rest
+        .trim()
+        .split(':')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<_>>()?;
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let secs = parts
+        .iter()
+        .try_fold(0u64, |acc, p| acc.checked_mul(60)?.checked_add(*p))?;
+
+    Some(kind(secs.checked_mul(1000)?))
+}
+
+/// Text seek bar like `▬▬▬🔘▬▬▬▬▬▬▬`
+fn seek_bar(position: u64, length: u64) -> String {
+    const WIDTH: usize = 12;
+    let filled = if length == 0 {
+        0
+    } else {
+        ((position as f64 / length as f64) * WIDTH as f64) as usize
+    }
+    .min(WIDTH - 1);
+    format!("{}🔘{}", "▬".repeat(filled), "▬".repeat(WIDTH - 1 - filled))
+}
+
+/// Seek within the current track
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn seek(
+    ctx: Context<'_>,
+    #[description = "Position: +30 (forward), -15 (back) or 1:30 (absolute)"] position: String,
+) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let player = ctx
+        .data()
+        .music_player
+        .as_ref()
+        .ok_or("Music player not available")?;
+
+    let Some(target) = parse_seek(&position) else {
+        send_embed(
+            ctx,
+            embed::error(
+                "Invalid Position",
+                "Use `+30` to skip forward, `-15` to go back or `1:30` for an exact time",
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let queue = player.get_queue(guild_id);
+    let (Some(player_ctx), Some(current)) = (player.get_player_context(guild_id), &queue.current)
+    else {
+        send_embed(
+            ctx,
+            embed::error("Not Playing", "No song is currently playing"),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let track_info = &current.track.info;
+    if track_info.is_stream || !track_info.is_seekable {
+        send_embed(
+            ctx,
+            embed::error("Cannot Seek", "This track doesn't support seeking"),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let length = track_info.length;
+    let new_position = match target {
+        SeekTarget::Absolute(ms) => ms,
+        SeekTarget::Forward(ms) | SeekTarget::Back(ms) => {
+            let current_position = player_ctx.get_player().await?.state.position;
+            if matches!(target, SeekTarget::Forward(_)) {
+                current_position.saturating_add(ms)
+            } else {
+                current_position.saturating_sub(ms)
+            }
+        }
+    }
+    .min(length);
+
+    player_ctx
+        .set_position(Duration::from_millis(new_position))
+        .await?;
+
+    let now_playing = embed::now_playing(
+        &track_info.title,
+        &track_info.uri.clone().unwrap_or_default(),
+        &track_info.author,
+        &format_duration(length),
+        &current.requester_name,
+        queue.volume,
+        queue.is_looping,
+        track_info.artwork_url.as_deref(),
+    )
+    .field(
+        "Position",
+        format!(
+            "{} `{} / {}`",
+            seek_bar(new_position, length),
+            format_duration(new_position),
+            format_duration(length)
+        ),
+        false,
+    );
+
+    // Edit the existing Now Playing message instead of posting another one
+    let edited = match player.get_now_playing_message(guild_id) {
+        Some((channel_id, message_id)) => channel_id
+            .edit_message(
+                &ctx.http(),
+                message_id,
+                serenity::all::EditMessage::new().embed(now_playing.clone()),
+            )
+            .await
+            .is_ok(),
+        None => false,
+    };
+
+    if edited {
+        ctx.send(
+            poise::CreateReply::default()
+                .embed(embed::music(
+                    "Seeked",
+                    &format!("Jumped to **{}**", format_duration(new_position)),
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+    } else {
+        let reply = ctx
+            .send(poise::CreateReply::default().embed(now_playing))
+            .await?;
+        let msg = reply.message().await?;
+        player.set_now_playing_message(guild_id, Some((msg.channel_id, msg.id)));
+    }
+
+    Ok(())
+}
+
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn volume(
     ctx: Context<'_>,
@@ -907,6 +1125,10 @@ pub async fn volume(
     #[max = 150]
     level: u8,
 ) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -951,6 +1173,10 @@ pub async fn repeat(
     ctx: Context<'_>,
     #[description = "'q' for queue repeat, empty for track"] mode: Option<String>,
 ) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     use crate::services::music::queue::LoopMode;
 
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
@@ -999,6 +1225,10 @@ pub async fn repeat(
 
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn shuffle(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -1021,6 +1251,10 @@ pub async fn remove(
     ctx: Context<'_>,
     #[description = "Position in queue (1, 2, 3, ...)"] position: usize,
 ) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -1056,8 +1290,71 @@ pub async fn remove(
     Ok(())
 }
 
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn jump(
+    ctx: Context<'_>,
+    #[description = "Position in queue (1, 2, 3, ...)"] position: usize,
+) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+    let player = ctx
+        .data()
+        .music_player
+        .as_ref()
+        .ok_or("Music player not available")?;
+
+    let Some(player_ctx) = player.get_player_context(guild_id) else {
+        send_embed(
+            ctx,
+            embed::error("Not Playing", "The bot is not playing music"),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let queue_len = player.get_queue(guild_id).len();
+    if position == 0 || position > queue_len {
+        send_embed(
+            ctx,
+            embed::error(
+                "Invalid Position",
+                &format!("Position must be between 1 and {}", queue_len.max(1)),
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    match player.jump_to(guild_id, position) {
+        Some(jumped) => {
+            player.set_last_track_title(guild_id, Some(jumped.track.info.title.clone()));
+            player_ctx.play(&jumped.track).await?;
+            send_embed(
+                ctx,
+                embed::music(
+                    "Jumped",
+                    &format!("Now playing: **{}**", jumped.track.info.title),
+                ),
+            )
+            .await?;
+        }
+        None => {
+            send_embed(ctx, embed::error("Not Found", "No song at that position")).await?;
+        }
+    }
+
+    Ok(())
+}
+
 #[poise::command(slash_command, prefix_command, guild_only)]
 pub async fn autoplay(ctx: Context<'_>) -> Result<(), Error> {
+    if !check_music_channel(ctx).await? {
+        return Ok(());
+    }
+
     let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
     let player = ctx
         .data()
@@ -1106,3 +1403,73 @@ fn format_duration(ms: u64) -> String {
         format!("{:02}:{:02}", mins, secs)
     }
 }
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn music_channel_set(
+    ctx: Context<'_>,
+    #[description = "Channel for music commands"] channel: serenity::all::GuildChannel,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+
+    let pool = ctx.data().db.as_ref();
+    MusicConfigRepository::set_channel(pool, guild_id.get(), channel.id.get()).await?;
+
+    send_embed(
+        ctx,
+        embed::success(
+            "Music Channel Set",
+            &format!(
+                "Music commands can now only be used in {}",
+                channel.mention()
+            ),
+        ),
+    )
+    .await
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD"
+)]
+pub async fn music_channel_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a server")?;
+
+    let pool = ctx.data().db.as_ref();
+    MusicConfigRepository::clear_channel(pool, guild_id.get()).await?;
+
+    send_embed(
+        ctx,
+        embed::success(
+            "Music Channel Disabled",
+            "Music commands can be used in any channel",
+        ),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seek() {
+        assert_eq!(parse_seek("1:30"), Some(SeekTarget::Absolute(90_000)));
+        assert_eq!(parse_seek("+15"), Some(SeekTarget::Forward(15_000)));
+        assert_eq!(parse_seek("-1:00"), Some(SeekTarget::Back(60_000)));
+        assert_eq!(parse_seek("1:2:3:4"), None);
+        assert_eq!(parse_seek("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_seek_rejects_overflow() {
+        assert_eq!(parse_seek("18446744073709551615"), None);
+        assert_eq!(parse_seek("99999999999999999:59"), None);
+    }
+}
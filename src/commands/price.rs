@@ -1,18 +1,21 @@
 use crate::commands::Data;
-use crate::services::tiingo::{AlertCondition, PriceAlert, get_global_tiingo};
+use crate::repository::{MarketSummaryRepository, PriceBoardRepository, WatchlistRepository};
+use crate::services::market_summary::{build_summary, guild_symbols};
+use crate::services::price_board::{MAX_BOARD_SYMBOLS, render_board};
+use crate::services::tiingo::{
+    AlertCondition, AlertNotify, DEFAULT_REARM_PIPS, ForexPrice, MAX_MOVE_WINDOW_MINS, PriceAlert,
+    SPREAD_REARM_SECS, Timeframe, format_price, get_global_tiingo, next_alert_id,
+};
+use crate::utils::chart::render_candles;
+use crate::utils::pagination::paginate;
+use crate::utils::time::parse_duration;
 use chrono::Utc;
-use poise::serenity_prelude::CreateEmbed;
-use std::sync::atomic::{AtomicI64, Ordering};
+use poise::serenity_prelude::{self as serenity, CreateEmbed};
+use std::collections::BTreeMap;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, Data, Error>;
 
-static ALERT_ID_COUNTER: AtomicI64 = AtomicI64::new(1);
-
-fn next_alert_id() -> i64 {
-    ALERT_ID_COUNTER.fetch_add(1, Ordering::SeqCst)
-}
-
 async fn send_embed(ctx: Context<'_>, embed: CreateEmbed) -> Result<(), Error> {
     ctx.send(poise::CreateReply::default().embed(embed)).await?;
     Ok(())
@@ -53,18 +56,24 @@ pub async fn price(
 
             let embed = CreateEmbed::new()
                 .title(format!("💱 {}", symbol.to_uppercase()))
-                .field("Bid", format!("{:.5}", price.bid), true)
-                .field("Ask", format!("{:.5}", price.ask), true)
+                .field("Bid", format_price(&symbol_lower, price.bid), true)
+                .field("Ask", format_price(&symbol_lower, price.ask), true)
                 .field("Spread", format!("{:.1} pips", spread_pips), true)
-                .field("Mid", format!("{:.5}", price.mid), false)
-                .footer(poise::serenity_prelude::CreateEmbedFooter::new(format!(
-                    "Updated: {}",
-                    time_str
-                )))
+                .field("Mid", format_price(&symbol_lower, price.mid), false)
+                .footer(poise::serenity_prelude::CreateEmbedFooter::new(
+                    if price.delayed {
+                        format!("Updated: {} • Delayed (REST polling)", time_str)
+                    } else {
+                        format!("Updated: {}", time_str)
+                    },
+                ))
                 .color(0x1DB954);
 
             send_embed(ctx, embed).await?;
         }
+        None if !tiingo.is_subscribed(&symbol_lower) && is_valid_symbol(&symbol_lower) => {
+            offer_subscription(ctx, &symbol_lower).await?;
+        }
         None => {
             let available = tiingo
                 .get_all_prices()
@@ -101,13 +110,256 @@ pub async fn price(
     Ok(())
 }
 
+/// Tiingo tickers are short alphanumeric codes like `eurusd`
+fn is_valid_symbol(symbol: &str) -> bool {
+    (3..=12).contains(&symbol.len()) && symbol.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Tell the user a symbol isn't streamed. Owners get a button to subscribe it
+async fn offer_subscription(ctx: Context<'_>, symbol: &str) -> Result<(), Error> {
+    let embed = CreateEmbed::new()
+        .title("Symbol Not Subscribed")
+        .color(0xffa500);
+
+    if !ctx.data().owners.contains(&ctx.author().id) {
+        let embed = embed.description(format!(
+            "**{}** isn't in the price stream.\n\nA bot owner can add it with `/price_subscribe {}`",
+            symbol.to_uppercase(),
+            symbol
+        ));
+        return send_embed(ctx, embed).await;
+    }
+
+    let button_id = format!("{}_price_subscribe", ctx.id());
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(embed.clone().description(format!(
+                    "**{}** isn't in the price stream. Subscribe to it now?",
+                    symbol.to_uppercase()
+                )))
+                .components(vec![serenity::CreateActionRow::Buttons(vec![
+                    serenity::CreateButton::new(&button_id)
+                        .label("Subscribe")
+                        .style(serenity::ButtonStyle::Primary),
+                ])]),
+        )
+        .await?;
+
+    let filter_id = button_id.clone();
+    let interaction = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .timeout(std::time::Duration::from_secs(60))
+        .filter(move |mci| mci.data.custom_id == filter_id)
+        .await;
+
+    let Some(interaction) = interaction else {
+        reply
+            .edit(ctx, poise::CreateReply::default().components(vec![]))
+            .await?;
+        return Ok(());
+    };
+
+    if let Some(tiingo) = get_global_tiingo() {
+        tiingo.subscribe(symbol);
+    }
+    interaction
+        .create_response(
+            ctx,
+            serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(subscribed_embed(symbol))
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+fn subscribed_embed(symbol: &str) -> CreateEmbed {
+    CreateEmbed::new()
+        .title("Symbol Subscribed")
+        .description(format!(
+            "**{}** was added to the price stream. Prices should arrive within a few seconds.\n\n\
+            Add it to `TIINGO_SYMBOLS` to keep it after a restart.",
+            symbol.to_uppercase()
+        ))
+        .color(0x00ff00)
+}
+
+/// Add a symbol to the Tiingo price stream
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn price_subscribe(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., eurjpy)"] symbol: String,
+) -> Result<(), Error> {
+    let Some(tiingo) = get_global_tiingo() else {
+        return send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Error")
+                .description("Price service not available")
+                .color(0xff0000),
+        )
+        .await;
+    };
+
+    let symbol = symbol.trim().to_lowercase();
+    if !is_valid_symbol(&symbol) {
+        return send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Invalid Symbol")
+                .description("Use a ticker like `eurjpy` or `xagusd`")
+                .color(0xff0000),
+        )
+        .await;
+    }
+
+    let embed = if tiingo.subscribe(&symbol) {
+        subscribed_embed(&symbol)
+    } else {
+        CreateEmbed::new()
+            .title("Already Subscribed")
+            .description(format!(
+                "**{}** is already in the price stream",
+                symbol.to_uppercase()
+            ))
+            .color(0x808080)
+    };
+    send_embed(ctx, embed).await
+}
+
+/// Candles needed before `/chart` draws anything
+const MIN_CHART_CANDLES: usize = 5;
+
+/// Candlestick chart built from prices seen since the bot started
+#[poise::command(slash_command, prefix_command)]
+pub async fn chart(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd, eurusd, gbpusd)"] symbol: String,
+    #[description = "Candle size (default 5m)"] timeframe: Option<Timeframe>,
+) -> Result<(), Error> {
+    let Some(tiingo) = get_global_tiingo() else {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Error")
+                .description("Price service not available")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let timeframe = timeframe.unwrap_or(Timeframe::M5);
+    let candles = tiingo.get_candles(&symbol, timeframe);
+    if candles.len() < MIN_CHART_CANDLES {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Not Enough Data")
+                .description(format!(
+                    "Only **{}** {} candle(s) collected for **{}** so far; at least {} are needed.\n\nTry a shorter timeframe or check back later.",
+                    candles.len(),
+                    timeframe.label(),
+                    symbol.to_uppercase(),
+                    MIN_CHART_CANDLES
+                ))
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+    let bars = candles.clone();
+    let png = tokio::task::spawn_blocking(move || render_candles(&bars)).await??;
+
+    let first = candles[0];
+    let last = candles[candles.len() - 1];
+    let high = candles
+        .iter()
+        .map(|c| c.high)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+    let change = (last.close - first.open) / first.open * 100.0;
+
+    let file_name = format!("{}_{}.png", symbol.to_lowercase(), timeframe.label());
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "📈 {} • {}",
+            symbol.to_uppercase(),
+            timeframe.label()
+        ))
+        .image(format!("attachment://{}", file_name))
+        .field("Open", format_price(&symbol, first.open), true)
+        .field("High", format_price(&symbol, high), true)
+        .field("Low", format_price(&symbol, low), true)
+        .field("Last", format_price(&symbol, last.close), true)
+        .field("Change", format!("{:+.2}%", change), true)
+        .field(
+            "Range",
+            format!(
+                "<t:{}:t> – <t:{}:t>",
+                first.start.timestamp(),
+                last.start.timestamp()
+            ),
+            true,
+        )
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "{} candles • mid prices",
+            candles.len()
+        )))
+        .color(if change >= 0.0 { 0x2ECC71 } else { 0xE74C3C });
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed)
+            .attachment(serenity::CreateAttachment::bytes(png, file_name)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Price-level condition for `/alert` and `/alertedit`
+fn parse_condition(input: &str) -> Option<AlertCondition> {
+    match input.trim().to_lowercase().as_str() {
+        "above" | ">" | "up" => Some(AlertCondition::Above),
+        "below" | "<" | "down" => Some(AlertCondition::Below),
+        "cross" | "x" => Some(AlertCondition::Cross),
+        "touch" => Some(AlertCondition::Touch),
+        _ => None,
+    }
+}
+
+/// Longest expiry `/alert` and `/alertedit` accept
+const MAX_ALERT_EXPIRY_DAYS: i64 = 365;
+
+/// Expiry time for a duration like `4h` from now, or `None` when the input is
+/// invalid, zero or longer than the maximum
+fn parse_expiry(input: &str) -> Option<chrono::DateTime<Utc>> {
+    let duration = chrono::Duration::from_std(parse_duration(input)?).ok()?;
+    if duration.is_zero() || duration > chrono::Duration::days(MAX_ALERT_EXPIRY_DAYS) {
+        return None;
+    }
+    Utc::now().checked_add_signed(duration)
+}
+
 /// Set a price alert
 #[poise::command(slash_command, prefix_command)]
+#[allow(clippy::too_many_arguments)]
 pub async fn alert(
     ctx: Context<'_>,
     #[description = "Symbol (e.g., xauusd)"] symbol: String,
-    #[description = "Condition: above or below"] condition: String,
+    #[description = "Condition: above, below, cross or touch"] condition: String,
     #[description = "Target price"] target: f64,
+    #[description = "Touch alerts: pips to move away before re-arming"] rearm_pips: Option<f64>,
+    #[description = "Keep the alert after it fires (15 min cooldown)"] repeat: Option<bool>,
+    #[description = "Remove the alert if not triggered within this time (e.g. 4h, 7d)"]
+    expires: Option<String>,
+    #[description = "Where to send the alert (default: this channel)"] notify: Option<AlertNotify>,
 ) -> Result<(), Error> {
     let tiingo = match get_global_tiingo() {
         Some(t) => t,
@@ -124,15 +376,14 @@ pub async fn alert(
         }
     };
 
-    let condition_parsed = match condition.to_lowercase().as_str() {
-        "above" | ">" | "up" => AlertCondition::Above,
-        "below" | "<" | "down" => AlertCondition::Below,
-        _ => {
+    let condition_parsed = match parse_condition(&condition) {
+        Some(condition) => condition,
+        None => {
             send_embed(
                 ctx,
                 CreateEmbed::new()
                     .title("Invalid Condition")
-                    .description("Use `above` or `below`")
+                    .description("Use `above`, `below`, `cross` or `touch`")
                     .color(0xff0000),
             )
             .await?;
@@ -140,6 +391,28 @@ pub async fn alert(
         }
     };
 
+    let expires_at = match expires {
+        Some(expires) => {
+            let Some(expires_at) = parse_expiry(&expires) else {
+                send_embed(
+                    ctx,
+                    CreateEmbed::new()
+                        .title("Invalid Expiry")
+                        .description(format!(
+                            "Use a duration like `30m`, `4h` or `7d`, up to {} days",
+                            MAX_ALERT_EXPIRY_DAYS
+                        ))
+                        .color(0xff0000),
+                )
+                .await?;
+                return Ok(());
+            };
+            Some(expires_at)
+        }
+        None => None,
+    };
+    let repeat = repeat.unwrap_or(false);
+
     let guild_id = ctx.guild_id().map(|g| g.get()).unwrap_or(0);
 
     let alert = PriceAlert {
@@ -151,6 +424,17 @@ pub async fn alert(
         condition: condition_parsed.clone(),
         target_price: target,
         created_at: Utc::now(),
+        last_side: None,
+        rearm_pips: rearm_pips.unwrap_or(DEFAULT_REARM_PIPS).max(0.0),
+        // Touch alerts start armed only if price is already away from the level
+        armed: condition_parsed != AlertCondition::Touch,
+        repeat,
+        expires_at,
+        last_triggered: None,
+        window_mins: None,
+        move_start: None,
+        calm_since: None,
+        notify: notify.unwrap_or_default(),
     };
 
     let alert_id = alert.id;
@@ -158,22 +442,34 @@ pub async fn alert(
 
     let current_price = tiingo
         .get_price(&symbol.to_lowercase())
-        .map(|p| format!("{:.5}", p.mid))
+        .map(|p| format_price(&symbol, p.mid))
         .unwrap_or_else(|| "N/A".to_string());
 
+    let mut description = format!(
+        "Alert **#{}** set!\n\n**{}** {} **{}**\n\nCurrent: {}",
+        alert_id,
+        symbol.to_uppercase(),
+        condition_parsed,
+        format_price(&symbol, target),
+        current_price
+    );
+    if let Some(expires_at) = expires_at {
+        description.push_str(&format!("\nExpires: <t:{}:R>", expires_at.timestamp()));
+    }
+    if notify == Some(AlertNotify::Dm) {
+        description.push_str("\nDelivered by DM (falls back to this channel if DMs are closed)");
+    }
+
     let embed = CreateEmbed::new()
         .title("Alert Created")
-        .description(format!(
-            "Alert **#{}** set!\n\n**{}** {} **{:.5}**\n\nCurrent: {}",
-            alert_id,
-            symbol.to_uppercase(),
-            condition_parsed,
-            target,
-            current_price
-        ))
+        .description(description)
         .color(0x00ff00)
         .footer(poise::serenity_prelude::CreateEmbedFooter::new(
-            "You'll be notified when the price is reached",
+            if repeat || condition_parsed == AlertCondition::Touch {
+                "You'll be notified every time the price is reached"
+            } else {
+                "You'll be notified when the price is reached"
+            },
         ));
 
     send_embed(ctx, embed).await?;
@@ -181,6 +477,184 @@ pub async fn alert(
     Ok(())
 }
 
+/// Alert when price moves by a percentage within a time window
+#[poise::command(slash_command, prefix_command)]
+pub async fn alert_move(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd)"] symbol: String,
+    #[description = "Percent change that triggers the alert (e.g., 0.5)"] percent: f64,
+    #[description = "Rolling window (e.g. 30m, 1h, max 4h). Default 1h"] window: Option<String>,
+) -> Result<(), Error> {
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if !(percent > 0.0 && percent <= 100.0) {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Invalid Percent")
+                .description("Percent must be between 0 and 100")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let window_mins = match window {
+        Some(window) => match parse_duration(&window).map(|d| d.as_secs() as i64 / 60) {
+            Some(mins) if (1..=MAX_MOVE_WINDOW_MINS).contains(&mins) => mins,
+            _ => {
+                send_embed(
+                    ctx,
+                    CreateEmbed::new()
+                        .title("Invalid Window")
+                        .description(format!(
+                            "Use a window between `1m` and `{}h`, e.g. `30m` or `1h`",
+                            MAX_MOVE_WINDOW_MINS / 60
+                        ))
+                        .color(0xff0000),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+        None => 60,
+    };
+
+    let alert = PriceAlert {
+        id: next_alert_id(),
+        guild_id: ctx.guild_id().map(|g| g.get()).unwrap_or(0),
+        user_id: ctx.author().id.get(),
+        channel_id: ctx.channel_id().get(),
+        symbol: symbol.to_lowercase(),
+        condition: AlertCondition::Move,
+        target_price: percent,
+        created_at: Utc::now(),
+        last_side: None,
+        rearm_pips: DEFAULT_REARM_PIPS,
+        armed: true,
+        repeat: false,
+        expires_at: None,
+        last_triggered: None,
+        window_mins: Some(window_mins),
+        move_start: None,
+        calm_since: None,
+        notify: AlertNotify::Channel,
+    };
+
+    let description = format!("Alert **#{}** set!\n\n**{}**", alert.id, alert.describe());
+    tiingo.add_alert(alert);
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Alert Created")
+            .description(description)
+            .color(0x00ff00)
+            .footer(poise::serenity_prelude::CreateEmbedFooter::new(
+                "Measured from the price at the start of the window",
+            )),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Alert when a symbol's spread widens past a number of pips
+#[poise::command(slash_command, prefix_command)]
+pub async fn spreadalert(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd)"] symbol: String,
+    #[description = "Spread in pips that triggers the alert"] pips: f64,
+) -> Result<(), Error> {
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    if !(pips > 0.0 && pips.is_finite()) {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Invalid Threshold")
+                .description("Pips must be greater than 0")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let alert = PriceAlert {
+        id: next_alert_id(),
+        guild_id: ctx.guild_id().map(|g| g.get()).unwrap_or(0),
+        user_id: ctx.author().id.get(),
+        channel_id: ctx.channel_id().get(),
+        symbol: symbol.to_lowercase(),
+        condition: AlertCondition::Spread,
+        target_price: pips,
+        created_at: Utc::now(),
+        last_side: None,
+        rearm_pips: DEFAULT_REARM_PIPS,
+        armed: true,
+        repeat: false,
+        expires_at: None,
+        last_triggered: None,
+        window_mins: None,
+        move_start: None,
+        calm_since: None,
+        notify: AlertNotify::Channel,
+    };
+
+    let current = tiingo
+        .get_price(&symbol.to_lowercase())
+        .map(|p| format!("{:.1} pips", p.spread_pips()))
+        .unwrap_or_else(|| "N/A".to_string());
+    let description = format!(
+        "Alert **#{}** set!\n\n**{}**\n\nCurrent spread: {}",
+        alert.id,
+        alert.describe(),
+        current
+    );
+    tiingo.add_alert(alert);
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Alert Created")
+            .description(description)
+            .color(0x00ff00)
+            .footer(poise::serenity_prelude::CreateEmbedFooter::new(format!(
+                "Notifies once per spike, re-arms after {}s back under the threshold",
+                SPREAD_REARM_SECS
+            ))),
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// List your active alerts
 #[poise::command(slash_command, prefix_command)]
 pub async fn alerts(ctx: Context<'_>) -> Result<(), Error> {
@@ -206,7 +680,7 @@ pub async fn alerts(ctx: Context<'_>) -> Result<(), Error> {
             ctx,
             CreateEmbed::new()
                 .title("Your Alerts")
-                .description("No active alerts.\n\nUse `/alert <symbol> <above/below> <price>` to create one.")
+                .description("No active alerts.\n\nUse `/alert <symbol> <above/below/cross/touch> <price>` to create one, `/alert_move` for percentage moves, or `/spreadalert` for spread spikes.")
                 .color(0x808080),
         )
         .await?;
@@ -215,12 +689,29 @@ pub async fn alerts(ctx: Context<'_>) -> Result<(), Error> {
 
     let mut description = String::new();
     for alert in &user_alerts {
+        let mut flags = Vec::new();
+        if alert.condition == AlertCondition::Spread {
+            flags.push("📏 spread".to_string());
+        } else if !alert.is_one_shot() {
+            flags.push("🔁 repeat".to_string());
+        }
+        if alert.notify == AlertNotify::Dm {
+            flags.push("📩 DM".to_string());
+        }
+        if let Some(expires_at) = alert.expires_at {
+            flags.push(format!("expires <t:{}:R>", expires_at.timestamp()));
+        }
+        let flags = if flags.is_empty() {
+            String::new()
+        } else {
+            format!(" • {}", flags.join(" • "))
+        };
+
         description.push_str(&format!(
-            "**#{}** {} {} {:.5}\n",
+            "**#{}** {}{}\n",
             alert.id,
-            alert.symbol.to_uppercase(),
-            alert.condition,
-            alert.target_price
+            alert.describe(),
+            flags
         ));
     }
 
@@ -285,3 +776,891 @@ pub async fn alertremove(
 
     Ok(())
 }
+
+/// Whether an alert set up as `alert` would fire on the very next quote.
+/// Cross and Touch alerts start from the current side, so they never do
+fn fires_immediately(alert: &PriceAlert, price: &ForexPrice) -> bool {
+    match alert.condition {
+        AlertCondition::Above => price.mid >= alert.target_price,
+        AlertCondition::Below => price.mid <= alert.target_price,
+        AlertCondition::Spread => price.spread_pips() > alert.target_price,
+        _ => false,
+    }
+}
+
+fn alert_summary(alert: &PriceAlert) -> String {
+    let expiry = match alert.expires_at {
+        Some(expires_at) => format!("expires <t:{}:R>", expires_at.timestamp()),
+        None => "no expiry".to_string(),
+    };
+    format!("{}\n{}", alert.describe(), expiry)
+}
+
+/// Change the target, condition or expiry of one of your alerts
+#[poise::command(slash_command, prefix_command)]
+pub async fn alertedit(
+    ctx: Context<'_>,
+    #[description = "Alert ID to edit"] id: i64,
+    #[description = "New target (price, percent for move alerts, pips for spread alerts)"]
+    target: Option<f64>,
+    #[description = "New condition: above, below, cross or touch"] condition: Option<String>,
+    #[description = "New expiry from now (e.g. 4h, 7d) or \"never\""] expires: Option<String>,
+    #[description = "Save even if the alert would fire right away"] force: Option<bool>,
+) -> Result<(), Error> {
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let user_id = ctx.author().id.get();
+    let Some(current) = tiingo
+        .get_user_alerts(user_id)
+        .into_iter()
+        .find(|a| a.id == id)
+    else {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Not Found")
+                .description(format!("Alert #{} not found or doesn't belong to you", id))
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    if target.is_none() && condition.is_none() && expires.is_none() {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Nothing to Change")
+                .description("Pass a new `target`, `condition` or `expires`")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let condition = match condition {
+        Some(_)
+            if matches!(
+                current.condition,
+                AlertCondition::Move | AlertCondition::Spread
+            ) =>
+        {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Invalid Condition")
+                    .description(format!(
+                        "The condition of a {} alert can't be changed, only its target",
+                        current.condition
+                    ))
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+        Some(condition) => match parse_condition(&condition) {
+            Some(condition) => Some(condition),
+            None => {
+                send_embed(
+                    ctx,
+                    CreateEmbed::new()
+                        .title("Invalid Condition")
+                        .description("Use `above`, `below`, `cross` or `touch`")
+                        .color(0xff0000),
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let expires_at = match expires.as_deref().map(str::trim) {
+        Some(never) if never.eq_ignore_ascii_case("never") => Some(None),
+        Some(expires) => {
+            let Some(expires_at) = parse_expiry(expires) else {
+                send_embed(
+                    ctx,
+                    CreateEmbed::new()
+                        .title("Invalid Expiry")
+                        .description(format!(
+                            "Use a duration like `30m`, `4h` or `7d` up to {} days, or `never`",
+                            MAX_ALERT_EXPIRY_DAYS
+                        ))
+                        .color(0xff0000),
+                )
+                .await?;
+                return Ok(());
+            };
+            Some(Some(expires_at))
+        }
+        None => None,
+    };
+
+    let apply = |alert: &mut PriceAlert| {
+        if let Some(target) = target {
+            alert.target_price = target;
+        }
+        if let Some(condition) = &condition {
+            alert.condition = condition.clone();
+        }
+        if let Some(expires_at) = expires_at {
+            alert.expires_at = expires_at;
+        }
+    };
+
+    let mut proposed = current.clone();
+    apply(&mut proposed);
+    if !force.unwrap_or(false)
+        && let Some(price) = tiingo.get_price(&proposed.symbol)
+        && fires_immediately(&proposed, &price)
+    {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Alert Would Fire Immediately")
+                .description(format!(
+                    "**{}** is already met at the current price ({}).\n\n\
+                    Run the command again with `force:true` to save it anyway.",
+                    proposed.describe(),
+                    format_price(&proposed.symbol, price.mid)
+                ))
+                .color(0xffa500),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some((before, after)) = tiingo.edit_alert(user_id, id, apply) else {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Not Found")
+                .description(format!(
+                    "Alert #{} was removed before it could be edited",
+                    id
+                ))
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title(format!("Alert #{} Updated", id))
+            .field("Before", alert_summary(&before), true)
+            .field("After", alert_summary(&after), true)
+            .color(0x00ff00),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Members shown per page of `/alerts_guild list`
+const GUILD_ALERTS_USERS_PER_PAGE: usize = 5;
+/// Alerts listed per member before the rest are summarized
+const GUILD_ALERTS_PER_USER: usize = 8;
+
+/// Alerts created by members of this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    subcommands("alerts_guild_list", "alerts_guild_clear"),
+    subcommand_required
+)]
+pub async fn alerts_guild(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// List every active alert in this server, grouped by member
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "list"
+)]
+pub async fn alerts_guild_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let alerts = tiingo.get_guild_alerts(guild_id);
+    if alerts.is_empty() {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Server Alerts")
+                .description("No active alerts in this server.")
+                .color(0x808080),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut by_user: BTreeMap<u64, Vec<PriceAlert>> = BTreeMap::new();
+    for alert in &alerts {
+        by_user
+            .entry(alert.user_id)
+            .or_default()
+            .push(alert.clone());
+    }
+    let mut users: Vec<(u64, Vec<PriceAlert>)> = by_user.into_iter().collect();
+    users.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(&b.0)));
+
+    let title = format!(
+        "Server Alerts • {} alert(s) from {} member(s)",
+        alerts.len(),
+        users.len()
+    );
+    let pages = users
+        .chunks(GUILD_ALERTS_USERS_PER_PAGE)
+        .map(|chunk| {
+            let description = chunk
+                .iter()
+                .map(|(user_id, user_alerts)| {
+                    let mut lines = vec![format!("<@{}> • **{}**", user_id, user_alerts.len())];
+                    lines.extend(
+                        user_alerts
+                            .iter()
+                            .take(GUILD_ALERTS_PER_USER)
+                            .map(|a| format!("└ `#{}` {}", a.id, a.describe())),
+                    );
+                    if user_alerts.len() > GUILD_ALERTS_PER_USER {
+                        lines.push(format!(
+                            "└ …and {} more",
+                            user_alerts.len() - GUILD_ALERTS_PER_USER
+                        ));
+                    }
+                    lines.join("\n")
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            CreateEmbed::new()
+                .title(&title)
+                .description(description)
+                .color(0x3498db)
+        })
+        .collect();
+
+    paginate(ctx, pages, 120).await?;
+    Ok(())
+}
+
+/// Remove all of a member's alerts in this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    rename = "clear"
+)]
+pub async fn alerts_guild_clear(
+    ctx: Context<'_>,
+    #[description = "Member whose alerts to remove"] user: serenity::User,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let removed = tiingo.remove_guild_user_alerts(guild_id, user.id.get());
+    let embed = if removed > 0 {
+        CreateEmbed::new()
+            .title("Alerts Cleared")
+            .description(format!(
+                "Removed **{}** alert(s) from <@{}>",
+                removed, user.id
+            ))
+            .color(0x00ff00)
+    } else {
+        CreateEmbed::new()
+            .title("No Alerts")
+            .description(format!("<@{}> has no alerts in this server", user.id))
+            .color(0x808080)
+    };
+    send_embed(ctx, embed).await
+}
+
+const WATCHLIST_LIMIT: usize = 15;
+
+/// Edit distance between two symbols, used to suggest close matches
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Up to three known symbols within two edits of `symbol`, closest first
+fn similar_symbols(symbol: &str, known: impl Iterator<Item = String>) -> Vec<String> {
+    let mut matches: Vec<(usize, String)> = known
+        .map(|s| (edit_distance(symbol, &s), s))
+        .filter(|(distance, _)| *distance <= 2)
+        .collect();
+    matches.sort();
+    matches.into_iter().take(3).map(|(_, s)| s).collect()
+}
+
+/// Manage your personal forex watchlist. Without a subcommand, shows it
+#[poise::command(
+    slash_command,
+    prefix_command,
+    subcommands("watchlist_add", "watchlist_remove", "watchlist_show")
+)]
+pub async fn watchlist(ctx: Context<'_>) -> Result<(), Error> {
+    show_watchlist(ctx).await
+}
+
+/// Add a symbol to your watchlist
+#[poise::command(slash_command, prefix_command, rename = "add")]
+pub async fn watchlist_add(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd)"] symbol: String,
+) -> Result<(), Error> {
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let symbol = symbol.trim().to_lowercase();
+    let prices = tiingo.get_all_prices();
+    if !prices.contains_key(&symbol) {
+        let suggestions = similar_symbols(&symbol, prices.into_keys());
+        let mut description = format!(
+            "No price data for **{}**. Check the symbol with `/price` first.",
+            symbol.to_uppercase()
+        );
+        if !suggestions.is_empty() {
+            description.push_str(&format!(
+                "\n\nDid you mean: {}?",
+                suggestions
+                    .iter()
+                    .map(|s| format!("**{}**", s.to_uppercase()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Symbol Not Found")
+                .description(description)
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let user_id = ctx.author().id.get();
+
+    let symbols = WatchlistRepository::get_symbols(pool, user_id).await?;
+    if !symbols.contains(&symbol) && symbols.len() >= WATCHLIST_LIMIT {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Watchlist Full")
+                .description(format!(
+                    "You can watch up to {} symbols. Remove one first.",
+                    WATCHLIST_LIMIT
+                ))
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let added = WatchlistRepository::add_symbol(pool, user_id, &symbol).await?;
+    let description = if added {
+        format!("Added **{}** to your watchlist", symbol.to_uppercase())
+    } else {
+        format!("**{}** is already on your watchlist", symbol.to_uppercase())
+    };
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Watchlist Updated")
+            .description(description)
+            .color(0x00ff00),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Remove a symbol from your watchlist
+#[poise::command(slash_command, prefix_command, rename = "remove")]
+pub async fn watchlist_remove(
+    ctx: Context<'_>,
+    #[description = "Symbol to remove"] symbol: String,
+) -> Result<(), Error> {
+    let symbol = symbol.trim().to_lowercase();
+    let pool = ctx.data().db.as_ref();
+    let removed = WatchlistRepository::remove_symbol(pool, ctx.author().id.get(), &symbol).await?;
+
+    let embed = if removed {
+        CreateEmbed::new()
+            .title("Watchlist Updated")
+            .description(format!(
+                "Removed **{}** from your watchlist",
+                symbol.to_uppercase()
+            ))
+            .color(0x00ff00)
+    } else {
+        CreateEmbed::new()
+            .title("Not Found")
+            .description(format!(
+                "**{}** is not on your watchlist",
+                symbol.to_uppercase()
+            ))
+            .color(0xff0000)
+    };
+
+    send_embed(ctx, embed).await?;
+
+    Ok(())
+}
+
+/// Show live prices for your watchlist
+#[poise::command(slash_command, prefix_command, rename = "show")]
+pub async fn watchlist_show(ctx: Context<'_>) -> Result<(), Error> {
+    show_watchlist(ctx).await
+}
+
+async fn show_watchlist(ctx: Context<'_>) -> Result<(), Error> {
+    let tiingo = match get_global_tiingo() {
+        Some(t) => t,
+        None => {
+            send_embed(
+                ctx,
+                CreateEmbed::new()
+                    .title("Error")
+                    .description("Price service not available")
+                    .color(0xff0000),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let pool = ctx.data().db.as_ref();
+    let symbols = WatchlistRepository::get_symbols(pool, ctx.author().id.get()).await?;
+
+    if symbols.is_empty() {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Your Watchlist")
+                .description(
+                    "Your watchlist is empty.\n\nUse `/watchlist add <symbol>` to add one.",
+                )
+                .color(0x808080),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let mut table = format!(
+        "{:<8} {:>11} {:>11} {:>11} {:>7}\n",
+        "Symbol", "Bid", "Ask", "Mid", "Spread"
+    );
+    for symbol in &symbols {
+        match tiingo.get_price(symbol) {
+            Some(price) => table.push_str(&format!(
+                "{:<8} {:>11.5} {:>11.5} {:>11.5} {:>7.1}\n",
+                symbol.to_uppercase(),
+                price.bid,
+                price.ask,
+                price.mid,
+                price.spread_pips()
+            )),
+            None => table.push_str(&format!(
+                "{:<8} {:>11} {:>11} {:>11} {:>7}\n",
+                symbol.to_uppercase(),
+                "N/A",
+                "N/A",
+                "N/A",
+                "-"
+            )),
+        }
+    }
+
+    let embed = CreateEmbed::new()
+        .title("Your Watchlist")
+        .description(format!("```\n{}```", table))
+        .footer(poise::serenity_prelude::CreateEmbedFooter::new(format!(
+            "{}/{} symbols • Spread in pips",
+            symbols.len(),
+            WATCHLIST_LIMIT
+        )))
+        .color(0x1DB954);
+
+    send_embed(ctx, embed).await?;
+
+    Ok(())
+}
+
+/// Parse `HH:MM` (24-hour) into a normalized string
+fn parse_post_time(input: &str) -> Option<String> {
+    chrono::NaiveTime::parse_from_str(input.trim(), "%H:%M")
+        .ok()
+        .map(|t| t.format("%H:%M").to_string())
+}
+
+/// Post a daily market summary to a channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn marketsummary_setup(
+    ctx: Context<'_>,
+    #[description = "Channel for the summary"] channel: serenity::GuildChannel,
+    #[description = "Time to post, HH:MM in UTC (e.g. 22:00)"] time: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let Some(post_time) = parse_post_time(&time) else {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Invalid Time")
+                .description("Use 24-hour `HH:MM` in UTC, e.g. `22:00`")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let pool = ctx.data().db.as_ref();
+    MarketSummaryRepository::set_config(pool, guild_id, channel.id.get(), &post_time).await?;
+
+    // Start tomorrow if today's time has already passed
+    let now = Utc::now();
+    if post_time <= now.format("%H:%M").to_string() {
+        MarketSummaryRepository::mark_sent(pool, guild_id, &now.format("%Y-%m-%d").to_string())
+            .await?;
+    }
+
+    let symbols = guild_symbols(pool, guild_id).await?;
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Market Summary Scheduled")
+            .description(format!(
+                "Daily summary will be posted in <#{}> at **{} UTC**.\n\nSymbols: {}",
+                channel.id,
+                post_time,
+                symbols
+                    .iter()
+                    .map(|s| s.to_uppercase())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .color(0x00ff00),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Daily market summary
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    subcommands("marketsummary_now", "marketsummary_symbols"),
+    subcommand_required
+)]
+pub async fn marketsummary(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Show the market summary now
+#[poise::command(slash_command, prefix_command, guild_only, rename = "now")]
+pub async fn marketsummary_now(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let symbols = guild_symbols(ctx.data().db.as_ref(), guild_id).await?;
+    send_embed(ctx, build_summary(&symbols)).await
+}
+
+/// Manage the symbols in the summary
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "symbols",
+    subcommands("marketsummary_symbols_add", "marketsummary_symbols_remove"),
+    subcommand_required
+)]
+pub async fn marketsummary_symbols(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Add a symbol to the summary
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "add"
+)]
+pub async fn marketsummary_symbols_add(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd)"] symbol: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let symbol = symbol.trim().to_lowercase();
+    let added =
+        MarketSummaryRepository::add_symbol(ctx.data().db.as_ref(), guild_id, &symbol).await?;
+
+    let embed = if added {
+        CreateEmbed::new()
+            .title("Symbol Added")
+            .description(format!(
+                "**{}** added to the market summary",
+                symbol.to_uppercase()
+            ))
+            .color(0x00ff00)
+    } else {
+        CreateEmbed::new()
+            .title("Already Added")
+            .description(format!(
+                "**{}** is already in the market summary",
+                symbol.to_uppercase()
+            ))
+            .color(0x808080)
+    };
+    send_embed(ctx, embed).await
+}
+
+/// Remove a symbol from the summary
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn marketsummary_symbols_remove(
+    ctx: Context<'_>,
+    #[description = "Symbol (e.g., xauusd)"] symbol: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let symbol = symbol.trim().to_lowercase();
+    let removed =
+        MarketSummaryRepository::remove_symbol(ctx.data().db.as_ref(), guild_id, &symbol).await?;
+
+    let embed = if removed {
+        CreateEmbed::new()
+            .title("Symbol Removed")
+            .description(format!(
+                "**{}** removed from the market summary",
+                symbol.to_uppercase()
+            ))
+            .color(0x00ff00)
+    } else {
+        CreateEmbed::new()
+            .title("Not Found")
+            .description(format!(
+                "**{}** is not in the market summary",
+                symbol.to_uppercase()
+            ))
+            .color(0xff0000)
+    };
+    send_embed(ctx, embed).await
+}
+
+/// Live price board that updates every minute
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("priceboard_setup", "priceboard_remove"),
+    subcommand_required
+)]
+pub async fn priceboard(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Post a live price board, replacing this server's current one
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "setup"
+)]
+pub async fn priceboard_setup(
+    ctx: Context<'_>,
+    #[description = "Channel for the board"] channel: serenity::GuildChannel,
+    #[rest]
+    #[description = "Symbols, separated by spaces or commas (e.g. xauusd eurusd)"]
+    symbols: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let mut symbol_list: Vec<String> = Vec::new();
+    for symbol in symbols.split([' ', ',']).map(|s| s.trim().to_lowercase()) {
+        if !symbol.is_empty() && !symbol_list.contains(&symbol) {
+            symbol_list.push(symbol);
+        }
+    }
+    if symbol_list.is_empty() || symbol_list.len() > MAX_BOARD_SYMBOLS {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Invalid Symbols")
+                .description(format!(
+                    "Give between 1 and {} symbols, e.g. `xauusd eurusd gbpusd`",
+                    MAX_BOARD_SYMBOLS
+                ))
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let (embed, _) = render_board(&symbol_list, None);
+    let message = channel
+        .id
+        .send_message(ctx.http(), serenity::CreateMessage::new().embed(embed))
+        .await?;
+
+    let pool = ctx.data().db.as_ref();
+    if let Some(old) = PriceBoardRepository::get_board(pool, guild_id).await? {
+        let _ = serenity::ChannelId::new(old.channel_id as u64)
+            .delete_message(ctx.http(), serenity::MessageId::new(old.message_id as u64))
+            .await;
+    }
+    PriceBoardRepository::set_board(
+        pool,
+        guild_id,
+        channel.id.get(),
+        message.id.get(),
+        &symbol_list,
+    )
+    .await?;
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Price Board Created")
+            .description(format!(
+                "Live prices for {} symbol(s) posted in <#{}>",
+                symbol_list.len(),
+                channel.id
+            ))
+            .color(0x00ff00),
+    )
+    .await
+}
+
+/// Stop and delete this server's price board
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "remove"
+)]
+pub async fn priceboard_remove(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let Some(board) = PriceBoardRepository::delete_board(ctx.data().db.as_ref(), guild_id).await?
+    else {
+        send_embed(
+            ctx,
+            CreateEmbed::new()
+                .title("Not Found")
+                .description("This server has no price board.")
+                .color(0xff0000),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let _ = serenity::ChannelId::new(board.channel_id as u64)
+        .delete_message(
+            ctx.http(),
+            serenity::MessageId::new(board.message_id as u64),
+        )
+        .await;
+
+    send_embed(
+        ctx,
+        CreateEmbed::new()
+            .title("Price Board Removed")
+            .description("The price board has been stopped and deleted.")
+            .color(0x00ff00),
+    )
+    .await
+}
@@ -1,9 +1,42 @@
-use crate::repository::RedeemRepository;
+use crate::repository::{RedeemCode, RedeemRepository};
+use crate::scraper::CodeData;
+use crate::services::genshin_redeem_checker::{game_info, notify_new_codes, parse_expiry};
 use poise::serenity_prelude as serenity;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
 type Context<'a> = poise::Context<'a, super::Data, Error>;
 
+const SUPPORTED_GAMES: [&str; 5] = ["wuwa", "genshin", "hsr", "zzz", "hi3"];
+const INVALID_GAME_MSG: &str =
+    "Invalid game! Available games: `wuwa`, `genshin`, `hsr`, `zzz`, `hi3`";
+
+/// Parse a comma-separated game list into a sorted, de-duplicated set.
+/// Returns None if any entry is not a supported game
+fn parse_games(input: &str) -> Option<Vec<String>> {
+    let mut games = Vec::new();
+    for game in input.split(',').map(|g| g.trim().to_lowercase()) {
+        if game.is_empty() {
+            continue;
+        }
+        if !SUPPORTED_GAMES.contains(&game.as_str()) {
+            return None;
+        }
+        if !games.contains(&game) {
+            games.push(game);
+        }
+    }
+    games.sort();
+    (!games.is_empty()).then_some(games)
+}
+
+fn format_games(games: &[String]) -> String {
+    games
+        .iter()
+        .map(|g| format!("**{}**", g.to_uppercase()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[poise::command(
     slash_command,
     prefix_command,
@@ -13,27 +46,25 @@ type Context<'a> = poise::Context<'a, super::Data, Error>;
 pub async fn redeem_setup(
     ctx: Context<'_>,
     #[description = "Channel for notifications"] channel: serenity::GuildChannel,
-    #[description = "Game (wuwa/genshin/hsr/zzz)"] game: String,
+    #[description = "Games, comma-separated (wuwa,genshin,hsr,zzz,hi3)"] games: String,
 ) -> Result<(), Error> {
     let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
     let channel_id = channel.id.get();
 
-    let game_lower = game.to_lowercase();
-    if !["wuwa", "genshin", "hsr", "zzz"].contains(&game_lower.as_str()) {
-        ctx.say("Invalid game! Available games: `wuwa`, `genshin`, `hsr`, `zzz`")
-            .await?;
+    let Some(games) = parse_games(&games) else {
+        ctx.say(INVALID_GAME_MSG).await?;
         return Ok(());
-    }
+    };
 
     let pool = ctx.data().db.as_ref();
-    RedeemRepository::insert_server(pool, guild_id, channel_id, &game_lower).await?;
+    RedeemRepository::insert_server(pool, guild_id, channel_id, &games.join(",")).await?;
 
     let embed = serenity::CreateEmbed::default()
         .title("✅ Redeem Setup Successful")
         .description(format!(
-            "Redeem code notifications for **{}** will be sent to <#{}>\n\n\
+            "Redeem code notifications for {} will be sent to <#{}>\n\n\
             The bot will automatically notify this channel when new codes are detected.",
-            game_lower.to_uppercase(),
+            format_games(&games),
             channel_id
         ))
         .color(serenity::Colour::DARK_GREEN)
@@ -98,16 +129,150 @@ pub async fn redeem_enable(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Add a game to this server's redeem notifications
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn redeem_addgame(
+    ctx: Context<'_>,
+    #[description = "Game (wuwa/genshin/hsr/zzz/hi3)"] game: String,
+) -> Result<(), Error> {
+    update_games(ctx, &game, true).await
+}
+
+/// Remove a game from this server's redeem notifications
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn redeem_removegame(
+    ctx: Context<'_>,
+    #[description = "Game (wuwa/genshin/hsr/zzz/hi3)"] game: String,
+) -> Result<(), Error> {
+    update_games(ctx, &game, false).await
+}
+
+async fn update_games(ctx: Context<'_>, game: &str, add: bool) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let game = game.trim().to_lowercase();
+
+    if !SUPPORTED_GAMES.contains(&game.as_str()) {
+        ctx.say(INVALID_GAME_MSG).await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let Some(server) = RedeemRepository::get_server(pool, guild_id).await? else {
+        ctx.say("Redeem notifications are not configured. Use `/redeem_setup` first.")
+            .await?;
+        return Ok(());
+    };
+
+    let mut games = parse_games(&server.games).unwrap_or_default();
+    if add {
+        if !games.contains(&game) {
+            games.push(game.clone());
+            games.sort();
+        }
+    } else {
+        games.retain(|g| g != &game);
+        if games.is_empty() {
+            ctx.say("Can't remove the last game. Use `/redeem_disable` instead.")
+                .await?;
+            return Ok(());
+        }
+    }
+
+    RedeemRepository::set_games(pool, guild_id, &games.join(",")).await?;
+
+    let embed = serenity::CreateEmbed::default()
+        .title(if add {
+            "✅ Game Added"
+        } else {
+            "🗑️ Game Removed"
+        })
+        .description(format!(
+            "Notifications in <#{}> now cover {}",
+            server.channel_id,
+            format_games(&games)
+        ))
+        .color(serenity::Colour::DARK_GREEN)
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Show redeem notification settings for this server
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR"
+)]
+pub async fn redeem_status(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let server = RedeemRepository::get_server(pool, guild_id).await?;
+
+    let embed = match server {
+        Some(server) => {
+            let (status, color) = if server.is_active {
+                ("🔔 Active", serenity::Colour::DARK_GREEN)
+            } else {
+                ("🔕 Disabled", serenity::Colour::RED)
+            };
+
+            serenity::CreateEmbed::default()
+                .title("🎮 Redeem Status")
+                .field("Status", status, true)
+                .field("Channel", format!("<#{}>", server.channel_id), true)
+                .field("Games", server.games.to_uppercase(), true)
+                .color(color)
+                .timestamp(serenity::Timestamp::now())
+        }
+        None => serenity::CreateEmbed::default()
+            .title("🎮 Redeem Status")
+            .description("Not configured. Use `/redeem_setup` to enable.")
+            .color(serenity::Colour::ORANGE)
+            .timestamp(serenity::Timestamp::now()),
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+/// Status line for /redeem_codes, based on explicit expiry or code age
+fn code_status(code: &RedeemCode) -> String {
+    const MAY_BE_EXPIRED_SECS: i64 = 7 * 24 * 60 * 60;
+
+    if code.is_expired {
+        return "\n└ ❌ Expired".to_string();
+    }
+    match code.expiry.as_deref().and_then(parse_expiry) {
+        Some(expiry) => format!("\n└ ⏳ Expires <t:{}:R>", expiry.timestamp()),
+        None if chrono::Utc::now().timestamp() - code.created_at > MAY_BE_EXPIRED_SECS => {
+            "\n└ ⚠️ May be expired".to_string()
+        }
+        None => String::new(),
+    }
+}
+
 #[poise::command(slash_command, prefix_command)]
 pub async fn redeem_codes(
     ctx: Context<'_>,
-    #[description = "Game (wuwa/genshin/hsr/zzz)"] game: String,
+    #[description = "Game (wuwa/genshin/hsr/zzz/hi3)"] game: String,
 ) -> Result<(), Error> {
     let game_lower = game.to_lowercase();
 
-    if !["wuwa", "genshin", "hsr", "zzz"].contains(&game_lower.as_str()) {
-        ctx.say("Invalid game! Available games: `wuwa`, `genshin`, `hsr`, `zzz`")
-            .await?;
+    if !SUPPORTED_GAMES.contains(&game_lower.as_str()) {
+        ctx.say(INVALID_GAME_MSG).await?;
         return Ok(());
     }
 
@@ -138,18 +303,18 @@ pub async fn redeem_codes(
                 .as_ref()
                 .map(|r| format!("\n└ 🎁 {}", r))
                 .unwrap_or_default();
-            format!("{}. `{}`{}", i + 1, code_data.code, rewards)
+            format!(
+                "{}. `{}`{}{}",
+                i + 1,
+                code_data.code,
+                rewards,
+                code_status(code_data)
+            )
         })
         .collect::<Vec<_>>()
         .join("\n\n");
 
-    let redeem_link = match game_lower.as_str() {
-        "genshin" => "https://genshin.hoyoverse.com/en/gift",
-        "hsr" => "https://hsr.hoyoverse.com/gift",
-        "zzz" => "https://zenless.hoyoverse.com/redemption",
-        "wuwa" => "https://wutheringwaves.kurogames.com/en/main/gift",
-        _ => "",
-    };
+    let (_, redeem_link, _) = game_info(&game_lower);
 
     let embed = serenity::CreateEmbed::default()
         .title(format!("🎮 {} Redeem Codes", game_lower.to_uppercase()))
@@ -170,3 +335,76 @@ pub async fn redeem_codes(
 
     Ok(())
 }
+
+/// Manually announce a redeem code to all subscribed servers
+///
+/// Owner only, since it posts in every guild and marks the code as already announced
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn redeem_announce(
+    ctx: Context<'_>,
+    #[description = "Game (wuwa/genshin/hsr/zzz/hi3)"] game: String,
+    #[description = "Redeem code"] code: String,
+    #[description = "Rewards description"]
+    #[rest]
+    rewards: String,
+) -> Result<(), Error> {
+    let game_lower = game.to_lowercase();
+
+    if !SUPPORTED_GAMES.contains(&game_lower.as_str()) {
+        ctx.say(INVALID_GAME_MSG).await?;
+        return Ok(());
+    }
+
+    let code = code.trim().to_uppercase();
+    let rewards = rewards.trim().to_string();
+
+    let pool = ctx.data().db.as_ref();
+    let inserted =
+        RedeemRepository::insert_code(pool, &game_lower, &code, Some(&rewards), None).await?;
+
+    if !inserted {
+        let embed = serenity::CreateEmbed::default()
+            .title("Code already announced")
+            .description(format!(
+                "`{}` has already been sent to subscribed servers.",
+                code
+            ))
+            .color(serenity::Colour::ORANGE);
+
+        ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+            .await?;
+        return Ok(());
+    }
+
+    ctx.defer().await?;
+
+    let code_data = CodeData {
+        code: code.clone(),
+        rewards,
+        status: "Active".to_string(),
+        expiry: None,
+        region: None,
+    };
+    let notified = notify_new_codes(
+        &ctx.serenity_context().http,
+        pool,
+        &game_lower,
+        &[&code_data],
+    )
+    .await?;
+
+    let embed = serenity::CreateEmbed::default()
+        .title("📢 Code Announced")
+        .description(format!(
+            "`{}` for **{}** was sent to **{}** server(s).",
+            code,
+            game_lower.to_uppercase(),
+            notified
+        ))
+        .color(serenity::Colour::DARK_GREEN)
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::repository::StarboardRepository;
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const DEFAULT_THRESHOLD: i32 = 3;
+const DEFAULT_EMOJI: &str = "⭐";
+
+/// Highlight popular messages in a starboard channel
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("starboard_set", "starboard_disable"),
+    subcommand_required
+)]
+pub async fn starboard(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Set the starboard channel, reaction count and emoji
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "set"
+)]
+pub async fn starboard_set(
+    ctx: Context<'_>,
+    #[description = "Channel for starred messages"] channel: serenity::GuildChannel,
+    #[description = "Reactions needed (default 3)"]
+    #[min = 1]
+    #[max = 100]
+    threshold: Option<i32>,
+    #[description = "Emoji to count (default ⭐)"] emoji: Option<String>,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD);
+    let emoji = emoji
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .unwrap_or_else(|| DEFAULT_EMOJI.to_string());
+
+    StarboardRepository::set_config(
+        ctx.data().db.as_ref(),
+        guild_id,
+        channel.id.get(),
+        threshold,
+        &emoji,
+    )
+    .await?;
+
+    let embed = embed::success(
+        "Starboard Set",
+        &format!(
+            "Messages with **{}** {} reactions will be posted in <#{}>",
+            threshold, emoji, channel.id
+        ),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Turn the starboard off
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    rename = "disable"
+)]
+pub async fn starboard_disable(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let embed = if StarboardRepository::delete_config(ctx.data().db.as_ref(), guild_id).await? {
+        embed::success("Starboard Disabled", "Messages will no longer be starred")
+    } else {
+        embed::error("Starboard", "The starboard is not set up")
+    };
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -1,3 +1,9 @@
+use crate::config::activity_from_type;
+use crate::services::genshin_redeem_checker::scraper_health;
+use crate::services::maintenance;
+use crate::services::music::node::{NodeStatus, fetch_node_status};
+use crate::utils::embed;
+use crate::utils::health::{ServiceHealth, format_ago};
 use crate::utils::sys::SysInfo;
 use poise::serenity_prelude as serenity;
 
@@ -27,4 +33,454 @@ pub async fn sys(ctx: Context<'_>) -> Result<(), Error> {
     ).await?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Show the status of the redeem code scrapers
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn scraper_status(ctx: Context<'_>) -> Result<(), Error> {
+    let scrapers = scraper_health();
+
+    let mut embed = serenity::CreateEmbed::default()
+        .title("Scraper Status")
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    if scrapers.is_empty() {
+        embed = embed.description("No scraper has run yet");
+    }
+
+    for scraper in &scrapers {
+        let indicator = match scraper.consecutive_failures {
+            0 => "🟢",
+            1..=2 => "🟡",
+            _ => "🔴",
+        };
+        let last_success = scraper
+            .last_success
+            .map(|t| format_ago(t.elapsed()))
+            .unwrap_or_else(|| "never".to_string());
+
+        embed = embed.field(
+            format!("{} {}", indicator, scraper.name),
+            format!(
+                "Last success: {}\nFailures in a row: {}\nAPI: {}",
+                last_success, scraper.consecutive_failures, scraper.api_url
+            ),
+            false,
+        );
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Show Lavalink node version, load and player counts
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn lavalink(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let (status, stale) = match fetch_node_status().await {
+        Ok(status) => {
+            *ctx.data().lavalink_status.write() = Some(status.clone());
+            (Some(status), None)
+        }
+        Err(e) => {
+            eprintln!("[LAVALINK] Failed to fetch node status: {}", e);
+            (ctx.data().lavalink_status.read().clone(), Some(e.to_string()))
+        }
+    };
+
+    let guild_queues = ctx
+        .data()
+        .music_player
+        .as_ref()
+        .map(|p| p.queues.read().len())
+        .unwrap_or(0);
+
+    let embed = match status {
+        Some(status) => {
+            let mut embed = lavalink_embed(&status, guild_queues);
+            if let Some(error) = stale {
+                embed = embed.description(format!(
+                    "⚠️ Lavalink is unreachable ({}). Showing stats from {}.",
+                    error,
+                    format_ago(status.fetched_at.elapsed())
+                ));
+            }
+            embed
+        }
+        None => embed::error(
+            "Lavalink Unreachable",
+            &format!(
+                "{}\nNo cached stats yet. Active guild queues: {}",
+                stale.unwrap_or_default(),
+                guild_queues
+            ),
+        ),
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+fn lavalink_embed(status: &NodeStatus, guild_queues: usize) -> serenity::CreateEmbed {
+    const MB: u64 = 1024 * 1024;
+    let stats = &status.stats;
+    let uptime = std::time::Duration::from_millis(stats.uptime).as_secs();
+
+    embed::info("Lavalink Node", "🟢 Connected")
+        .field(
+            "Version",
+            format!(
+                "Lavalink {}\nLavaplayer {}",
+                status.info.version.semver, status.info.lavaplayer
+            ),
+            true,
+        )
+        .field("Java", &status.info.jvm, true)
+        .field(
+            "Uptime",
+            format!(
+                "{}d {}h {}m",
+                uptime / 86400,
+                (uptime % 86400) / 3600,
+                (uptime % 3600) / 60
+            ),
+            true,
+        )
+        .field(
+            "Memory",
+            format!(
+                "Used: {} MB\nAllocated: {} MB\nFree: {} MB",
+                stats.memory.used / MB,
+                stats.memory.allocated / MB,
+                stats.memory.free / MB
+            ),
+            true,
+        )
+        .field(
+            "CPU",
+            format!(
+                "Cores: {}\nSystem: {:.1}%\nLavalink: {:.1}%",
+                stats.cpu.cores,
+                stats.cpu.system_load * 100.0,
+                stats.cpu.lavalink_load * 100.0
+            ),
+            true,
+        )
+        .field(
+            "Players",
+            format!(
+                "Playing: {}\nTotal: {}\nGuild queues: {}",
+                stats.playing_players, stats.players, guild_queues
+            ),
+            true,
+        )
+        .timestamp(serenity::Timestamp::now())
+}
+
+/// Show the status of background services
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn health(ctx: Context<'_>) -> Result<(), Error> {
+    let mut services: Vec<ServiceHealth> = ctx.data().health.read().values().cloned().collect();
+    services.sort_by(|a, b| a.service_name.cmp(&b.service_name));
+
+    let mut embed = serenity::CreateEmbed::default()
+        .title("Service Health")
+        .color(serenity::Colour::BLUE)
+        .timestamp(serenity::Timestamp::now());
+
+    if services.is_empty() {
+        embed = embed.description("No background services are running");
+    }
+
+    for service in &services {
+        embed = embed.field(
+            format!("{} {}", service.status().indicator(), service.service_name),
+            format!(
+                "Last success: {}\nFailures: {}",
+                format_ago(service.last_success.elapsed()),
+                service.failure_count
+            ),
+            true,
+        );
+    }
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Show gateway reconnects and resumes since startup
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn gateway(ctx: Context<'_>) -> Result<(), Error> {
+    let stats = ctx.data().gateway.snapshot();
+    let latency = ctx.ping().await;
+    let when = |time: Option<chrono::DateTime<chrono::Utc>>| {
+        time.map(|t| format!("<t:{}:R>", t.timestamp()))
+            .unwrap_or_else(|| "Never".to_string())
+    };
+
+    let embed = serenity::CreateEmbed::default()
+        .title("Gateway")
+        .color(if stats.disconnects > 0 {
+            serenity::Colour::ORANGE
+        } else {
+            serenity::Colour::BLUE
+        })
+        .field(
+            "Shard latency",
+            if latency.is_zero() {
+                "Not measured yet".to_string()
+            } else {
+                format!("{}ms", latency.as_millis())
+            },
+            true,
+        )
+        .field(
+            "Ready events",
+            format!("{}\nLast: {}", stats.readies, when(stats.last_ready)),
+            true,
+        )
+        .field(
+            "Resumes",
+            format!("{}\nLast: {}", stats.resumes, when(stats.last_resume)),
+            true,
+        )
+        .field(
+            "Disconnects",
+            format!(
+                "{}\nLast: {}",
+                stats.disconnects,
+                when(stats.last_disconnect)
+            ),
+            true,
+        )
+        .footer(serenity::CreateEmbedFooter::new(
+            "Counts reset on restart. More than one ready means a full reconnect",
+        ))
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum ActivityKind {
+    #[name = "playing"]
+    Playing,
+    #[name = "watching"]
+    Watching,
+    #[name = "listening"]
+    Listening,
+    #[name = "streaming"]
+    Streaming,
+    #[name = "competing"]
+    Competing,
+    #[name = "custom"]
+    Custom,
+}
+
+impl ActivityKind {
+    /// Type name accepted by `activity_from_type`
+    fn key(&self) -> &'static str {
+        match self {
+            ActivityKind::Playing => "playing",
+            ActivityKind::Watching => "watching",
+            ActivityKind::Listening => "listening",
+            ActivityKind::Streaming => "streaming",
+            ActivityKind::Competing => "competing",
+            ActivityKind::Custom => "custom",
+        }
+    }
+}
+
+/// "Playing Genshin Impact", or the status text for custom activities
+fn activity_label(activity: &serenity::ActivityData) -> String {
+    let text = activity.state.as_deref().unwrap_or(&activity.name);
+    match activity.kind {
+        serenity::ActivityType::Playing => format!("Playing {}", activity.name),
+        serenity::ActivityType::Streaming => format!("Streaming {}", activity.name),
+        serenity::ActivityType::Listening => format!("Listening to {}", activity.name),
+        serenity::ActivityType::Watching => format!("Watching {}", activity.name),
+        serenity::ActivityType::Competing => format!("Competing in {}", activity.name),
+        _ => text.to_string(),
+    }
+}
+
+/// Manage the bot's rotating presence
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    subcommands("activity_add", "activity_remove", "activity_list"),
+    subcommand_required
+)]
+pub async fn activity(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Add an activity to the rotation. `{users}`, `{servers}` and `{guilds}` are filled in live
+#[poise::command(slash_command, prefix_command, owners_only, rename = "add")]
+pub async fn activity_add(
+    ctx: Context<'_>,
+    #[description = "Activity type"] kind: ActivityKind,
+    #[description = "Activity text"] name: String,
+    #[description = "Twitch or YouTube URL (streaming only)"] url: Option<String>,
+) -> Result<(), Error> {
+    let Some(activity) = activity_from_type(kind.key(), &name, url.as_deref()) else {
+        ctx.send(
+            poise::CreateReply::default()
+                .embed(embed::error(
+                    "Invalid Activity",
+                    "Streaming activities need a valid Twitch or YouTube `url`",
+                ))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let label = activity_label(&activity);
+    let count = {
+        let mut activities = ctx.data().activities.write();
+        activities.push(activity);
+        activities.len()
+    };
+
+    ctx.send(
+        poise::CreateReply::default()
+            .embed(embed::success(
+                "Activity Added",
+                &format!("Added **{}** ({} in rotation)", label, count),
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Remove an activity from the rotation by its number in `/activity list`
+#[poise::command(slash_command, prefix_command, owners_only, rename = "remove")]
+pub async fn activity_remove(
+    ctx: Context<'_>,
+    #[description = "Activity number"]
+    #[min = 1]
+    index: usize,
+) -> Result<(), Error> {
+    let removed = {
+        let mut activities = ctx.data().activities.write();
+        if activities.len() <= 1 {
+            Err("The rotation needs at least one activity")
+        } else if index == 0 || index > activities.len() {
+            Err("No activity with that number")
+        } else {
+            Ok(activities.remove(index - 1))
+        }
+    };
+
+    let embed = match removed {
+        Ok(activity) => embed::success(
+            "Activity Removed",
+            &format!("Removed **{}**", activity_label(&activity)),
+        ),
+        Err(reason) => embed::error("Cannot Remove", reason),
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Show the activities in rotation
+#[poise::command(slash_command, prefix_command, owners_only, rename = "list")]
+pub async fn activity_list(ctx: Context<'_>) -> Result<(), Error> {
+    let lines: Vec<String> = ctx
+        .data()
+        .activities
+        .read()
+        .iter()
+        .enumerate()
+        .map(|(i, a)| format!("`{}.` {}", i + 1, activity_label(a)))
+        .collect();
+
+    let embed = embed::info("Activity Rotation", &lines.join("\n"));
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Database maintenance
+#[poise::command(
+    slash_command,
+    prefix_command,
+    owners_only,
+    subcommands("maintenance_run"),
+    subcommand_required
+)]
+pub async fn maintenance(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Run the daily database cleanup now
+#[poise::command(slash_command, prefix_command, owners_only, rename = "run")]
+pub async fn maintenance_run(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let report = maintenance::run(ctx.data().db.as_ref()).await?;
+
+    let embed = embed::success(
+        "Maintenance Complete",
+        &format!(
+            "Forex news removed: **{}**\nRedeem codes removed: **{}**\nSent reminders removed: **{}**",
+            report.news, report.codes, report.reminders
+        ),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Re-register slash commands without restarting the bot
+#[poise::command(slash_command, prefix_command, owners_only)]
+pub async fn reload(
+    ctx: Context<'_>,
+    #[description = "Only register in this server"] here: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let commands = &ctx.framework().options().commands;
+    let count = poise::builtins::create_application_commands(commands).len();
+
+    let scope = if here.unwrap_or(false) {
+        let guild_id = ctx
+            .guild_id()
+            .ok_or("Use this in a server to register commands there")?;
+        poise::builtins::register_in_guild(ctx, commands, guild_id).await?;
+        "in this server"
+    } else {
+        poise::builtins::register_globally(ctx, commands).await?;
+        "globally"
+    };
+    println!("[OK] Reloaded {} slash command(s) {}", count, scope);
+
+    let embed = embed::success(
+        "Commands Reloaded",
+        &format!("Registered **{}** slash command(s) {}", count, scope),
+    );
+    ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,260 @@
+use crate::repository::TagRepository;
+use crate::utils::embed;
+use poise::serenity_prelude as serenity;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const MAX_NAME_LEN: usize = 32;
+const MAX_CONTENT_LEN: usize = 2000;
+const MAX_TAGS_PER_GUILD: i64 = 100;
+
+/// Fill in the `{author}` and `{server}` placeholders in a tag's content,
+/// cut to the message length limit since names can push it over
+pub fn render_tag(content: &str, author: &str, server: &str) -> String {
+    content
+        .replace("{author}", author)
+        .replace("{server}", server)
+        .chars()
+        .take(MAX_CONTENT_LEN)
+        .collect()
+}
+
+fn guild_name(ctx: Context<'_>) -> String {
+    ctx.guild()
+        .map(|g| g.name.clone())
+        .unwrap_or_else(|| "this server".to_string())
+}
+
+fn content_too_long(content: &str) -> bool {
+    content.chars().count() > MAX_CONTENT_LEN
+}
+
+fn is_command_name(ctx: Context<'_>, name: &str) -> bool {
+    ctx.framework()
+        .options()
+        .commands
+        .iter()
+        .any(|cmd| cmd.name == name || cmd.aliases.iter().any(|alias| alias == name))
+}
+
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn tag(ctx: Context<'_>, #[description = "Tag name"] name: String) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    match TagRepository::get_tag(pool, guild_id, &name.to_lowercase()).await? {
+        Some(tag) => {
+            let content = render_tag(&tag.content, ctx.author().display_name(), &guild_name(ctx));
+            // Tag content is user-written, so it must not ping anyone
+            ctx.send(
+                poise::CreateReply::default()
+                    .content(content)
+                    .allowed_mentions(serenity::CreateAllowedMentions::new()),
+            )
+            .await?;
+        }
+        None => {
+            let embed = embed::error("Tag Not Found", &format!("No tag named `{}`", name));
+            ctx.send(poise::CreateReply::default().embed(embed).ephemeral(true))
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn tag_add(
+    ctx: Context<'_>,
+    #[description = "Tag name (single word, up to 32 characters)"] name: String,
+    #[description = "Also respond to !<name>"] auto_trigger: Option<bool>,
+    #[description = "Content to post; {author} and {server} are filled in"]
+    #[rest]
+    content: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let name = name.to_lowercase();
+
+    if name.chars().any(char::is_whitespace) || name.starts_with('!') {
+        let embed = embed::error("Invalid Name", "Tag names must be a single word");
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        let embed = embed::error(
+            "Invalid Name",
+            &format!("Tag names can be at most {} characters", MAX_NAME_LEN),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    if content_too_long(&content) {
+        let embed = embed::error(
+            "Too Long",
+            &format!("Tag content can be at most {} characters", MAX_CONTENT_LEN),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    if is_command_name(ctx, &name) {
+        let embed = embed::error(
+            "Invalid Name",
+            &format!("`{}` is already a bot command", name),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let auto_trigger = auto_trigger.unwrap_or(false);
+    let pool = ctx.data().db.as_ref();
+
+    if TagRepository::get_tag(pool, guild_id, &name)
+        .await?
+        .is_none()
+        && TagRepository::count_tags(pool, guild_id).await? >= MAX_TAGS_PER_GUILD
+    {
+        let embed = embed::error(
+            "Tag Limit Reached",
+            &format!(
+                "This server already has {} tags. Remove one with `/tag_remove` first",
+                MAX_TAGS_PER_GUILD
+            ),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+    let created = TagRepository::upsert_tag(
+        pool,
+        guild_id,
+        &name,
+        &content,
+        auto_trigger,
+        ctx.author().id.get(),
+    )
+    .await?;
+
+    let mut description = format!(
+        "Tag `{}` {}",
+        name,
+        if created { "created" } else { "updated" }
+    );
+    if auto_trigger {
+        description.push_str(&format!("\nIt will also respond to `!{}`", name));
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Tag Saved")
+        .description(description)
+        .color(serenity::Colour::DARK_GREEN)
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn tag_edit(
+    ctx: Context<'_>,
+    #[description = "Tag name"] name: String,
+    #[description = "Change whether it responds to !<name>"] auto_trigger: Option<bool>,
+    #[description = "New content"]
+    #[rest]
+    content: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let name = name.to_lowercase();
+
+    if content_too_long(&content) {
+        let embed = embed::error(
+            "Too Long",
+            &format!("Tag content can be at most {} characters", MAX_CONTENT_LEN),
+        );
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let pool = ctx.data().db.as_ref();
+    let embed = if TagRepository::update_tag(pool, guild_id, &name, &content, auto_trigger).await? {
+        embed::success("Tag Updated", &format!("Tag `{}` has been updated", name))
+    } else {
+        embed::error("Tag Not Found", &format!("No tag named `{}`", name))
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_MESSAGES"
+)]
+pub async fn tag_remove(
+    ctx: Context<'_>,
+    #[description = "Tag name"] name: String,
+) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+    let name = name.to_lowercase();
+
+    let pool = ctx.data().db.as_ref();
+    let embed = if TagRepository::delete_tag(pool, guild_id, &name).await? {
+        embed::success("Tag Removed", &format!("Tag `{}` has been removed", name))
+    } else {
+        embed::error("Tag Not Found", &format!("No tag named `{}`", name))
+    };
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[poise::command(slash_command, prefix_command, guild_only)]
+pub async fn tag_list(ctx: Context<'_>) -> Result<(), Error> {
+    let guild_id = ctx.guild_id().ok_or("Must be used in a guild")?.get();
+
+    let pool = ctx.data().db.as_ref();
+    let tags = TagRepository::get_tags(pool, guild_id).await?;
+
+    if tags.is_empty() {
+        let embed = embed::info("No Tags", "This server has no tags yet. Use `/tag_add`");
+        ctx.send(poise::CreateReply::default().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let list = tags
+        .iter()
+        .map(|t| {
+            if t.auto_trigger {
+                format!("`{}` (!{})", t.name, t.name)
+            } else {
+                format!("`{}`", t.name)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let embed = serenity::CreateEmbed::new()
+        .title("🏷️ Tags")
+        .description(list)
+        .color(serenity::Colour::BLUE)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Total: {}/{} tags",
+            tags.len(),
+            MAX_TAGS_PER_GUILD
+        )))
+        .timestamp(serenity::Timestamp::now());
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::config::Config;
+use crate::utils::embed;
+use chrono::{FixedOffset, Utc};
+use parking_lot::RwLock;
+use poise::serenity_prelude::{CreateEmbed, CreateEmbedFooter};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, super::Data, Error>;
+
+const WEATHER_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Recent weather lookups keyed by lowercase city name
+pub type WeatherCache = Arc<RwLock<HashMap<String, (WeatherData, Instant)>>>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherCondition {
+    pub id: u32,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherMain {
+    pub temp: f64,
+    pub feels_like: f64,
+    pub humidity: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherWind {
+    pub speed: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherSys {
+    #[serde(default)]
+    pub country: String,
+}
+
+/// Current weather response from OpenWeatherMap
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeatherData {
+    pub name: String,
+    pub weather: Vec<WeatherCondition>,
+    pub main: WeatherMain,
+    pub wind: WeatherWind,
+    pub sys: WeatherSys,
+    /// Offset from UTC in seconds
+    pub timezone: i32,
+}
+
+/// Emoji for an OpenWeatherMap condition code
+fn condition_emoji(id: u32) -> &'static str {
+    match id {
+        200..=299 => "⛈️",
+        300..=399 => "🌦️",
+        500..=599 => "🌧️",
+        600..=699 => "❄️",
+        700..=799 => "🌫️",
+        800 => "☀️",
+        801 | 802 => "⛅",
+        _ => "☁️",
+    }
+}
+
+async fn fetch_weather(api_key: &str, city: &str) -> Result<WeatherData, Error> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let response = client
+        .get(WEATHER_URL)
+        .query(&[("q", city), ("appid", api_key), ("units", "metric")])
+        .send()
+        .await?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("City `{}` not found", city).into());
+    }
+
+    Ok(response.error_for_status()?.json().await?)
+}
+
+/// Current weather for a city
+#[poise::command(slash_command, prefix_command)]
+pub async fn weather(
+    ctx: Context<'_>,
+    #[rest]
+    #[description = "City name (e.g. Jakarta, New York)"]
+    city: String,
+) -> Result<(), Error> {
+    let config = Config::from_env()?;
+
+    let Some(api_key) = config.openweather_api_key else {
+        ctx.send(poise::CreateReply::default().embed(embed::error(
+            "Weather Unavailable",
+            "Weather is not configured. Set `OPENWEATHER_API_KEY` in the environment.",
+        )))
+        .await?;
+        return Ok(());
+    };
+
+    let key = city.trim().to_lowercase();
+    let cached = ctx
+        .data()
+        .weather_cache
+        .read()
+        .get(&key)
+        .filter(|(_, fetched)| fetched.elapsed() < CACHE_TTL)
+        .map(|(data, _)| data.clone());
+
+    let data = match cached {
+        Some(data) => data,
+        None => {
+            ctx.defer().await?;
+            let data = fetch_weather(&api_key, city.trim()).await?;
+            let mut cache = ctx.data().weather_cache.write();
+            cache.retain(|_, (_, fetched)| fetched.elapsed() < CACHE_TTL);
+            cache.insert(key, (data.clone(), Instant::now()));
+            data
+        }
+    };
+
+    let (emoji, description) = data
+        .weather
+        .first()
+        .map(|c| (condition_emoji(c.id), c.description.clone()))
+        .unwrap_or(("🌡️", "Unknown".to_string()));
+
+    let local_time = FixedOffset::east_opt(data.timezone)
+        .map(|offset| {
+            Utc::now()
+                .with_timezone(&offset)
+                .format("%a %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let location = if data.sys.country.is_empty() {
+        data.name.clone()
+    } else {
+        format!("{}, {}", data.name, data.sys.country)
+    };
+
+    let embed = CreateEmbed::new()
+        .title(format!("{} {}", emoji, location))
+        .description(description)
+        .field("Temperature", format!("{:.1}°C", data.main.temp), true)
+        .field("Feels Like", format!("{:.1}°C", data.main.feels_like), true)
+        .field("Humidity", format!("{}%", data.main.humidity), true)
+        .field("Wind", format!("{:.1} m/s", data.wind.speed), true)
+        .field("Local Time", local_time, true)
+        .footer(CreateEmbedFooter::new("OpenWeatherMap"))
+        .color(0x3498DB);
+
+    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
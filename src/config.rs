@@ -1,5 +1,9 @@
+use crate::error::{BotError, Result};
+use serde::Deserialize;
+use serenity::all::ActivityData;
 use std::env;
 use std::fs;
+use std::sync::Once;
 
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -12,31 +16,108 @@ pub struct Config {
     pub scraper_url: String,
     pub gemini_api_key: String,
     pub gemini_prompt: String,
+    /// Presence rotation; `{users}`, `{servers}` and `{guilds}` are replaced with live counts
+    pub activities: Vec<ActivityData>,
+    /// Seconds between presence changes (`ACTIVITY_INTERVAL_SECS`)
+    pub activity_interval_secs: u64,
+    /// Channel that receives detailed command error reports
+    pub error_log_channel_id: Option<u64>,
+    pub openweather_api_key: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct ActivityEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    name: String,
+    /// Twitch or YouTube URL, required for "streaming"
+    url: Option<String>,
+}
+
+/// Used when `system-prompt.txt` is missing
+pub const DEFAULT_PROMPT: &str = "You are a helpful assistant in a Discord server. \
+Answer clearly and concisely, and keep replies short enough to read in chat.";
+
+const DEFAULT_ACTIVITY_INTERVAL_SECS: u64 = 60;
+// Discord drops presence updates sent much faster than this
+const MIN_ACTIVITY_INTERVAL_SECS: u64 = 15;
+
+/// Config is rebuilt per command, so the missing prompt is only reported once
+static PROMPT_WARNING: Once = Once::new();
+
+/// Variables the bot cannot start without
+const REQUIRED_VARS: [&str; 2] = ["TOKEN", "CLIENT_ID"];
+
 impl Config {
-    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
-        let prompt_file = "system-prompt.txt";
-        let prompt = fs::read_to_string(&prompt_file)
-            .map_err(|e| format!("Failed to read prompt file '{}': {}", prompt_file, e))?;
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Build the config from any variable source. Every missing required
+    /// variable is reported in one error
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
 
-        let token = env::var("TOKEN").map_err(|_| "TOKEN not configured in .env")?;
-        let client_id = env::var("CLIENT_ID").map_err(|_| "CLIENT_ID not configured in .env")?;
+        let missing: Vec<&str> = REQUIRED_VARS
+            .into_iter()
+            .filter(|name| var(name).is_none())
+            .collect();
+        if !missing.is_empty() {
+            return Err(BotError::Config(format!(
+                "missing required environment variable(s): {} (set them in .env)",
+                missing.join(", ")
+            )));
+        }
+        let token = var("TOKEN").unwrap_or_default();
+        let client_id = var("CLIENT_ID").unwrap_or_default();
 
-        let api_key = env::var("API_KEY").ok();
-        let model_ai = env::var("MODEL_AI")
-            .unwrap_or_else(|_| "tngtech/deepseek-r1t2-chimera:free".to_string());
+        let prompt_file = "system-prompt.txt";
+        let prompt = fs::read_to_string(prompt_file).unwrap_or_else(|e| {
+            PROMPT_WARNING.call_once(|| {
+                eprintln!(
+                    "[WARN] Could not read '{}' ({}), using the built-in prompt",
+                    prompt_file, e
+                );
+            });
+            DEFAULT_PROMPT.to_string()
+        });
+
+        let api_key = var("API_KEY");
+        let model_ai = var("MODEL_AI")
+            .unwrap_or_else(|| "tngtech/deepseek-r1t2-chimera:free".to_string());
         let base_url =
-            env::var("BASE_URL").unwrap_or_else(|_| "https://openrouter.ai/api/v1".to_string());
+            var("BASE_URL").unwrap_or_else(|| "https://openrouter.ai/api/v1".to_string());
         let scraper_url =
-            env::var("SCRAPER_URL").unwrap_or_else(|_| "https://api.ennead.cc/mihoyo".to_string());
+            var("SCRAPER_URL").unwrap_or_else(|| "https://api.ennead.cc/mihoyo".to_string());
 
-        let gemini_api_key = env::var("GEMINI_API_KEY").unwrap_or_else(|_| "api_key".to_string());
+        let gemini_api_key = var("GEMINI_API_KEY").unwrap_or_else(|| "api_key".to_string());
 
         let gemini_prompt_file = "gemini_prompt.txt";
         let gemini_prompt = fs::read_to_string(gemini_prompt_file)
             .unwrap_or_else(|_| String::new());
 
+        let openweather_api_key = var("OPENWEATHER_API_KEY");
+
+        let error_log_channel_id =
+            var("ERROR_LOG_CHANNEL").and_then(|id| id.trim().parse::<u64>().ok());
+
+        // ACTIVITY_LIST takes precedence over activities.json
+        let activities_file = "activities.json";
+        let activities_json = var("ACTIVITY_LIST")
+            .or_else(|| fs::read_to_string(activities_file).ok());
+        let activities = match activities_json {
+            Some(json) => parse_activities(&json).unwrap_or_else(|| {
+                eprintln!("[WARN] Invalid activity list, using the default activities");
+                default_activities()
+            }),
+            None => default_activities(),
+        };
+
+        let activity_interval_secs = var("ACTIVITY_INTERVAL_SECS")
+            .and_then(|secs| secs.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_ACTIVITY_INTERVAL_SECS)
+            .max(MIN_ACTIVITY_INTERVAL_SECS);
+
         Ok(Self {
             token,
             client_id,
@@ -47,6 +128,10 @@ impl Config {
             scraper_url,
             gemini_api_key,
             gemini_prompt,
+            activities,
+            activity_interval_secs,
+            error_log_channel_id,
+            openweather_api_key,
         })
     }
 
@@ -54,3 +139,82 @@ impl Config {
         self.api_key.is_some()
     }
 }
+
+/// Build an activity from its type name: playing, watching, listening,
+/// streaming, competing or custom. Streaming needs a Twitch or YouTube URL
+pub fn activity_from_type(kind: &str, name: &str, url: Option<&str>) -> Option<ActivityData> {
+    match kind.to_lowercase().as_str() {
+        "playing" => Some(ActivityData::playing(name)),
+        "watching" => Some(ActivityData::watching(name)),
+        "listening" => Some(ActivityData::listening(name)),
+        "competing" => Some(ActivityData::competing(name)),
+        "custom" => Some(ActivityData::custom(name)),
+        "streaming" => ActivityData::streaming(name, url?).ok(),
+        _ => None,
+    }
+}
+
+pub fn default_activities() -> Vec<ActivityData> {
+    vec![
+        ActivityData::custom("With {users} users!"),
+        ActivityData::custom("In {servers} server!"),
+    ]
+}
+
+/// Parse a JSON array like `[{"type":"playing","name":"Genshin Impact"}]`.
+/// Returns None if it is malformed, empty or has an unknown type
+fn parse_activities(json: &str) -> Option<Vec<ActivityData>> {
+    let entries: Vec<ActivityEntry> = serde_json::from_str(json).ok()?;
+    let activities = entries
+        .iter()
+        .map(|e| activity_from_type(&e.kind, &e.name, e.url.as_deref()))
+        .collect::<Option<Vec<_>>>()?;
+
+    if activities.is_empty() {
+        None
+    } else {
+        Some(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_missing_vars_are_listed_together() {
+        let err = Config::from_lookup(vars(&[])).unwrap_err();
+        assert!(matches!(err, BotError::Config(_)));
+
+        let message = err.to_string();
+        assert!(message.contains("TOKEN"), "{}", message);
+        assert!(message.contains("CLIENT_ID"), "{}", message);
+    }
+
+    #[test]
+    fn test_blank_var_counts_as_missing() {
+        let err = Config::from_lookup(vars(&[("TOKEN", "  "), ("CLIENT_ID", "123")])).unwrap_err();
+
+        let message = err.to_string();
+        assert!(message.contains("TOKEN"), "{}", message);
+        assert!(!message.contains("CLIENT_ID"), "{}", message);
+    }
+
+    #[test]
+    fn test_loads_with_required_vars_only() {
+        let config = Config::from_lookup(vars(&[("TOKEN", "abc"), ("CLIENT_ID", "123")])).unwrap();
+        assert_eq!(config.token, "abc");
+        assert_eq!(config.client_id, "123");
+        assert!(config.api_key.is_none());
+        assert!(!config.prompt.is_empty());
+    }
+}
@@ -1,13 +1,29 @@
 use crate::commands::Data;
-use poise::serenity_prelude::CreateEmbed;
+use parking_lot::Mutex;
+use poise::serenity_prelude::{CreateEmbed, CreateMessage, Timestamp};
+use std::backtrace::Backtrace;
 
 type Error = Box<dyn std::error::Error + Send + Sync>;
+type Context<'a> = poise::Context<'a, Data, Error>;
+
+// Backtrace of the most recent panic, picked up by the CommandPanic report
+static LAST_PANIC_BACKTRACE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Keep the default panic output and also record the backtrace for error reports
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        *LAST_PANIC_BACKTRACE.lock() = Some(Backtrace::force_capture().to_string());
+        default_hook(info);
+    }));
+}
 
 /// Handle framework errors
 pub async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
     match error {
         poise::FrameworkError::Command { error, ctx, .. } => {
             eprintln!("Error in command '{}': {:?}", ctx.command().name, error);
+            report_error(ctx, "Command Error", &format!("{:?}", error), None).await;
             let embed = CreateEmbed::new()
                 .title("[ERROR] Command Failed")
                 .description(format!("{}", error))
@@ -16,14 +32,137 @@ pub async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
         }
         poise::FrameworkError::CommandPanic { payload, ctx, .. } => {
             eprintln!("Command '{}' panicked: {:?}", ctx.command().name, payload);
+            let backtrace = LAST_PANIC_BACKTRACE.lock().take();
+            report_error(
+                ctx,
+                "Command Panic",
+                payload.as_deref().unwrap_or("(no panic message)"),
+                backtrace.as_deref(),
+            )
+            .await;
             let embed = CreateEmbed::new()
                 .title("[ERROR] Internal Error")
                 .description("An unexpected error occurred. Please try again later.")
                 .color(0xE74C3C);
             let _ = ctx.send(poise::CreateReply::default().embed(embed)).await;
         }
+        poise::FrameworkError::CommandCheckFailed { error, ctx, .. } => {
+            let message = match error {
+                Some(error) => format!("{}", error),
+                None => "You can't use this command here.".to_string(),
+            };
+            send_denied(ctx, message).await;
+        }
+        poise::FrameworkError::MissingUserPermissions {
+            missing_permissions,
+            ctx,
+            ..
+        } => {
+            let message = match missing_permissions {
+                Some(permissions) => {
+                    format!(
+                        "You need the **{}** permission to use this command.",
+                        permissions
+                    )
+                }
+                None => "You don't have permission to use this command.".to_string(),
+            };
+            send_denied(ctx, message).await;
+        }
+        poise::FrameworkError::MissingBotPermissions {
+            missing_permissions,
+            ctx,
+            ..
+        } => {
+            send_denied(
+                ctx,
+                format!(
+                    "I need the **{}** permission to run this command.",
+                    missing_permissions
+                ),
+            )
+            .await;
+        }
+        poise::FrameworkError::NotAnOwner { ctx, .. } => {
+            send_denied(ctx, "Only the bot owner can use this command.".to_string()).await;
+        }
         error => {
             eprintln!("Other error: {:?}", error);
         }
     }
 }
+
+/// Tell the user why a command was refused
+async fn send_denied(ctx: Context<'_>, message: String) {
+    let embed = CreateEmbed::new()
+        .title("[ERROR] Permission Denied")
+        .description(message)
+        .color(0xE74C3C);
+    let _ = ctx
+        .send(poise::CreateReply::default().embed(embed).ephemeral(true))
+        .await;
+}
+
+/// Send error details to `ERROR_LOG_CHANNEL`, if configured. Failures here are
+/// only printed so reporting can never trigger another report
+async fn report_error(ctx: Context<'_>, title: &str, message: &str, backtrace: Option<&str>) {
+    let Some(channel_id) = ctx.data().error_log_channel else {
+        return;
+    };
+
+    let guild = match ctx.guild_id() {
+        Some(guild_id) => {
+            let name = ctx
+                .guild()
+                .map(|g| g.name.clone())
+                .unwrap_or_else(|| "Unknown".to_string());
+            format!("{} (`{}`)", name, guild_id)
+        }
+        None => "Direct message".to_string(),
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("[ERROR] {}", title))
+        .field(
+            "Command",
+            format!("`/{}`", ctx.command().qualified_name),
+            true,
+        )
+        .field("Guild", guild, true)
+        .field(
+            "Channel",
+            format!("<#{}> (`{}`)", ctx.channel_id(), ctx.channel_id()),
+            true,
+        )
+        .field(
+            "User",
+            format!("{} (`{}`)", ctx.author().name, ctx.author().id),
+            true,
+        )
+        .field("Error", code_block(message, 1000), false)
+        .color(0xE74C3C)
+        .timestamp(Timestamp::now());
+
+    if let Some(backtrace) = backtrace {
+        embed = embed.description(code_block(backtrace, 3900));
+    }
+
+    if let Err(e) = channel_id
+        .send_message(ctx.http(), CreateMessage::new().embed(embed))
+        .await
+    {
+        eprintln!("[ERROR] Failed to send error report: {}", e);
+    }
+}
+
+/// Wrap text in a code block, keeping it under `max` characters
+fn code_block(text: &str, max: usize) -> String {
+    let text = text.replace("```", "'''");
+    let limit = max.saturating_sub(12);
+    if text.chars().count() > limit {
+        let truncated: String = text.chars().take(limit).collect();
+        format!("```\n{}…\n```", truncated)
+    } else {
+        format!("```\n{}\n```", text)
+    }
+}
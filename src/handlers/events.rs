@@ -1,11 +1,25 @@
 use crate::commands::Data;
-use crate::repository::ModerationRepository;
+use crate::commands::tags::render_tag;
+use crate::repository::{ModerationRepository, TagRepository, WelcomeConfig, WelcomeRepository};
 use crate::services::link::Downloader;
 use crate::services::music::player::get_bot_user_id;
 use crate::utils::embed;
+use parking_lot::Mutex;
 use serenity::all::{
-    ChannelId, Context, CreateAttachment, CreateMessage, FullEvent, GuildId, Member, RoleId, User,
+    ChannelId, ComponentInteraction, Context, CreateAllowedMentions, CreateAttachment, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, FullEvent, GuildId,
+    Interaction, Member, RoleId, User, UserId,
 };
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const VERIFY_BUTTON_ID: &str = "verify";
+const VERIFY_COOLDOWN: Duration = Duration::from_secs(10);
+/// Discord's limit for plain message content
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+static VERIFY_COOLDOWNS: OnceLock<Mutex<HashMap<UserId, Instant>>> = OnceLock::new();
 
 /// Main event handler for Discord events
 pub async fn handle_event(
@@ -14,15 +28,75 @@ pub async fn handle_event(
     data: &Data,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     match event {
+        FullEvent::Ready { data_about_bot } => {
+            crate::handlers::gateway::handle_ready(ctx, data_about_bot.guilds.len(), data);
+        }
+        FullEvent::Resume { event } => {
+            crate::handlers::gateway::handle_resume(ctx, event, data);
+        }
+        FullEvent::ShardStageUpdate { event } => {
+            crate::handlers::gateway::handle_stage_update(event, data);
+        }
         FullEvent::Message { new_message } => {
-            handle_video_link(ctx, new_message).await?;
+            // Each step is independent, so one failing must not skip the XP award
+            match crate::handlers::link_filter::handle_link_filter(ctx, new_message, data).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => eprintln!("[LINK FILTER] Failed to check message links: {}", e),
+            }
+            if let Err(e) = handle_tag_trigger(ctx, new_message, data).await {
+                eprintln!("[TAGS] Failed to handle tag trigger: {}", e);
+            }
+            if let Err(e) = handle_video_link(ctx, new_message).await {
+                eprintln!("[VIDEO] Failed to handle video link: {}", e);
+            }
+            crate::handlers::levels::handle_message_xp(ctx, new_message, data).await?;
+        }
+        FullEvent::MessageUpdate { new, event, .. } => {
+            crate::handlers::link_filter::handle_link_filter_edit(ctx, new.as_ref(), event, data)
+                .await?;
         }
         FullEvent::VoiceStateUpdate { old, new } => {
             handle_voice_state_update(ctx, old, new, data).await?;
         }
+        FullEvent::ReactionAdd { add_reaction } => {
+            crate::handlers::starboard::handle_star_reaction(ctx, add_reaction, data).await?;
+        }
+        FullEvent::ReactionRemove { removed_reaction } => {
+            crate::handlers::starboard::handle_star_reaction(ctx, removed_reaction, data).await?;
+        }
+        FullEvent::ReactionRemoveAll {
+            channel_id,
+            removed_from_message_id,
+        } => {
+            crate::handlers::starboard::handle_reactions_cleared(
+                ctx,
+                *channel_id,
+                *removed_from_message_id,
+                data,
+            )
+            .await?;
+        }
+        FullEvent::ReactionRemoveEmoji { removed_reactions } => {
+            crate::handlers::starboard::handle_star_reaction(ctx, removed_reactions, data).await?;
+        }
         FullEvent::GuildMemberAddition { new_member } => {
             handle_member_join(ctx, new_member, data).await?;
         }
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(component),
+        } if component.data.custom_id == VERIFY_BUTTON_ID => {
+            handle_verify_button(ctx, component, data).await?;
+        }
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(component),
+        } if component
+            .data
+            .custom_id
+            .starts_with(crate::handlers::music::CONTROL_BUTTON_PREFIX) =>
+        {
+            crate::handlers::music::handle_control_button(ctx, component, data).await?;
+        }
         FullEvent::GuildMemberRemoval {
             guild_id,
             user,
@@ -43,6 +117,49 @@ pub async fn handle_event(
     Ok(())
 }
 
+/// Reply with a tag's content when a message is `!<name>` for an auto-trigger tag
+async fn handle_tag_trigger(
+    ctx: &Context,
+    message: &serenity::all::Message,
+    data: &Data,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if message.author.bot {
+        return Ok(());
+    }
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+    let Some(name) = message
+        .content
+        .strip_prefix('!')
+        .and_then(|rest| rest.split_whitespace().next())
+    else {
+        return Ok(());
+    };
+
+    let tag =
+        TagRepository::get_trigger_tag(data.db.as_ref(), guild_id.get(), &name.to_lowercase())
+            .await?;
+    if let Some(tag) = tag {
+        let server = guild_id
+            .to_guild_cached(&ctx.cache)
+            .map(|g| g.name.clone())
+            .unwrap_or_else(|| "this server".to_string());
+        let content = render_tag(&tag.content, message.author.display_name(), &server);
+        message
+            .channel_id
+            .send_message(
+                &ctx.http,
+                CreateMessage::new()
+                    .content(content)
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
 async fn handle_video_link(
     ctx: &Context,
     message: &serenity::all::Message,
@@ -317,6 +434,97 @@ fn get_channel_name(ctx: &Context, guild_id: GuildId, channel_id: ChannelId) ->
         .unwrap_or_else(|| "Unknown".to_string())
 }
 
+/// Build the join announcement from the guild's welcome template, or the
+/// default embed when none is set. Also used by `/welcome_test`
+pub fn welcome_message(
+    welcome: Option<&WelcomeConfig>,
+    user: &User,
+    guild_name: &str,
+    member_count: u64,
+) -> CreateMessage {
+    let avatar = member_avatar(welcome, user);
+    match welcome.and_then(|w| w.welcome_message.as_deref()) {
+        Some(template) => custom_member_message(
+            welcome,
+            user,
+            &embed::render_member_template(template, user, true, guild_name, member_count),
+            &format!("WELCOME • {}", user.name),
+            embed::COLOR_WELCOME,
+        ),
+        None => CreateMessage::new().embed(apply_color(
+            welcome,
+            embed::member_join(
+                &user.name,
+                user.id.get(),
+                member_count,
+                avatar.as_deref(),
+                guild_name,
+            ),
+        )),
+    }
+}
+
+fn goodbye_message(
+    welcome: Option<&WelcomeConfig>,
+    user: &User,
+    guild_name: &str,
+    member_count: u64,
+) -> CreateMessage {
+    let avatar = member_avatar(welcome, user);
+    match welcome.and_then(|w| w.leave_message.as_deref()) {
+        Some(template) => custom_member_message(
+            welcome,
+            user,
+            &embed::render_member_template(template, user, false, guild_name, member_count),
+            &format!("GOODBYE • {}", user.name),
+            embed::COLOR_GOODBYE,
+        ),
+        None => CreateMessage::new().embed(apply_color(
+            welcome,
+            embed::member_leave(&user.name, member_count, avatar.as_deref(), guild_name),
+        )),
+    }
+}
+
+/// Send a rendered template as plain text, or as an embed when it was
+/// wrapped in `{embed:...}`. Only the member themselves can be pinged
+fn custom_member_message(
+    welcome: Option<&WelcomeConfig>,
+    user: &User,
+    rendered: &str,
+    footer: &str,
+    color: u32,
+) -> CreateMessage {
+    let (text, as_embed) = embed::split_member_template(rendered);
+    let message =
+        CreateMessage::new().allowed_mentions(CreateAllowedMentions::new().users(vec![user.id]));
+    if as_embed {
+        let avatar = member_avatar(welcome, user);
+        message.embed(apply_color(
+            welcome,
+            embed::member_custom(text, footer, color, avatar.as_deref()),
+        ))
+    } else {
+        // Placeholders like {user} can push a template past the limit
+        message.content(text.chars().take(MAX_MESSAGE_CHARS).collect::<String>())
+    }
+}
+
+fn member_avatar(welcome: Option<&WelcomeConfig>, user: &User) -> Option<String> {
+    if welcome.is_none_or(|w| w.show_avatar) {
+        user.avatar_url()
+    } else {
+        None
+    }
+}
+
+fn apply_color(welcome: Option<&WelcomeConfig>, embed: CreateEmbed) -> CreateEmbed {
+    match welcome.and_then(|w| w.color) {
+        Some(color) => embed.color(color as u32),
+        None => embed,
+    }
+}
+
 /// Handle new member joining the server
 async fn handle_member_join(
     ctx: &Context,
@@ -337,30 +545,31 @@ async fn handle_member_join(
             }
         }
 
+        if let Some(role_id) = config.unverified_role_id {
+            let role = RoleId::new(role_id as u64);
+            if let Err(e) = new_member.add_role(&ctx.http, role).await {
+                eprintln!("[MOD] Failed to assign unverified role: {}", e);
+            }
+        }
+
         if let Some(log_channel_id) = config.log_channel_id {
             let channel = ChannelId::new(log_channel_id as u64);
-            let member_count = ctx
+            let (member_count, guild_name) = ctx
                 .cache
                 .guild(guild_id)
-                .map(|g| g.member_count)
-                .unwrap_or(0);
-
-            let account_created = new_member
-                .user
-                .created_at()
-                .format("%Y-%m-%d %H:%M UTC")
-                .to_string();
-            let avatar = new_member.user.avatar_url();
-
-            let embed_msg = embed::member_join(
-                &new_member.user.name,
-                new_member.user.id.get(),
+                .map(|g| (g.member_count, g.name.clone()))
+                .unwrap_or_else(|| (0, "Server".to_string()));
+
+            let welcome = WelcomeRepository::get_config(pool, guild_id.get())
+                .await
+                .ok()
+                .flatten();
+            let message = welcome_message(
+                welcome.as_ref(),
+                &new_member.user,
+                &guild_name,
                 member_count,
-                avatar.as_deref(),
-                &account_created,
             );
-
-            let message = CreateMessage::new().embed(embed_msg);
             if let Err(e) = channel.send_message(&ctx.http, message).await {
                 eprintln!("[MOD] Failed to send join log: {}", e);
             }
@@ -370,6 +579,83 @@ async fn handle_member_join(
     Ok(())
 }
 
+/// Grant the verified role when someone clicks the verification button
+async fn handle_verify_button(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (Some(guild_id), Some(member)) = (component.guild_id, component.member.as_ref()) else {
+        return Ok(());
+    };
+
+    let on_cooldown = {
+        let mut cooldowns = VERIFY_COOLDOWNS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock();
+        cooldowns.retain(|_, clicked| clicked.elapsed() < VERIFY_COOLDOWN);
+        let limited = cooldowns.contains_key(&member.user.id);
+        cooldowns.insert(member.user.id, Instant::now());
+        limited
+    };
+
+    let pool = data.db.as_ref();
+    let config = ModerationRepository::get_config(pool, guild_id.get()).await?;
+
+    let reply = match config.as_ref().and_then(|c| c.verify_role_id) {
+        _ if on_cooldown => embed::warning("Slow Down", "Please wait a few seconds and try again."),
+        None => embed::error(
+            "Verification",
+            "Verification is not enabled on this server.",
+        ),
+        Some(role_id) if member.roles.contains(&RoleId::new(role_id as u64)) => {
+            embed::info("Verification", "You are already verified.")
+        }
+        Some(role_id) => {
+            match member
+                .add_role(&ctx.http, RoleId::new(role_id as u64))
+                .await
+            {
+                Ok(()) => {
+                    if let Some(unverified_id) = config.and_then(|c| c.unverified_role_id) {
+                        let _ = member
+                            .remove_role(&ctx.http, RoleId::new(unverified_id as u64))
+                            .await;
+                    }
+                    println!(
+                        "[MOD] Verified {} in guild {}",
+                        member.user.name,
+                        guild_id.get()
+                    );
+                    embed::success("Verified", "Welcome! You now have access to the server.")
+                }
+                // Usually the bot lacks Manage Roles or the role sits above the bot's
+                Err(e) => {
+                    eprintln!(
+                        "[MOD] Failed to verify {} in guild {}: {}",
+                        member.user.name,
+                        guild_id.get(),
+                        e
+                    );
+                    embed::error(
+                        "Verification Failed",
+                        "I couldn't give you the verified role. Please contact a moderator.",
+                    )
+                }
+            }
+        }
+    };
+
+    let response = CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(reply)
+            .ephemeral(true),
+    );
+    component.create_response(&ctx.http, response).await?;
+
+    Ok(())
+}
+
 /// Handle member leaving the server
 async fn handle_member_leave(
     ctx: &Context,
@@ -385,18 +671,17 @@ async fn handle_member_leave(
         if let Some(log_channel_id) = config.log_channel_id {
             let channel = ChannelId::new(log_channel_id as u64);
 
-            let guild_name = ctx
+            let (member_count, guild_name) = ctx
                 .cache
                 .guild(guild_id)
-                .map(|g| g.name.clone())
-                .unwrap_or_else(|| "Server".to_string());
-
-            let avatar = user.avatar_url();
-
-            let embed_msg =
-                embed::member_leave(&user.name, user.id.get(), avatar.as_deref(), &guild_name);
-
-            let message = CreateMessage::new().embed(embed_msg);
+                .map(|g| (g.member_count, g.name.clone()))
+                .unwrap_or_else(|| (0, "Server".to_string()));
+
+            let welcome = WelcomeRepository::get_config(pool, guild_id.get())
+                .await
+                .ok()
+                .flatten();
+            let message = goodbye_message(welcome.as_ref(), user, &guild_name, member_count);
             if let Err(e) = channel.send_message(&ctx.http, message).await {
                 eprintln!("[MOD] Failed to send leave log: {}", e);
             }
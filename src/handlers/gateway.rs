@@ -0,0 +1,89 @@
+use crate::commands::Data;
+use chrono::{DateTime, TimeZone, Utc};
+use serenity::all::{ConnectionStage, Context, ResumedEvent, ShardStageUpdateEvent};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+/// Gateway connection counters since startup, shown by `/gateway`.
+/// Timestamps are unix seconds, 0 until the first event
+#[derive(Debug, Default)]
+pub struct GatewayMetrics {
+    readies: AtomicU64,
+    resumes: AtomicU64,
+    disconnects: AtomicU64,
+    last_ready: AtomicI64,
+    last_resume: AtomicI64,
+    last_disconnect: AtomicI64,
+}
+
+/// Point-in-time copy of `GatewayMetrics`
+#[derive(Debug, Clone, Copy)]
+pub struct GatewaySnapshot {
+    pub readies: u64,
+    pub resumes: u64,
+    pub disconnects: u64,
+    pub last_ready: Option<DateTime<Utc>>,
+    pub last_resume: Option<DateTime<Utc>>,
+    pub last_disconnect: Option<DateTime<Utc>>,
+}
+
+impl GatewayMetrics {
+    fn record(count: &AtomicU64, last: &AtomicI64) -> u64 {
+        last.store(Utc::now().timestamp(), Ordering::Relaxed);
+        count.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn snapshot(&self) -> GatewaySnapshot {
+        let time = |last: &AtomicI64| match last.load(Ordering::Relaxed) {
+            0 => None,
+            secs => Utc.timestamp_opt(secs, 0).single(),
+        };
+        GatewaySnapshot {
+            readies: self.readies.load(Ordering::Relaxed),
+            resumes: self.resumes.load(Ordering::Relaxed),
+            disconnects: self.disconnects.load(Ordering::Relaxed),
+            last_ready: time(&self.last_ready),
+            last_resume: time(&self.last_resume),
+            last_disconnect: time(&self.last_disconnect),
+        }
+    }
+}
+
+/// A shard identified (again). Every ready after the first is a full reconnect
+pub fn handle_ready(ctx: &Context, guilds: usize, data: &Data) {
+    let metrics = &data.gateway;
+    let count = GatewayMetrics::record(&metrics.readies, &metrics.last_ready);
+    if count > 1 {
+        println!(
+            "[GATEWAY] Shard {} reconnected with a new session ({} guilds, ready #{})",
+            ctx.shard_id, guilds, count
+        );
+    } else {
+        println!("[GATEWAY] Shard {} ready ({} guilds)", ctx.shard_id, guilds);
+    }
+}
+
+pub fn handle_resume(ctx: &Context, _event: &ResumedEvent, data: &Data) {
+    let metrics = &data.gateway;
+    let count = GatewayMetrics::record(&metrics.resumes, &metrics.last_resume);
+    println!(
+        "[GATEWAY] Shard {} resumed its session (total resumes: {})",
+        ctx.shard_id, count
+    );
+}
+
+/// Counts a disconnect whenever a shard leaves the connected stage
+pub fn handle_stage_update(event: &ShardStageUpdateEvent, data: &Data) {
+    if event.old == ConnectionStage::Connected && event.new != ConnectionStage::Connected {
+        let metrics = &data.gateway;
+        let count = GatewayMetrics::record(&metrics.disconnects, &metrics.last_disconnect);
+        eprintln!(
+            "[GATEWAY] Shard {} disconnected ({:?} -> {:?}, total disconnects: {})",
+            event.shard_id, event.old, event.new, count
+        );
+    } else {
+        println!(
+            "[GATEWAY] Shard {} stage {:?} -> {:?}",
+            event.shard_id, event.old, event.new
+        );
+    }
+}
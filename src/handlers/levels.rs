@@ -0,0 +1,112 @@
+use crate::commands::Data;
+use crate::repository::LevelRepository;
+use parking_lot::Mutex;
+use serenity::all::{
+    Context, CreateAllowedMentions, CreateMessage, GuildId, Message, RoleId, UserId,
+};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Only one message per member earns XP in this window
+const XP_COOLDOWN: Duration = Duration::from_secs(60);
+const MIN_MESSAGE_XP: i32 = 5;
+const MAX_MESSAGE_XP: i32 = 15;
+
+static XP_COOLDOWNS: OnceLock<Mutex<HashMap<(GuildId, UserId), Instant>>> = OnceLock::new();
+
+/// Total XP needed to reach `level`
+pub fn xp_for_level(level: i32) -> i64 {
+    (level as f64 * 100.0 * 1.5f64.powi(level)) as i64
+}
+
+/// Highest level reached with `xp`
+pub fn level_for_xp(xp: i64) -> i32 {
+    let mut level = 0;
+    while xp >= xp_for_level(level + 1) {
+        level += 1;
+    }
+    level
+}
+
+fn message_xp() -> i32 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0) as i32;
+    MIN_MESSAGE_XP + nanos.rem_euclid(MAX_MESSAGE_XP - MIN_MESSAGE_XP + 1)
+}
+
+/// Award XP for a guild message, then announce level ups and hand out
+/// any reward roles for the levels reached
+pub async fn handle_message_xp(
+    ctx: &Context,
+    message: &Message,
+    data: &Data,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if message.author.bot {
+        return Ok(());
+    }
+    let Some(guild_id) = message.guild_id else {
+        return Ok(());
+    };
+
+    let on_cooldown = {
+        let mut cooldowns = XP_COOLDOWNS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock();
+        cooldowns.retain(|_, earned| earned.elapsed() < XP_COOLDOWN);
+        let key = (guild_id, message.author.id);
+        let limited = cooldowns.contains_key(&key);
+        if !limited {
+            cooldowns.insert(key, Instant::now());
+        }
+        limited
+    };
+    if on_cooldown {
+        return Ok(());
+    }
+
+    let pool = data.db.as_ref();
+    let user_id = message.author.id;
+    let user = LevelRepository::add_xp(pool, guild_id.get(), user_id.get(), message_xp()).await?;
+    let level = level_for_xp(user.xp as i64);
+    if level <= user.level {
+        return Ok(());
+    }
+    LevelRepository::set_level(pool, guild_id.get(), user_id.get(), level).await?;
+
+    // Every reward between the old and new level, in case one was skipped
+    let rewards: Vec<RoleId> = LevelRepository::get_level_roles(pool, guild_id.get())
+        .await?
+        .into_iter()
+        .filter(|r| r.level > user.level && r.level <= level)
+        .map(|r| RoleId::new(r.role_id as u64))
+        .collect();
+    for role in &rewards {
+        if let Err(e) = ctx
+            .http
+            .add_member_role(guild_id, user_id, *role, Some("Level reward"))
+            .await
+        {
+            eprintln!("[LEVELS] Failed to add reward role {}: {}", role, e);
+        }
+    }
+
+    let mut text = format!("🎉 <@{}> reached **level {}**!", user_id, level);
+    if !rewards.is_empty() {
+        let roles: Vec<String> = rewards.iter().map(|r| format!("<@&{}>", r)).collect();
+        text.push_str(&format!("\nNew role: {}", roles.join(", ")));
+    }
+    message
+        .channel_id
+        .send_message(
+            &ctx.http,
+            CreateMessage::new()
+                .content(text)
+                .allowed_mentions(CreateAllowedMentions::new().users(vec![user_id])),
+        )
+        .await?;
+
+    Ok(())
+}
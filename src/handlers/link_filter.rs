@@ -0,0 +1,221 @@
+use crate::commands::Data;
+use crate::repository::{LinkFilterRepository, ModerationRepository};
+use serenity::all::{
+    ChannelId, Context, CreateEmbed, CreateMessage, Message, MessageUpdateEvent, Timestamp,
+};
+
+/// Hosts of the http(s) links in a message. The scheme is looked for anywhere
+/// in a word so masked `[text](url)`, parenthesized and spoilered links count
+pub fn link_hosts(content: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = content
+        .split_whitespace()
+        .flat_map(word_links)
+        .filter_map(|link| reqwest::Url::parse(link).ok())
+        .filter_map(|url| url.host_str().map(str::to_lowercase))
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Every link starting inside `word`, cut at the markdown around it
+fn word_links(word: &str) -> Vec<&str> {
+    // ASCII lowercasing keeps byte offsets valid for slicing `word`
+    let lower = word.to_ascii_lowercase();
+    let starts: Vec<usize> = lower
+        .match_indices("http")
+        .map(|(i, _)| i)
+        .filter(|&i| lower[i..].starts_with("http://") || lower[i..].starts_with("https://"))
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(word.len());
+            let link = &word[start..end];
+            let link = link
+                .find(['(', ')', '[', ']', '<', '>', '|', '"', '\'', '`'])
+                .map_or(link, |cut| &link[..cut]);
+            link.trim_end_matches(['.', ',', '!', '?', ';', ':', '*', '_', '~'])
+        })
+        .collect()
+}
+
+/// Turn user input like `https://www.YouTube.com/watch` into `youtube.com`
+pub fn normalize_domain(input: &str) -> Option<String> {
+    let input = input.trim().to_lowercase();
+    let host = match reqwest::Url::parse(&input) {
+        Ok(url) if input.contains("://") => url.host_str()?.to_string(),
+        _ => input.split('/').next()?.to_string(),
+    };
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let valid = host.contains('.')
+        && !host.starts_with('.')
+        && !host.ends_with('.')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    valid.then(|| host.to_string())
+}
+
+/// Whether `host` is a whitelisted domain or one of its subdomains
+pub fn is_whitelisted(host: &str, whitelist: &[String]) -> bool {
+    whitelist.iter().any(|domain| {
+        host == domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|rest| rest.ends_with('.'))
+    })
+}
+
+/// Delete a message whose links fall outside the guild's whitelist, unless
+/// the author has an exempt role. Returns true when the message was deleted
+/// so the other message handlers can skip it
+pub async fn handle_link_filter(
+    ctx: &Context,
+    message: &Message,
+    data: &Data,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    if message.author.bot {
+        return Ok(false);
+    }
+    let Some(guild_id) = message.guild_id else {
+        return Ok(false);
+    };
+    let hosts = link_hosts(&message.content);
+    if hosts.is_empty() {
+        return Ok(false);
+    }
+
+    let pool = data.db.as_ref();
+    let Some(config) = LinkFilterRepository::get_config(pool, guild_id.get()).await? else {
+        return Ok(false);
+    };
+    if !config.is_enabled {
+        return Ok(false);
+    }
+
+    let whitelist = config.whitelist();
+    let blocked: Vec<String> = hosts
+        .into_iter()
+        .filter(|host| !is_whitelisted(host, &whitelist))
+        .collect();
+    if blocked.is_empty() {
+        return Ok(false);
+    }
+
+    let exempt = LinkFilterRepository::get_exempt_roles(pool, guild_id.get()).await?;
+    let is_exempt = message
+        .member
+        .as_ref()
+        .is_some_and(|m| m.roles.iter().any(|r| exempt.contains(&(r.get() as i64))));
+    if is_exempt {
+        return Ok(false);
+    }
+
+    if let Err(e) = message.delete(&ctx.http).await {
+        eprintln!(
+            "[LINK FILTER] Failed to delete message in {}: {}",
+            message.channel_id, e
+        );
+        return Ok(false);
+    }
+
+    let guild_name = ctx
+        .cache
+        .guild(guild_id)
+        .map(|g| g.name.clone())
+        .unwrap_or_else(|| "the server".to_string());
+    let warning = CreateMessage::new().embed(
+        CreateEmbed::new()
+            .title("🔗 Link Removed")
+            .description(format!(
+                "Your message in **{}** was removed because links to {} aren't allowed there.",
+                guild_name,
+                blocked.join(", ")
+            ))
+            .color(0xFFA500),
+    );
+    // Members with closed DMs just don't get the notice
+    let _ = message.author.direct_message(&ctx.http, warning).await;
+
+    if let Some(log_channel_id) = ModerationRepository::get_config(pool, guild_id.get())
+        .await?
+        .and_then(|c| c.log_channel_id)
+    {
+        let content: String = message.content.chars().take(1000).collect();
+        let embed = CreateEmbed::new()
+            .title("🔗 Link Filtered")
+            .field("Author", format!("<@{}>", message.author.id), true)
+            .field("Channel", format!("<#{}>", message.channel_id), true)
+            .field("Blocked", blocked.join(", "), false)
+            .field("Message", content, false)
+            .color(0xFFA500)
+            .timestamp(Timestamp::now());
+        let _ = ChannelId::new(log_channel_id as u64)
+            .send_message(&ctx.http, CreateMessage::new().embed(embed))
+            .await;
+    }
+
+    Ok(true)
+}
+
+/// Filter edited messages too, so a link can't be slipped in after sending.
+/// Messages aren't cached, so the edited message is fetched only when the new
+/// content has links
+pub async fn handle_link_filter_edit(
+    ctx: &Context,
+    new: Option<&Message>,
+    event: &MessageUpdateEvent,
+    data: &Data,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Updates without content are embeds resolving, not user edits
+    let Some(content) = &event.content else {
+        return Ok(());
+    };
+    if event.guild_id.is_none() || link_hosts(content).is_empty() {
+        return Ok(());
+    }
+
+    let message = match new {
+        Some(message) => message.clone(),
+        None => {
+            let mut message = event.channel_id.message(&ctx.http, event.id).await?;
+            event.apply_to_message(&mut message);
+            message
+        }
+    };
+    handle_link_filter(ctx, &message, data).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_hosts_plain_and_angle_brackets() {
+        assert_eq!(
+            link_hosts("see https://Example.com/a and <http://foo.org> or example.com"),
+            vec!["example.com", "foo.org"]
+        );
+    }
+
+    #[test]
+    fn test_link_hosts_inside_markdown() {
+        assert_eq!(
+            link_hosts("[free nitro](https://evil.com/gift) (https://paren.net)."),
+            vec!["evil.com", "paren.net"]
+        );
+        assert_eq!(
+            link_hosts("||HTTPS://spoiler.io|| **https://bold.dev**"),
+            vec!["bold.dev", "spoiler.io"]
+        );
+        assert_eq!(
+            link_hosts("x(https://a.com)[b](https://c.com)"),
+            vec!["a.com", "c.com"]
+        );
+    }
+}
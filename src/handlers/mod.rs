@@ -1,7 +1,11 @@
 pub mod error;
 pub mod events;
+pub mod gateway;
+pub mod levels;
+pub mod link_filter;
 pub mod music;
+pub mod starboard;
 
-pub use error::on_error;
+pub use error::{install_panic_hook, on_error};
 pub use events::handle_event;
 pub use music::handle_track_end;
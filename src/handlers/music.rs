@@ -1,9 +1,19 @@
+use crate::commands::Data;
+use crate::repository::MusicConfigRepository;
 use crate::services::music::player::{get_global_http, get_global_player};
-use crate::services::music::queue::QueuedTrack;
+use crate::services::music::queue::{MusicQueue, QueuedTrack};
 use crate::utils::embed;
 use lavalink_rs::client::LavalinkClient;
 use lavalink_rs::model::events::{TrackEnd, TrackEndReason};
-use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage, GuildId};
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, Context, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    EditInteractionResponse, EditMessage, GuildId, Http,
+};
+
+/// Prefix of the `/player` control panel button IDs, e.g. `music:pause`
+pub const CONTROL_BUTTON_PREFIX: &str = "music:";
+const VOLUME_STEP: u8 = 10;
 
 pub async fn handle_track_end(_client: LavalinkClient, event: &TrackEnd) {
     let should_continue: bool = event.reason.clone().into();
@@ -77,6 +87,10 @@ pub async fn handle_track_end(_client: LavalinkClient, event: &TrackEnd) {
                     is_same_track
                 );
 
+                if let Some(http) = get_global_http() {
+                    refresh_control_panel(http, guild_id).await;
+                }
+
                 // Only send Now Playing embed if this is a NEW track (not looping same track)
                 if !is_same_track {
                     if let Some(channel_id) = text_channel {
@@ -101,8 +115,14 @@ pub async fn handle_track_end(_client: LavalinkClient, event: &TrackEnd) {
                             );
 
                             let message = CreateMessage::new().embed(now_playing_embed);
-                            if let Err(e) = channel_id.send_message(http.as_ref(), message).await {
-                                eprintln!("[MUSIC] Failed to send Now Playing embed: {}", e);
+                            match channel_id.send_message(http.as_ref(), message).await {
+                                Ok(msg) => player.set_now_playing_message(
+                                    guild_id,
+                                    Some((msg.channel_id, msg.id)),
+                                ),
+                                Err(e) => {
+                                    eprintln!("[MUSIC] Failed to send Now Playing embed: {}", e)
+                                }
                             }
                         }
                     }
@@ -114,6 +134,9 @@ pub async fn handle_track_end(_client: LavalinkClient, event: &TrackEnd) {
         None => {
             println!("[MUSIC] Queue is empty, checking autoplay...");
             handle_autoplay(player, &player_ctx, guild_id, text_channel).await;
+            if let Some(http) = get_global_http() {
+                refresh_control_panel(http, guild_id).await;
+            }
         }
     }
 }
@@ -307,6 +330,277 @@ async fn handle_autoplay(
     }
 }
 
+/// Embed and buttons for the `/player` control panel. Buttons are disabled
+/// while nothing is playing or queued
+pub fn control_panel(queue: &MusicQueue) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let idle = queue.current.is_none() && queue.is_empty();
+
+    let mut embed = CreateEmbed::new()
+        .title("🎛️ Music Player")
+        .color(embed::COLOR_MUSIC);
+
+    embed = match &queue.current {
+        Some(current) => {
+            let info = &current.track.info;
+            let duration_ms = info.length;
+            let mut embed = embed
+                .description(format!(
+                    "**[{}]({})**\nby {}",
+                    info.title,
+                    info.uri.clone().unwrap_or_default(),
+                    info.author
+                ))
+                .field(
+                    "Duration",
+                    format!(
+                        "{}:{:02}",
+                        duration_ms / 60000,
+                        (duration_ms % 60000) / 1000
+                    ),
+                    true,
+                )
+                .field("Requested by", &current.requester_name, true)
+                .field(
+                    "Status",
+                    if queue.is_paused { "Paused" } else { "Playing" },
+                    true,
+                );
+            if let Some(artwork) = &info.artwork_url {
+                embed = embed.thumbnail(artwork);
+            }
+            embed
+        }
+        None => embed.description("Nothing is playing. Use `/play` to start."),
+    };
+
+    embed = embed
+        .field("Up Next", queue.len().to_string(), true)
+        .field("Loop", queue.loop_mode.label(), true)
+        .field("Volume", format!("{}%", queue.volume), true);
+
+    let button = |action: &str, label: &str, style: ButtonStyle| {
+        CreateButton::new(format!("{}{}", CONTROL_BUTTON_PREFIX, action))
+            .label(label)
+            .style(style)
+            .disabled(idle)
+    };
+
+    let pause_label = if queue.is_paused {
+        "▶ Resume"
+    } else {
+        "⏸ Pause"
+    };
+
+    let components = vec![
+        CreateActionRow::Buttons(vec![
+            button("pause", pause_label, ButtonStyle::Primary),
+            button("skip", "⏭ Skip", ButtonStyle::Secondary),
+            button("shuffle", "🔀 Shuffle", ButtonStyle::Secondary),
+            button("loop", "🔁 Loop", ButtonStyle::Secondary),
+            button("stop", "⏹ Stop", ButtonStyle::Danger),
+        ]),
+        CreateActionRow::Buttons(vec![
+            button("voldown", "🔉 -10", ButtonStyle::Secondary),
+            button("volup", "🔊 +10", ButtonStyle::Secondary),
+        ]),
+    ];
+
+    (embed, components)
+}
+
+/// Redraw the guild's control panel, forgetting it if the message is gone
+pub async fn refresh_control_panel(http: &Http, guild_id: GuildId) {
+    let Some(player) = get_global_player() else {
+        return;
+    };
+    let Some((channel_id, message_id)) = player.get_control_message(guild_id) else {
+        return;
+    };
+
+    let (embed, components) = control_panel(&player.get_queue(guild_id));
+    let edit = EditMessage::new().embed(embed).components(components);
+    if let Err(e) = channel_id.edit_message(http, message_id, edit).await {
+        println!(
+            "[MUSIC] Control panel in guild {} is gone: {}",
+            guild_id.get(),
+            e
+        );
+        player.set_control_message(guild_id, None);
+    }
+}
+
+/// Pause or resume playback. Shared by `/pause`, `/resume` and the control panel
+pub async fn set_paused(
+    player: &crate::services::music::MusicPlayer,
+    player_ctx: &lavalink_rs::player_context::PlayerContext,
+    guild_id: GuildId,
+    paused: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    player_ctx.set_pause(paused).await?;
+    player.set_paused(guild_id, paused);
+    Ok(())
+}
+
+/// Play the next queued track. With an empty queue playback stops and
+/// autoplay, when enabled, picks a related song and announces it in
+/// `text_channel`. Returns the queued track now playing, if there was one.
+/// Shared by `/skip` and the control panel
+pub async fn skip_track(
+    player: &crate::services::music::MusicPlayer,
+    player_ctx: &lavalink_rs::player_context::PlayerContext,
+    guild_id: GuildId,
+    text_channel: Option<serenity::all::ChannelId>,
+) -> Result<Option<QueuedTrack>, Box<dyn std::error::Error + Send + Sync>> {
+    match player.next_track(guild_id) {
+        Some(next_track) => {
+            // Save track title for autoplay
+            player.set_last_track_title(guild_id, Some(next_track.track.info.title.clone()));
+            player.set_current(guild_id, Some(next_track.clone()));
+            player_ctx.play(&next_track.track).await?;
+            Ok(Some(next_track))
+        }
+        None => {
+            player_ctx.stop_now().await?;
+            handle_autoplay(player, player_ctx, guild_id, text_channel).await;
+            Ok(None)
+        }
+    }
+}
+
+async fn reply_ephemeral(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    embed: CreateEmbed,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let response = CreateInteractionResponseMessage::new()
+        .embed(embed)
+        .ephemeral(true);
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
+        .await?;
+    Ok(())
+}
+
+/// The same checks as the music commands: the configured music channel, plus
+/// being in the bot's voice channel. Returns the reason when not allowed
+async fn control_denied(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    guild_id: GuildId,
+    data: &Data,
+) -> Result<Option<CreateEmbed>, Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(channel_id) =
+        MusicConfigRepository::get_channel(data.db.as_ref(), guild_id.get()).await?
+        && channel_id as u64 != component.channel_id.get()
+    {
+        return Ok(Some(embed::warning(
+            "Wrong Channel",
+            &format!("Please use music commands in <#{}>", channel_id),
+        )));
+    }
+
+    let (user_channel, bot_channel) = match guild_id.to_guild_cached(&ctx.cache) {
+        Some(guild) => {
+            let channel_of = |user| guild.voice_states.get(&user).and_then(|vs| vs.channel_id);
+            (
+                channel_of(component.user.id),
+                channel_of(ctx.cache.current_user().id),
+            )
+        }
+        None => (None, None),
+    };
+    if user_channel.is_none() || user_channel != bot_channel {
+        return Ok(Some(embed::error(
+            "Not in Voice",
+            "Join the bot's voice channel to use the controls",
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Handle a `music:*` button press from the control panel
+pub async fn handle_control_button(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Some(guild_id) = component.guild_id else {
+        return Ok(());
+    };
+    let Some(player) = get_global_player() else {
+        return Ok(());
+    };
+
+    let Some(player_ctx) = player.get_player_context(guild_id) else {
+        let embed = embed::error("Not Playing", "The bot is not playing music");
+        return reply_ephemeral(ctx, component, embed).await;
+    };
+    if let Some(embed) = control_denied(ctx, component, guild_id, data).await? {
+        return reply_ephemeral(ctx, component, embed).await;
+    }
+
+    let action = component
+        .data
+        .custom_id
+        .trim_start_matches(CONTROL_BUTTON_PREFIX);
+
+    // Skipping into autoplay can take longer than Discord's 3 second
+    // deadline, so acknowledge first and edit the panel afterwards
+    component
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    match action {
+        "pause" => {
+            let paused = !player.is_paused(guild_id);
+            set_paused(player, &player_ctx, guild_id, paused).await?;
+        }
+        "skip" => {
+            let text_channel = player.get_text_channel(guild_id);
+            skip_track(player, &player_ctx, guild_id, text_channel).await?;
+        }
+        "shuffle" => player.shuffle_queue(guild_id),
+        "loop" => {
+            player.cycle_loop_mode(guild_id);
+        }
+        "stop" => {
+            player_ctx.stop_now().await?;
+            player.clear_queue(guild_id);
+        }
+        "voldown" | "volup" => {
+            let current = player.get_volume(guild_id);
+            let volume = if action == "volup" {
+                current.saturating_add(VOLUME_STEP).min(150)
+            } else {
+                current.saturating_sub(VOLUME_STEP)
+            };
+            player.set_volume(guild_id, volume);
+            player_ctx.set_volume(volume as u16).await?;
+        }
+        _ => return Ok(()),
+    }
+
+    println!(
+        "[MUSIC] Control panel '{}' by {} in guild {}",
+        action,
+        component.user.name,
+        guild_id.get()
+    );
+
+    let (embed, components) = control_panel(&player.get_queue(guild_id));
+    component
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(components),
+        )
+        .await?;
+
+    Ok(())
+}
+
 /// Extract YouTube video ID from URL
 fn extract_video_id(url: &str) -> Option<String> {
     // Handle youtu.be/ID format
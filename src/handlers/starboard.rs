@@ -0,0 +1,206 @@
+use crate::commands::Data;
+use crate::repository::{StarboardConfig, StarboardRepository};
+use serenity::all::{
+    ChannelId, Context, CreateEmbed, CreateEmbedAuthor, CreateMessage, EditMessage, GuildId,
+    Message, MessageId, Reaction,
+};
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Placeholder id stored while a claimed entry's starboard post is being sent
+const PENDING_STAR_MESSAGE_ID: i64 = 0;
+
+/// Post, update or remove a message's starboard entry after a reaction change
+pub async fn handle_star_reaction(
+    ctx: &Context,
+    reaction: &Reaction,
+    data: &Data,
+) -> Result<(), Error> {
+    let Some(guild_id) = reaction.guild_id else {
+        return Ok(());
+    };
+
+    let pool = data.db.as_ref();
+    let Some(config) = StarboardRepository::get_config(pool, guild_id.get()).await? else {
+        return Ok(());
+    };
+    if reaction.emoji.to_string() != config.emoji {
+        return Ok(());
+    }
+
+    update_star_entry(
+        ctx,
+        data,
+        guild_id,
+        &config,
+        reaction.channel_id,
+        reaction.message_id,
+    )
+    .await
+}
+
+/// Recount a message's starboard entry after all of its reactions were removed
+pub async fn handle_reactions_cleared(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    data: &Data,
+) -> Result<(), Error> {
+    let Some(guild_id) = channel_id
+        .to_channel(ctx)
+        .await?
+        .guild()
+        .map(|c| c.guild_id)
+    else {
+        return Ok(());
+    };
+
+    let pool = data.db.as_ref();
+    let Some(config) = StarboardRepository::get_config(pool, guild_id.get()).await? else {
+        return Ok(());
+    };
+
+    update_star_entry(ctx, data, guild_id, &config, channel_id, message_id).await
+}
+
+async fn update_star_entry(
+    ctx: &Context,
+    data: &Data,
+    guild_id: GuildId,
+    config: &StarboardConfig,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<(), Error> {
+    let pool = data.db.as_ref();
+    let starboard_channel = ChannelId::new(config.channel_id as u64);
+    // Reactions on the starboard's own posts are not counted
+    if channel_id == starboard_channel {
+        return Ok(());
+    }
+
+    let message = channel_id.message(&ctx.http, message_id).await?;
+    let count = message
+        .reactions
+        .iter()
+        .find(|r| r.reaction_type.to_string() == config.emoji)
+        .map(|r| r.count as i32)
+        .unwrap_or(0);
+
+    let existing =
+        StarboardRepository::get_star_message(pool, guild_id.get(), message.id.get()).await?;
+    let content = format!("{} **{}** | <#{}>", config.emoji, count, message.channel_id);
+
+    match existing {
+        // Another event claimed the entry and is still posting it
+        Some(star) if star.star_message_id == PENDING_STAR_MESSAGE_ID => {}
+        Some(star) if count < config.threshold => {
+            let _ = starboard_channel
+                .delete_message(&ctx.http, MessageId::new(star.star_message_id as u64))
+                .await;
+            StarboardRepository::delete_star_message(pool, guild_id.get(), message.id.get())
+                .await?;
+        }
+        Some(star) if star.star_count != count => {
+            starboard_channel
+                .edit_message(
+                    &ctx.http,
+                    MessageId::new(star.star_message_id as u64),
+                    EditMessage::new().content(content),
+                )
+                .await?;
+            StarboardRepository::set_star_message(
+                pool,
+                guild_id.get(),
+                message.id.get(),
+                star.star_message_id as u64,
+                count,
+            )
+            .await?;
+        }
+        Some(_) => {}
+        None if count >= config.threshold => {
+            // Claim the entry first so concurrent reactions can't post it twice
+            if !StarboardRepository::claim_star_message(
+                pool,
+                guild_id.get(),
+                message.id.get(),
+                PENDING_STAR_MESSAGE_ID as u64,
+                count,
+            )
+            .await?
+            {
+                return Ok(());
+            }
+
+            let star_message = match starboard_channel
+                .send_message(
+                    &ctx.http,
+                    CreateMessage::new()
+                        .content(content)
+                        .embed(star_embed(&message, guild_id.get())),
+                )
+                .await
+            {
+                Ok(star_message) => star_message,
+                Err(e) => {
+                    // Release the claim so the next reaction can retry
+                    StarboardRepository::delete_star_message(
+                        pool,
+                        guild_id.get(),
+                        message.id.get(),
+                    )
+                    .await?;
+                    return Err(e.into());
+                }
+            };
+            StarboardRepository::set_star_message(
+                pool,
+                guild_id.get(),
+                message.id.get(),
+                star_message.id.get(),
+                count,
+            )
+            .await?;
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+fn star_embed(message: &Message, guild_id: u64) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .author(CreateEmbedAuthor::new(&message.author.name).icon_url(message.author.face()))
+        .description(&message.content)
+        .field(
+            "Source",
+            format!(
+                "[Jump to message](https://discord.com/channels/{}/{}/{})",
+                guild_id, message.channel_id, message.id
+            ),
+            false,
+        )
+        .color(0xFFAC33)
+        .timestamp(message.timestamp);
+
+    let image = message.attachments.iter().find(|a| {
+        a.content_type
+            .as_deref()
+            .is_some_and(|t| t.starts_with("image/"))
+    });
+    if let Some(image) = image {
+        embed = embed.image(&image.url);
+    }
+
+    let others: Vec<String> = message
+        .attachments
+        .iter()
+        .filter(|a| image.is_none_or(|i| i.id != a.id))
+        .map(|a| format!("[{}]({})", a.filename, a.url))
+        .collect();
+    if !others.is_empty() {
+        embed = embed.field("Attachments", others.join("\n"), false);
+    }
+
+    embed
+}
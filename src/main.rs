@@ -2,37 +2,43 @@ use dotenvy::dotenv;
 use lavalink_rs::client::LavalinkClient;
 use lavalink_rs::model::events::Events;
 use lavalink_rs::node::NodeBuilder;
+use parking_lot::{Mutex, RwLock};
 use poise::serenity_prelude::UserId;
-use serenity::all::{ActivityData, GatewayIntents, OnlineStatus};
+use serenity::all::{ActivityData, ChannelId, GatewayIntents, OnlineStatus};
 use songbird::SerenityInit;
 use std::collections::HashSet;
 use std::env;
 use std::sync::Arc;
+use tokio::task::JoinHandle;
 use worm::commands::{
-    Data, admin, ai, forex, general, moderation, music, ping, price, redeem, sys,
+    ActivityList, Data, admin, ai, birthday, crypto, forex, general, giveaway, github, levels,
+    link_filter, moderation, music, ping, price, redeem, starboard, sys, tags, weather,
 };
 use worm::config::Config;
 use worm::error::BotError;
-use worm::handlers::{handle_event, handle_track_end, on_error};
-use worm::repository::create_pool;
+use worm::handlers::{handle_event, handle_track_end, install_panic_hook, on_error};
+use worm::repository::{ModerationRepository, create_pool};
 use worm::services::genshin_redeem_checker::start_code_checker;
+use worm::services::maintenance;
+use worm::services::music::persist::{restore_queues, save_queues};
 use worm::services::music::MusicPlayer;
-use worm::services::tiingo::TiingoService;
+use worm::services::tiingo::{TiingoService, get_global_tiingo};
 
 #[tokio::main]
 async fn main() -> Result<(), BotError> {
     dotenv().ok();
+    install_panic_hook();
 
     println!("Starting WR Bot...");
 
-    let config = Config::from_env()
-        .map_err(|e| BotError::Config(format!("Failed to load config: {}", e)))?;
+    let config = Config::from_env()?;
 
     let intents = GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT
         | GatewayIntents::GUILDS
         | GatewayIntents::GUILD_VOICE_STATES
-        | GatewayIntents::GUILD_MEMBERS;
+        | GatewayIntents::GUILD_MEMBERS
+        | GatewayIntents::GUILD_MESSAGE_REACTIONS;
 
     let owner_id = env::var("CLIENT_ID")
         .unwrap_or_else(|_| "0".to_string())
@@ -68,6 +74,16 @@ async fn main() -> Result<(), BotError> {
     let owners_clone = owners.clone();
     let db_for_checker = db.clone();
     let db_for_setup = db.clone();
+    let db_for_shutdown = db.clone();
+    let health = worm::utils::health::new_registry();
+    let health_for_setup = health.clone();
+    let activities: ActivityList = Arc::new(RwLock::new(config.activities.clone()));
+    let activities_for_setup = activities.clone();
+    let activity_interval_secs = config.activity_interval_secs;
+    let error_log_channel = config.error_log_channel_id.map(ChannelId::new);
+    // Tiingo is started during setup, so its task is handed back here for shutdown
+    let tiingo_task: Arc<Mutex<Option<JoinHandle<()>>>> = Arc::default();
+    let tiingo_task_for_setup = tiingo_task.clone();
 
     let songbird = songbird::Songbird::serenity();
     let songbird_for_data = songbird.clone();
@@ -80,8 +96,20 @@ async fn main() -> Result<(), BotError> {
                 general::ping(),
                 general::say(),
                 general::purge(),
+                general::cleanup(),
+                general::delete_my_data(),
+                general::export_data(),
+                general::color(),
+                general::embed(),
+                general::encode(),
+                general::decode(),
+                general::shorten(),
+                general::timezone(),
+                general::define(),
+                general::botinfo(),
+                general::membercount(),
                 // Admin commands
-                admin::everyone(),
+                admin::announce(),
                 // AI commands
                 ai::worm(),
                 // Gemini AI commands
@@ -93,15 +121,29 @@ async fn main() -> Result<(), BotError> {
                 ai::gemini_translate(),
                 ai::gemini_code(),
                 ai::gemini_explain(),
+                ai::ai_model(),
+                ai::ai_export(),
+                ai::ai_stats(),
                 // Market Analysis commands (prefix only)
                 ai::analisa(),
                 // System commands
                 sys::sys(),
+                sys::health(),
+                sys::gateway(),
+                sys::scraper_status(),
+                sys::lavalink(),
+                sys::activity(),
+                sys::maintenance(),
+                sys::reload(),
                 // Redeem commands
                 redeem::redeem_setup(),
                 redeem::redeem_codes(),
                 redeem::redeem_disable(),
                 redeem::redeem_enable(),
+                redeem::redeem_announce(),
+                redeem::redeem_status(),
+                redeem::redeem_addgame(),
+                redeem::redeem_removegame(),
                 // Music commands
                 music::join(),
                 music::leave(),
@@ -112,37 +154,106 @@ async fn main() -> Result<(), BotError> {
                 music::stop(),
                 music::queue(),
                 music::nowplaying(),
+                music::player(),
+                music::seek(),
                 music::volume(),
                 music::repeat(),
                 music::shuffle(),
                 music::remove(),
+                music::jump(),
+                music::music_channel_set(),
+                music::music_channel_disable(),
                 music::autoplay(),
                 // Moderation commands
                 moderation::warn(),
                 moderation::warnings(),
+                moderation::delwarn(),
+                moderation::warn_expiry(),
                 moderation::clearwarnings(),
                 moderation::mute(),
                 moderation::unmute(),
                 moderation::kick(),
                 moderation::ban(),
                 moderation::unban(),
+                moderation::modlog(),
+                moderation::moderator_stats(),
+                moderation::role_add(),
+                moderation::role_remove(),
+                // Color role commands
+                moderation::color_role(),
+                moderation::color_pick(),
                 // Auto-role commands
                 moderation::autorole_set(),
                 moderation::autorole_disable(),
+                // Link filter commands
+                link_filter::link_filter(),
                 // Logging commands
                 moderation::log_setup(),
                 moderation::log_disable(),
+                // Welcome message commands
+                moderation::welcome_set(),
+                moderation::welcome_test(),
+                moderation::goodbye_set(),
+                moderation::welcome_style(),
+                moderation::welcome_reset(),
+                // Verification commands
+                moderation::verify_setup(),
+                moderation::verify_disable(),
                 // Forex commands
                 forex::forex_setup(),
                 forex::forex_disable(),
                 forex::forex_enable(),
                 forex::forex_status(),
+                forex::forex_latest(),
+                forex::forex_search(),
+                forex::forex_ping(),
+                forex::forex_language(),
+                forex::forex_ai_impact(),
+                forex::forex_threads(),
+                forex::forex_debug(),
+                forex::forex_filter(),
                 forex::forex_calendar(),
                 // Price commands
                 price::price(),
+                price::chart(),
                 price::alert(),
+                price::alert_move(),
+                price::spreadalert(),
                 price::alerts(),
                 price::alertremove(),
+                price::price_subscribe(),
+                price::alertedit(),
+                price::alerts_guild(),
+                price::watchlist(),
+                price::marketsummary_setup(),
+                price::marketsummary(),
+                price::priceboard(),
+                // Crypto commands
+                crypto::crypto_price(),
+                crypto::crypto_alert(),
+                crypto::crypto_alerts(),
+                crypto::crypto_alert_remove(),
+                // GitHub commands
+                github::github_subscribe(),
+                github::github_unsubscribe(),
+                // Weather commands
+                weather::weather(),
+                // Starboard commands
+                starboard::starboard(),
+                // Level commands
+                levels::rank(),
+                levels::leaderboard(),
+                levels::level_role(),
+                // Birthday commands
+                birthday::birthday(),
+                // Giveaway commands
+                giveaway::giveaway(),
+                // Tag commands
+                tags::tag(),
+                tags::tag_add(),
+                tags::tag_edit(),
+                tags::tag_remove(),
+                tags::tag_list(),
             ],
             prefix_options: poise::PrefixFrameworkOptions {
                 prefix: Some("!".into()),
@@ -155,6 +266,9 @@ async fn main() -> Result<(), BotError> {
         .setup(move |ctx, ready, framework| {
             let inner_db = db_for_setup.clone();
             let owners_inner = owners_clone.clone();
+            let health_inner = health_for_setup.clone();
+            let activities_inner = activities_for_setup.clone();
+            let tiingo_task_inner = tiingo_task_for_setup.clone();
             let user_id = ready.user.id;
             let songbird_clone = songbird_for_data.clone();
             let http_clone = ctx.http.clone();
@@ -194,6 +308,15 @@ async fn main() -> Result<(), BotError> {
                     }
                 };
 
+                if let Some(player) = music_player.clone() {
+                    let songbird = songbird_clone.clone();
+                    let http = ctx.http.clone();
+                    let db = inner_db.clone();
+                    tokio::spawn(async move {
+                        restore_queues(&player, &songbird, &http, db.as_ref()).await;
+                    });
+                }
+
                 let youtube_search = worm::services::youtube::YouTubeSearch::new();
                 if let Some(ref yt) = youtube_search {
                     worm::services::youtube::init_global_youtube(yt.clone());
@@ -206,10 +329,19 @@ async fn main() -> Result<(), BotError> {
                     let tiingo = Arc::new(TiingoService::new(tiingo_key));
                     worm::services::tiingo::init_global_tiingo(tiingo.clone());
 
+                    match tiingo.load_alerts(inner_db.as_ref()).await {
+                        Ok(0) => {}
+                        Ok(count) => println!("[OK] Restored {} price alert(s)", count),
+                        Err(e) => eprintln!("[WARN] Failed to restore price alerts: {}", e),
+                    }
+
                     let http_for_tiingo = ctx.http.clone();
-                    tokio::spawn(async move {
-                        tiingo.start_price_polling(http_for_tiingo).await;
-                    });
+                    let health_for_tiingo = health_inner.clone();
+                    *tiingo_task_inner.lock() = Some(tokio::spawn(async move {
+                        tiingo
+                            .start_price_polling(http_for_tiingo, health_for_tiingo)
+                            .await;
+                    }));
                     println!("[OK] Tiingo price service initialized");
                 } else {
                     println!("[WARN] Tiingo not available (no TIINGO_API_KEY)");
@@ -221,6 +353,14 @@ async fn main() -> Result<(), BotError> {
                     music_player,
                     songbird: songbird_clone,
                     youtube_search,
+                    health: health_inner,
+                    lavalink_status: Default::default(),
+                    activities: activities_inner,
+                    error_log_channel,
+                    weather_cache: Default::default(),
+                    calendar_cache: Default::default(),
+                    started_at: std::time::Instant::now(),
+                    gateway: Default::default(),
                 })
             })
         })
@@ -236,8 +376,19 @@ async fn main() -> Result<(), BotError> {
     let http = client.http.clone();
     let cache = client.cache.clone();
 
+    let shard_manager_for_shutdown = client.shard_manager.clone();
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        shutdown_signal().await;
+        println!("[SHUTDOWN] Signal received, disconnecting shards...");
+        shard_manager_for_shutdown.shutdown_all().await;
+    });
+
+    // Background loops and services, stopped before state is flushed on shutdown
+    let mut background_tasks = Vec::new();
+
+    background_tasks.push(tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(std::time::Duration::from_secs(activity_interval_secs));
         let mut idx = 0;
         loop {
             interval.tick().await;
@@ -250,31 +401,99 @@ async fn main() -> Result<(), BotError> {
                 .sum();
             let total_server: u64 = cache.guilds().len() as u64;
 
-            let activities = vec![
-                ActivityData::custom(format!("With {} users!", total_users)),
-                ActivityData::custom(format!("In {} server!", total_server)),
-            ];
-
-            let runners = shard_manager.runners.lock().await;
-            for (_, runner) in runners.iter() {
-                runner.runner_tx.set_presence(
-                    Some(activities[idx % activities.len()].clone()),
-                    OnlineStatus::Online,
-                );
+            let current = activities.read().clone();
+            if current.is_empty() {
+                continue;
+            }
+            let activity = render_activity(&current[idx % current.len()], total_users, total_server);
+
+            // Copy the messengers out so the lock is not held while sending; shards
+            // started or stopped since the last tick are simply picked up or skipped
+            let messengers: Vec<_> = shard_manager
+                .runners
+                .lock()
+                .await
+                .values()
+                .map(|runner| runner.runner_tx.clone())
+                .collect();
+            for messenger in messengers {
+                messenger.set_presence(Some(activity.clone()), OnlineStatus::Online);
             }
-            idx = (idx + 1) % activities.len();
+            idx = (idx + 1) % current.len();
         }
-    });
+    }));
 
     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
 
-    start_code_checker(db_for_checker.clone(), http.clone()).await;
+    background_tasks.extend(
+        start_code_checker(
+            db_for_checker.clone(),
+            http.clone(),
+            health.clone(),
+            owners.clone(),
+        )
+        .await,
+    );
     println!("[OK] Code checker service started!");
-    worm::services::forex::start_forex_service(db_for_checker, http.clone()).await;
+    let db_for_warnings = db_for_checker.clone();
+    background_tasks.push(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            match ModerationRepository::delete_expired_warnings(db_for_warnings.as_ref()).await {
+                Ok(0) => {}
+                Ok(count) => println!("[MOD] Deleted {} expired warning(s)", count),
+                Err(e) => eprintln!("[MOD] Failed to delete expired warnings: {}", e),
+            }
+        }
+    }));
+    let db_for_maintenance = db_for_checker.clone();
+    background_tasks.push(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(24 * 60 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = maintenance::run(db_for_maintenance.as_ref()).await {
+                eprintln!("[MAINTENANCE] Cleanup failed: {}", e);
+            }
+        }
+    }));
+    background_tasks.push(worm::services::market_summary::start_market_summary(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.push(worm::services::price_board::start_price_boards(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.push(worm::services::birthday::start_birthdays(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.push(worm::services::giveaway::start_giveaways(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.push(worm::services::crypto::start_crypto_alerts(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.push(worm::services::github::start_github_releases(
+        db_for_checker.clone(),
+        http.clone(),
+        health.clone(),
+    ));
+    background_tasks.extend(
+        worm::services::forex::start_forex_service(db_for_checker, http.clone(), health).await,
+    );
     println!("[OK] Forex news service started!");
     let http_for_idle = http.clone();
     let songbird_for_idle = songbird.clone();
-    tokio::spawn(async move {
+    background_tasks.push(tokio::spawn(async move {
         use serenity::all::CreateMessage;
         use std::time::Duration;
         use worm::utils::embed;
@@ -312,17 +531,84 @@ async fn main() -> Result<(), BotError> {
                 }
             }
         }
-    });
+    }));
     println!("[OK] Music idle timeout checker started!");
 
-    client
-        .start()
-        .await
-        .map_err(|e| BotError::Client(format!("Failed to initialize client: {}", e)))?;
+    let result = client.start().await;
+
+    println!("[SHUTDOWN] Stopping background services...");
+    background_tasks.extend(tiingo_task.lock().take());
+    for task in background_tasks {
+        task.abort();
+    }
+
+    if let Some(tiingo) = get_global_tiingo() {
+        match tiingo.save_alerts(db_for_shutdown.as_ref()).await {
+            Ok(count) => println!("[SHUTDOWN] Saved {} price alert(s)", count),
+            Err(e) => eprintln!("[SHUTDOWN] Failed to save price alerts: {}", e),
+        }
+    }
+
+    if let Some(player) = worm::services::music::player::get_global_player() {
+        match save_queues(player, &songbird, db_for_shutdown.as_ref()).await {
+            Ok(count) => println!("[SHUTDOWN] Saved {} music queue(s)", count),
+            Err(e) => eprintln!("[SHUTDOWN] Failed to save music queues: {}", e),
+        }
+    }
+
+    db_for_shutdown.close().await;
+    println!("[OK] Shutdown complete");
+
+    result.map_err(|e| BotError::Client(format!("Failed to initialize client: {}", e)))?;
 
     Ok(())
 }
 
+/// Fill in the `{users}`, `{servers}` and `{guilds}` placeholders of a configured activity
+fn render_activity(activity: &ActivityData, users: u64, servers: u64) -> ActivityData {
+    let fill = |text: &str| {
+        text.replace("{users}", &users.to_string())
+            .replace("{servers}", &servers.to_string())
+            .replace("{guilds}", &servers.to_string())
+    };
+
+    let mut activity = activity.clone();
+    activity.name = fill(&activity.name);
+    activity.state = activity.state.as_deref().map(fill);
+    activity
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix (sent by `docker stop` and systemd)
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            eprintln!("[SHUTDOWN] Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                eprintln!("[SHUTDOWN] Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 async fn initialize_lavalink(
     host: &str,
     port: u16,
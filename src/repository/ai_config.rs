@@ -0,0 +1,47 @@
+use sqlx::PgPool;
+
+pub struct AiConfigRepository;
+
+impl AiConfigRepository {
+    pub async fn get_model(pool: &PgPool, guild_id: u64) -> Result<Option<String>, sqlx::Error> {
+        let model = sqlx::query_scalar!(
+            r#"
+            SELECT model FROM guild_ai_config WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(model)
+    }
+
+    pub async fn set_model(pool: &PgPool, guild_id: u64, model: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO guild_ai_config (guild_id, model)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET model = EXCLUDED.model
+            "#,
+            guild_id as i64,
+            model,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn reset_model(pool: &PgPool, guild_id: u64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM guild_ai_config WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
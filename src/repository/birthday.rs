@@ -0,0 +1,172 @@
+use sqlx::{PgConnection, PgPool};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Birthday {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub month: i32,
+    pub day: i32,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BirthdayConfig {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub last_sent_date: Option<String>,
+}
+
+pub struct BirthdayRepository;
+
+impl BirthdayRepository {
+    pub async fn set_birthday(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        month: i32,
+        day: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO birthdays (guild_id, user_id, month, day)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id, user_id) DO UPDATE SET
+                month = EXCLUDED.month,
+                day = EXCLUDED.day
+            "#,
+            guild_id as i64,
+            user_id as i64,
+            month,
+            day,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_birthday(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM birthdays WHERE guild_id = $1 AND user_id = $2",
+            guild_id as i64,
+            user_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a user's birthday from every guild
+    pub async fn delete_user(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM birthdays WHERE user_id = $1", user_id as i64)
+            .execute(conn)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn get_guild_birthdays(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<Birthday>, sqlx::Error> {
+        let birthdays = sqlx::query_as!(
+            Birthday,
+            r#"
+            SELECT guild_id, user_id, month, day
+            FROM birthdays
+            WHERE guild_id = $1
+            ORDER BY month, day
+            "#,
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(birthdays)
+    }
+
+    /// Birthdays in a guild falling on any of the given days
+    pub async fn get_birthdays_on(
+        pool: &PgPool,
+        guild_id: u64,
+        dates: &[(i32, i32)],
+    ) -> Result<Vec<Birthday>, sqlx::Error> {
+        let (months, days): (Vec<i32>, Vec<i32>) = dates.iter().copied().unzip();
+        let birthdays = sqlx::query_as!(
+            Birthday,
+            r#"
+            SELECT b.guild_id, b.user_id, b.month, b.day
+            FROM birthdays b
+            JOIN UNNEST($2::INTEGER[], $3::INTEGER[]) AS d(month, day)
+                ON b.month = d.month AND b.day = d.day
+            WHERE b.guild_id = $1
+            ORDER BY b.user_id
+            "#,
+            guild_id as i64,
+            &months,
+            &days,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(birthdays)
+    }
+
+    pub async fn set_channel(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO birthday_config (guild_id, channel_id)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET channel_id = EXCLUDED.channel_id
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_channel(pool: &PgPool, guild_id: u64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM birthday_config WHERE guild_id = $1",
+            guild_id as i64
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_all_configs(pool: &PgPool) -> Result<Vec<BirthdayConfig>, sqlx::Error> {
+        let configs = sqlx::query_as!(
+            BirthdayConfig,
+            "SELECT guild_id, channel_id, last_sent_date FROM birthday_config"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(configs)
+    }
+
+    pub async fn mark_sent(pool: &PgPool, guild_id: u64, date: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE birthday_config SET last_sent_date = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            date,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
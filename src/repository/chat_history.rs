@@ -0,0 +1,116 @@
+use chrono::Utc;
+use sqlx::{PgConnection, PgPool};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ChatMessage {
+    /// `user` or `model`
+    pub role: String,
+    pub content: String,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+pub struct ChatHistoryRepository;
+
+impl ChatHistoryRepository {
+    /// `guild_id` is 0 for DMs
+    pub async fn add_message(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        role: &str,
+        content: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO chat_history (guild_id, user_id, role, content)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            guild_id as i64,
+            user_id as i64,
+            role,
+            content,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete all but the newest `keep` messages of a user's history
+    pub async fn prune(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        keep: i64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM chat_history
+            WHERE guild_id = $1 AND user_id = $2 AND id NOT IN (
+                SELECT id FROM chat_history
+                WHERE guild_id = $1 AND user_id = $2
+                ORDER BY id DESC
+                LIMIT $3
+            )
+            "#,
+            guild_id as i64,
+            user_id as i64,
+            keep,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Oldest first
+    pub async fn get_history(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Vec<ChatMessage>, sqlx::Error> {
+        let messages = sqlx::query_as!(
+            ChatMessage,
+            r#"
+            SELECT role, content, created_at
+            FROM chat_history
+            WHERE guild_id = $1 AND user_id = $2
+            ORDER BY id
+            "#,
+            guild_id as i64,
+            user_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(messages)
+    }
+
+    pub async fn clear(pool: &PgPool, guild_id: u64, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM chat_history WHERE guild_id = $1 AND user_id = $2
+            "#,
+            guild_id as i64,
+            user_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Every guild's history for a user, for `/delete_my_data`
+    pub async fn delete_user(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM chat_history WHERE user_id = $1
+            "#,
+            user_id as i64,
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
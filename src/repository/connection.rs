@@ -1,16 +1,33 @@
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::sync::Arc;
+use std::time::Duration;
 
 pub type DbPool = Arc<PgPool>;
 
+const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+
 pub async fn create_pool(database_url: &str) -> Result<DbPool, sqlx::Error> {
+    // Background services and commands each check out their own connection
+    let max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_MAX_CONNECTIONS);
+
     let pool = PgPoolOptions::new()
-        .max_connections(10)
+        .max_connections(max_connections)
+        .acquire_timeout(Duration::from_secs(10))
         .connect(database_url)
         .await?;
 
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    // Versioned migrations; applied versions are tracked in _sqlx_migrations
+    let migrator = sqlx::migrate!("./migrations");
+    migrator.run(&pool).await?;
+    let schema_version = migrator.iter().map(|m| m.version).max().unwrap_or(0);
 
-    println!("[OK] Database connected and migrations applied");
+    println!(
+        "[OK] Database connected ({} max connections), schema version {}",
+        max_connections, schema_version
+    );
     Ok(Arc::new(pool))
 }
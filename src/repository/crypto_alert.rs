@@ -0,0 +1,113 @@
+use super::StoredAlert;
+use sqlx::{PgConnection, PgPool};
+
+/// Crypto alerts share `StoredAlert` with forex alerts. `symbol` holds the
+/// CoinGecko coin id and `target_price` is in USD
+pub struct CryptoAlertRepository;
+
+impl CryptoAlertRepository {
+    /// Save a new alert and return its id. `alert.id` is ignored
+    pub async fn add_alert(pool: &PgPool, alert: &StoredAlert) -> Result<i64, sqlx::Error> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO crypto_alerts (guild_id, user_id, channel_id, symbol, condition,
+                target_price, rearm_pips, created_at, repeat, expires_at, last_triggered,
+                window_mins, notify_dm)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING id
+            "#,
+            alert.guild_id,
+            alert.user_id,
+            alert.channel_id,
+            alert.symbol,
+            alert.condition,
+            alert.target_price,
+            alert.rearm_pips,
+            alert.created_at,
+            alert.repeat,
+            alert.expires_at,
+            alert.last_triggered,
+            alert.window_mins,
+            alert.notify_dm,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn get_alerts(pool: &PgPool) -> Result<Vec<StoredAlert>, sqlx::Error> {
+        let alerts = sqlx::query_as!(
+            StoredAlert,
+            r#"
+            SELECT id, guild_id, user_id, channel_id, symbol, condition, target_price,
+                rearm_pips, created_at, repeat, expires_at, last_triggered, window_mins, notify_dm,
+                last_side, armed
+            FROM crypto_alerts
+            ORDER BY id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    pub async fn get_user_alerts(
+        pool: &PgPool,
+        user_id: u64,
+    ) -> Result<Vec<StoredAlert>, sqlx::Error> {
+        let alerts = sqlx::query_as!(
+            StoredAlert,
+            r#"
+            SELECT id, guild_id, user_id, channel_id, symbol, condition, target_price,
+                rearm_pips, created_at, repeat, expires_at, last_triggered, window_mins, notify_dm,
+                last_side, armed
+            FROM crypto_alerts
+            WHERE user_id = $1
+            ORDER BY id
+            "#,
+            user_id as i64
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    /// Delete an alert only if it belongs to `user_id`
+    pub async fn delete_user_alert(
+        pool: &PgPool,
+        id: i64,
+        user_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM crypto_alerts WHERE id = $1 AND user_id = $2",
+            id,
+            user_id as i64
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_alert(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM crypto_alerts WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_user(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM crypto_alerts WHERE user_id = $1",
+            user_id as i64
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,251 @@
+use serde_json::Value;
+use sqlx::PgPool;
+
+pub struct ExportRepository;
+
+impl ExportRepository {
+    /// Every row stored for a guild as a JSON array per table
+    pub async fn guild_data(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<(&'static str, Value)>, sqlx::Error> {
+        let guild_id = guild_id as i64;
+
+        let tables = vec![
+            (
+                "mod_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM mod_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "mod_warnings",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM mod_warnings t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "reminders",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM reminders t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "forex_channels",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM forex_channels t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "forex_filters",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM forex_filters t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "redeem_servers",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM redeem_servers t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "welcome_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM welcome_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "music_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM music_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "tags",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY name), '[]')::TEXT as "data!" FROM tags t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "starboard",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM starboard t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "star_messages",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY source_message_id), '[]')::TEXT as "data!" FROM star_messages t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "price_boards",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM price_boards t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "market_summary_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM market_summary_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "market_summary_symbols",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY symbol), '[]')::TEXT as "data!" FROM market_summary_symbols t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "user_xp",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY user_id), '[]')::TEXT as "data!" FROM user_xp t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "level_roles",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY level), '[]')::TEXT as "data!" FROM level_roles t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "link_filter_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM link_filter_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "link_filter_roles_exempt",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY role_id), '[]')::TEXT as "data!" FROM link_filter_roles_exempt t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "mod_actions",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM mod_actions t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "color_roles",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY role_id), '[]')::TEXT as "data!" FROM color_roles t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "birthdays",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY month, day, user_id), '[]')::TEXT as "data!" FROM birthdays t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "birthday_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM birthday_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "guild_ai_config",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t), '[]')::TEXT as "data!" FROM guild_ai_config t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "crypto_alerts",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY id), '[]')::TEXT as "data!" FROM crypto_alerts t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+            (
+                "github_subscriptions",
+                sqlx::query_scalar!(
+                    r#"SELECT COALESCE(json_agg(t ORDER BY repo), '[]')::TEXT as "data!" FROM github_subscriptions t WHERE guild_id = $1"#,
+                    guild_id,
+                )
+                .fetch_one(pool)
+                .await?,
+            ),
+        ];
+
+        tables
+            .into_iter()
+            .map(|(table, json)| {
+                let value =
+                    serde_json::from_str(&json).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                Ok((table, value))
+            })
+            .collect()
+    }
+}
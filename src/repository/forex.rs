@@ -6,24 +6,77 @@ pub struct ForexChannel {
     pub channel_id: i64,
     pub guild_id: i64,
     pub is_active: bool,
+    pub min_impact: String,
+    /// Only send items from this source key (e.g. "wsj_world")
+    pub source: Option<String>,
+    /// Only send items mentioning this currency or keyword (e.g. "XAU")
+    pub currency: Option<String>,
+    /// Role mentioned for news at or above `ping_min_impact`
+    pub ping_role_id: Option<i64>,
+    pub ping_min_impact: String,
+    /// Translation language code, or "off"
+    pub language: String,
+    /// Classify impact with Gemini; None follows `FOREX_AI_IMPACT`
+    pub ai_impact: Option<bool>,
+    /// Post news into per-currency threads under the channel
+    pub use_threads: bool,
+}
+
+/// Cached translation of a news item
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ForexTranslation {
+    pub title: String,
+    pub description: String,
+}
+
+/// Include/exclude filter; `kind` is "include" or "exclude"
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ForexFilter {
+    pub id: i64,
+    pub guild_id: i64,
+    pub kind: String,
+    pub value: String,
+}
+
+/// A news item that was sent, as shown by `/forex_latest`
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SentNews {
+    pub news_id: String,
+    pub source: String,
+    pub title: Option<String>,
+    pub currency: Option<String>,
+    pub impact: Option<String>,
+    pub link: Option<String>,
+    pub sent_at: i64,
 }
 
 pub struct ForexRepository;
 
 impl ForexRepository {
+    /// Add or update a forex channel. `min_impact` keeps the current value when None
     pub async fn insert_channel(
         pool: &PgPool,
         guild_id: u64,
         channel_id: u64,
+        source: Option<&str>,
+        currency: Option<&str>,
+        min_impact: Option<&str>,
     ) -> Result<(), sqlx::Error> {
         sqlx::query!(
             r#"
-            INSERT INTO forex_channels (guild_id, channel_id, is_active)
-            VALUES ($1, $2, TRUE)
-            ON CONFLICT(guild_id) DO UPDATE SET channel_id = $2, is_active = TRUE
+            INSERT INTO forex_channels (guild_id, channel_id, is_active, source, currency, min_impact)
+            VALUES ($1, $2, TRUE, $3, $4, COALESCE($5, 'low'))
+            ON CONFLICT(guild_id, channel_id) DO UPDATE
+            SET is_active = TRUE,
+                source = EXCLUDED.source,
+                currency = EXCLUDED.currency,
+                min_impact = COALESCE($5, forex_channels.min_impact)
             "#,
             guild_id as i64,
             channel_id as i64,
+            source,
+            currency,
+            min_impact,
         )
         .execute(pool)
         .await?;
@@ -31,10 +84,174 @@ impl ForexRepository {
         Ok(())
     }
 
-    pub async fn disable_channel(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
-        sqlx::query!(
-            "UPDATE forex_channels SET is_active = FALSE WHERE guild_id = $1",
+    /// Disable one channel, or every channel in the guild when `channel_id` is None.
+    /// Returns the number of channels changed
+    pub async fn disable_channel(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE forex_channels SET is_active = FALSE
+            WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR channel_id = $2)
+            "#,
+            guild_id as i64,
+            channel_id.map(|id| id as i64),
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Enable one channel, or every channel in the guild when `channel_id` is None.
+    /// Returns the number of channels changed
+    pub async fn enable_channel(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: Option<u64>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE forex_channels SET is_active = TRUE
+            WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR channel_id = $2)
+            "#,
+            guild_id as i64,
+            channel_id.map(|id| id as i64),
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Set the minimum impact level for every channel in a guild. Returns false if forex is not set up
+    pub async fn set_min_impact(
+        pool: &PgPool,
+        guild_id: u64,
+        min_impact: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE forex_channels SET min_impact = $2 WHERE guild_id = $1",
             guild_id as i64,
+            min_impact,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set or clear the ping role for one channel, or all guild channels when
+    /// `channel_id` is None. Returns the number of channels changed
+    pub async fn set_ping_role(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: Option<u64>,
+        role_id: Option<u64>,
+        min_impact: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE forex_channels SET ping_role_id = $3, ping_min_impact = $4
+            WHERE guild_id = $1 AND ($2::BIGINT IS NULL OR channel_id = $2)
+            "#,
+            guild_id as i64,
+            channel_id.map(|id| id as i64),
+            role_id.map(|id| id as i64),
+            min_impact,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Set the translation language for every channel in a guild. Returns false if forex is not set up
+    pub async fn set_language(
+        pool: &PgPool,
+        guild_id: u64,
+        language: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE forex_channels SET language = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            language,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Override AI impact classification for every channel in a guild, or
+    /// follow the global default when None. Returns false if forex is not set up
+    pub async fn set_ai_impact(
+        pool: &PgPool,
+        guild_id: u64,
+        enabled: Option<bool>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE forex_channels SET ai_impact = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            enabled,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Turn per-currency threads on or off for every channel in a guild.
+    /// Returns false if forex is not set up
+    pub async fn set_use_threads(
+        pool: &PgPool,
+        guild_id: u64,
+        enabled: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE forex_channels SET use_threads = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            enabled,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_thread(
+        pool: &PgPool,
+        channel_id: u64,
+        currency: &str,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let thread_id = sqlx::query_scalar!(
+            "SELECT thread_id FROM forex_threads WHERE channel_id = $1 AND currency = $2",
+            channel_id as i64,
+            currency,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(thread_id)
+    }
+
+    pub async fn set_thread(
+        pool: &PgPool,
+        channel_id: u64,
+        currency: &str,
+        thread_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO forex_threads (channel_id, currency, thread_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(channel_id, currency) DO UPDATE SET thread_id = EXCLUDED.thread_id
+            "#,
+            channel_id as i64,
+            currency,
+            thread_id as i64,
         )
         .execute(pool)
         .await?;
@@ -42,10 +259,42 @@ impl ForexRepository {
         Ok(())
     }
 
-    pub async fn enable_channel(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
+    pub async fn get_translation(
+        pool: &PgPool,
+        news_id: &str,
+        language: &str,
+    ) -> Result<Option<ForexTranslation>, sqlx::Error> {
+        let translation = sqlx::query_as!(
+            ForexTranslation,
+            "SELECT title, description FROM forex_translations WHERE news_id = $1 AND language = $2",
+            news_id,
+            language,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(translation)
+    }
+
+    pub async fn insert_translation(
+        pool: &PgPool,
+        news_id: &str,
+        language: &str,
+        title: &str,
+        description: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
         sqlx::query!(
-            "UPDATE forex_channels SET is_active = TRUE WHERE guild_id = $1",
-            guild_id as i64,
+            r#"
+            INSERT INTO forex_translations (news_id, language, title, description, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT(news_id, language) DO NOTHING
+            "#,
+            news_id,
+            language,
+            title,
+            description,
+            now,
         )
         .execute(pool)
         .await?;
@@ -56,7 +305,12 @@ impl ForexRepository {
     pub async fn get_active_channels(pool: &PgPool) -> Result<Vec<ForexChannel>, sqlx::Error> {
         let channels = sqlx::query_as!(
             ForexChannel,
-            "SELECT id, channel_id, guild_id, is_active FROM forex_channels WHERE is_active = TRUE"
+            r#"
+            SELECT id, channel_id, guild_id, is_active, min_impact, source, currency,
+                ping_role_id, ping_min_impact, language, ai_impact, use_threads
+            FROM forex_channels
+            WHERE is_active = TRUE
+            "#
         )
         .fetch_all(pool)
         .await?;
@@ -64,19 +318,97 @@ impl ForexRepository {
         Ok(channels)
     }
 
-    pub async fn get_channel(
+    pub async fn get_channels(
         pool: &PgPool,
         guild_id: u64,
-    ) -> Result<Option<ForexChannel>, sqlx::Error> {
-        let channel = sqlx::query_as!(
+    ) -> Result<Vec<ForexChannel>, sqlx::Error> {
+        let channels = sqlx::query_as!(
             ForexChannel,
-            "SELECT id, channel_id, guild_id, is_active FROM forex_channels WHERE guild_id = $1",
+            r#"
+            SELECT id, channel_id, guild_id, is_active, min_impact, source, currency,
+                ping_role_id, ping_min_impact, language, ai_impact, use_threads
+            FROM forex_channels
+            WHERE guild_id = $1
+            ORDER BY id
+            "#,
             guild_id as i64,
         )
-        .fetch_optional(pool)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(channels)
+    }
+
+    /// Add a filter. Returns false if it already exists
+    pub async fn add_filter(
+        pool: &PgPool,
+        guild_id: u64,
+        kind: &str,
+        value: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO forex_filters (guild_id, kind, value)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id, kind, value) DO NOTHING
+            "#,
+            guild_id as i64,
+            kind,
+            value,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove_filter(
+        pool: &PgPool,
+        guild_id: u64,
+        kind: &str,
+        value: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM forex_filters WHERE guild_id = $1 AND kind = $2 AND value = $3",
+            guild_id as i64,
+            kind,
+            value,
+        )
+        .execute(pool)
         .await?;
 
-        Ok(channel)
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_filters(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<ForexFilter>, sqlx::Error> {
+        let filters = sqlx::query_as!(
+            ForexFilter,
+            r#"
+            SELECT id, guild_id, kind, value
+            FROM forex_filters
+            WHERE guild_id = $1
+            ORDER BY kind, value
+            "#,
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(filters)
+    }
+
+    pub async fn get_all_filters(pool: &PgPool) -> Result<Vec<ForexFilter>, sqlx::Error> {
+        let filters = sqlx::query_as!(
+            ForexFilter,
+            "SELECT id, guild_id, kind, value FROM forex_filters"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(filters)
     }
 
     pub async fn is_news_sent(pool: &PgPool, news_id: &str) -> Result<bool, sqlx::Error> {
@@ -94,17 +426,25 @@ impl ForexRepository {
         pool: &PgPool,
         news_id: &str,
         source: &str,
+        title: &str,
+        currency: &str,
+        impact: &str,
+        link: Option<&str>,
     ) -> Result<(), sqlx::Error> {
         let now = chrono::Utc::now().timestamp();
         sqlx::query!(
             r#"
-            INSERT INTO forex_news_sent (news_id, source, sent_at)
-            VALUES ($1, $2, $3)
+            INSERT INTO forex_news_sent (news_id, source, sent_at, title, currency, impact, link)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             ON CONFLICT(news_id) DO NOTHING
             "#,
             news_id,
             source,
             now,
+            title,
+            currency,
+            impact,
+            link,
         )
         .execute(pool)
         .await?;
@@ -112,12 +452,119 @@ impl ForexRepository {
         Ok(())
     }
 
+    /// Most recently sent news with stored metadata, optionally only those whose
+    /// currency or title mentions `currency`
+    pub async fn get_recent_news(
+        pool: &PgPool,
+        limit: i64,
+        currency: Option<&str>,
+    ) -> Result<Vec<SentNews>, sqlx::Error> {
+        let news = sqlx::query_as!(
+            SentNews,
+            r#"
+            SELECT news_id, source, title, currency, impact, link, sent_at
+            FROM forex_news_sent
+            WHERE title IS NOT NULL
+              AND ($2::TEXT IS NULL OR currency ILIKE '%' || $2 || '%' OR title ILIKE '%' || $2 || '%')
+            ORDER BY sent_at DESC, id DESC
+            LIMIT $1
+            "#,
+            limit,
+            currency,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(news)
+    }
+
+    /// News sent within the last `days` whose title or currency contains
+    /// `keyword`, optionally only one impact level, newest first
+    pub async fn search_news(
+        pool: &PgPool,
+        keyword: &str,
+        impact: Option<&str>,
+        days: i64,
+        limit: i64,
+    ) -> Result<Vec<SentNews>, sqlx::Error> {
+        let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
+        let news = sqlx::query_as!(
+            SentNews,
+            r#"
+            SELECT news_id, source, title, currency, impact, link, sent_at
+            FROM forex_news_sent
+            WHERE title IS NOT NULL
+              AND sent_at >= $1
+              AND (title ILIKE '%' || $2 || '%' OR currency ILIKE '%' || $2 || '%')
+              AND ($3::TEXT IS NULL OR impact = $3)
+            ORDER BY sent_at DESC, id DESC
+            LIMIT $4
+            "#,
+            cutoff,
+            keyword,
+            impact,
+            limit,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(news)
+    }
+
+    /// Titles of news sent within the last `hours`
+    pub async fn get_recent_titles(pool: &PgPool, hours: i64) -> Result<Vec<String>, sqlx::Error> {
+        let cutoff = chrono::Utc::now().timestamp() - (hours * 3600);
+        let titles = sqlx::query_scalar!(
+            r#"SELECT title as "title!" FROM forex_news_sent WHERE sent_at >= $1 AND title IS NOT NULL"#,
+            cutoff,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(titles)
+    }
+
+    pub async fn is_reminder_sent(pool: &PgPool, reminder_id: &str) -> Result<bool, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM forex_calendar_reminders WHERE reminder_id = $1"#,
+            reminder_id,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    pub async fn insert_reminder(pool: &PgPool, reminder_id: &str) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        sqlx::query!(
+            r#"
+            INSERT INTO forex_calendar_reminders (reminder_id, sent_at)
+            VALUES ($1, $2)
+            ON CONFLICT(reminder_id) DO NOTHING
+            "#,
+            reminder_id,
+            now,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Old sent news and calendar reminders
     pub async fn cleanup_old_news(pool: &PgPool, days: i64) -> Result<u64, sqlx::Error> {
         let cutoff = chrono::Utc::now().timestamp() - (days * 86400);
-        let result = sqlx::query!("DELETE FROM forex_news_sent WHERE sent_at < $1", cutoff,)
+        let news = sqlx::query!("DELETE FROM forex_news_sent WHERE sent_at < $1", cutoff,)
             .execute(pool)
             .await?;
+        let reminders = sqlx::query!(
+            "DELETE FROM forex_calendar_reminders WHERE sent_at < $1",
+            cutoff
+        )
+        .execute(pool)
+        .await?;
 
-        Ok(result.rows_affected())
+        Ok(news.rows_affected() + reminders.rows_affected())
     }
 }
@@ -0,0 +1,90 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct GithubSubscription {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    /// `owner/repo`, lowercased
+    pub repo: String,
+    pub last_release_id: Option<String>,
+}
+
+pub struct GithubRepository;
+
+impl GithubRepository {
+    /// Subscribe a guild to a repo, or move an existing subscription to `channel_id`
+    pub async fn subscribe(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        repo: &str,
+        last_release_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO github_subscriptions (guild_id, channel_id, repo, last_release_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id, repo) DO UPDATE SET
+                channel_id = EXCLUDED.channel_id,
+                last_release_id = EXCLUDED.last_release_id
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+            repo,
+            last_release_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn unsubscribe(
+        pool: &PgPool,
+        guild_id: u64,
+        repo: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM github_subscriptions WHERE guild_id = $1 AND repo = $2",
+            guild_id as i64,
+            repo,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_all(pool: &PgPool) -> Result<Vec<GithubSubscription>, sqlx::Error> {
+        let subscriptions = sqlx::query_as!(
+            GithubSubscription,
+            r#"
+            SELECT guild_id, channel_id, repo, last_release_id
+            FROM github_subscriptions
+            ORDER BY repo, guild_id
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+
+    pub async fn set_last_release(
+        pool: &PgPool,
+        guild_id: u64,
+        repo: &str,
+        release_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE github_subscriptions SET last_release_id = $3 WHERE guild_id = $1 AND repo = $2",
+            guild_id as i64,
+            repo,
+            release_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
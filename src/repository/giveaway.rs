@@ -0,0 +1,122 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Giveaway {
+    pub message_id: i64,
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub host_id: i64,
+    pub prize: String,
+    pub winner_count: i32,
+    pub ends_at: i64,
+    pub ended: bool,
+    /// JSON array of user ids, e.g. `["123","456"]`
+    pub winner_ids: String,
+}
+
+impl Giveaway {
+    /// Every user drawn so far, oldest first
+    pub fn winners(&self) -> Vec<u64> {
+        serde_json::from_str::<Vec<String>>(&self.winner_ids)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|id| id.parse().ok())
+            .collect()
+    }
+}
+
+pub struct GiveawayRepository;
+
+impl GiveawayRepository {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        message_id: u64,
+        guild_id: u64,
+        channel_id: u64,
+        host_id: u64,
+        prize: &str,
+        winner_count: i32,
+        ends_at: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO giveaways
+                (message_id, guild_id, channel_id, host_id, prize, winner_count, ends_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            message_id as i64,
+            guild_id as i64,
+            channel_id as i64,
+            host_id as i64,
+            prize,
+            winner_count,
+            ends_at,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &PgPool,
+        guild_id: u64,
+        message_id: u64,
+    ) -> Result<Option<Giveaway>, sqlx::Error> {
+        let giveaway = sqlx::query_as!(
+            Giveaway,
+            r#"
+            SELECT message_id, guild_id, channel_id, host_id, prize, winner_count, ends_at,
+                   ended, winner_ids
+            FROM giveaways
+            WHERE guild_id = $1 AND message_id = $2
+            "#,
+            guild_id as i64,
+            message_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(giveaway)
+    }
+
+    /// Running giveaways whose end time has passed
+    pub async fn get_due(pool: &PgPool, now: i64) -> Result<Vec<Giveaway>, sqlx::Error> {
+        let giveaways = sqlx::query_as!(
+            Giveaway,
+            r#"
+            SELECT message_id, guild_id, channel_id, host_id, prize, winner_count, ends_at,
+                   ended, winner_ids
+            FROM giveaways
+            WHERE NOT ended AND ends_at <= $1
+            ORDER BY ends_at
+            "#,
+            now,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(giveaways)
+    }
+
+    /// Replace the winner list and mark the giveaway as ended
+    pub async fn set_winners(
+        pool: &PgPool,
+        message_id: u64,
+        winners: &[u64],
+    ) -> Result<(), sqlx::Error> {
+        let ids: Vec<String> = winners.iter().map(|id| id.to_string()).collect();
+        let winner_ids = serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string());
+
+        sqlx::query!(
+            "UPDATE giveaways SET ended = TRUE, winner_ids = $1 WHERE message_id = $2",
+            winner_ids,
+            message_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
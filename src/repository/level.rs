@@ -0,0 +1,185 @@
+use sqlx::{PgConnection, PgPool};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserXp {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub xp: i32,
+    pub level: i32,
+    pub messages: i32,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LevelRole {
+    pub guild_id: i64,
+    pub level: i32,
+    pub role_id: i64,
+}
+
+pub struct LevelRepository;
+
+impl LevelRepository {
+    /// Add XP for one message and return the updated totals
+    pub async fn add_xp(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        amount: i32,
+    ) -> Result<UserXp, sqlx::Error> {
+        let user = sqlx::query_as!(
+            UserXp,
+            r#"
+            INSERT INTO user_xp (guild_id, user_id, xp, messages)
+            VALUES ($1, $2, $3, 1)
+            ON CONFLICT(guild_id, user_id) DO UPDATE SET
+                xp = user_xp.xp + EXCLUDED.xp,
+                messages = user_xp.messages + 1
+            RETURNING guild_id, user_id, xp, level, messages
+            "#,
+            guild_id as i64,
+            user_id as i64,
+            amount,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    pub async fn set_level(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+        level: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE user_xp SET level = $3 WHERE guild_id = $1 AND user_id = $2",
+            guild_id as i64,
+            user_id as i64,
+            level,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_user(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Option<UserXp>, sqlx::Error> {
+        let user = sqlx::query_as!(
+            UserXp,
+            r#"
+            SELECT guild_id, user_id, xp, level, messages
+            FROM user_xp
+            WHERE guild_id = $1 AND user_id = $2
+            "#,
+            guild_id as i64,
+            user_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// 1-based position of a member in the guild leaderboard
+    pub async fn get_rank(pool: &PgPool, guild_id: u64, xp: i32) -> Result<i64, sqlx::Error> {
+        let above = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM user_xp WHERE guild_id = $1 AND xp > $2"#,
+            guild_id as i64,
+            xp,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(above + 1)
+    }
+
+    pub async fn get_leaderboard(
+        pool: &PgPool,
+        guild_id: u64,
+        limit: i64,
+    ) -> Result<Vec<UserXp>, sqlx::Error> {
+        let users = sqlx::query_as!(
+            UserXp,
+            r#"
+            SELECT guild_id, user_id, xp, level, messages
+            FROM user_xp
+            WHERE guild_id = $1
+            ORDER BY xp DESC, user_id
+            LIMIT $2
+            "#,
+            guild_id as i64,
+            limit,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// Remove a user's XP in every guild, returning how many rows were deleted
+    pub async fn delete_user(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM user_xp WHERE user_id = $1", user_id as i64)
+            .execute(conn)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn set_level_role(
+        pool: &PgPool,
+        guild_id: u64,
+        level: i32,
+        role_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO level_roles (guild_id, level, role_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id, level) DO UPDATE SET role_id = EXCLUDED.role_id
+            "#,
+            guild_id as i64,
+            level,
+            role_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_level_role(
+        pool: &PgPool,
+        guild_id: u64,
+        level: i32,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM level_roles WHERE guild_id = $1 AND level = $2",
+            guild_id as i64,
+            level,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_level_roles(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Vec<LevelRole>, sqlx::Error> {
+        let roles = sqlx::query_as!(
+            LevelRole,
+            "SELECT guild_id, level, role_id FROM level_roles WHERE guild_id = $1 ORDER BY level",
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(roles)
+    }
+}
@@ -0,0 +1,131 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LinkFilterConfig {
+    pub guild_id: i64,
+    pub is_enabled: bool,
+    /// Comma-separated, see `whitelist`
+    pub whitelist_domains: String,
+}
+
+impl LinkFilterConfig {
+    pub fn whitelist(&self) -> Vec<String> {
+        self.whitelist_domains
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+pub struct LinkFilterRepository;
+
+impl LinkFilterRepository {
+    pub async fn get_config(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<LinkFilterConfig>, sqlx::Error> {
+        let config = sqlx::query_as!(
+            LinkFilterConfig,
+            r#"
+            SELECT guild_id, is_enabled, whitelist_domains
+            FROM link_filter_config
+            WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(config)
+    }
+
+    pub async fn set_enabled(
+        pool: &PgPool,
+        guild_id: u64,
+        enabled: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO link_filter_config (guild_id, is_enabled)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET is_enabled = EXCLUDED.is_enabled
+            "#,
+            guild_id as i64,
+            enabled,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the whitelist with `domains`
+    pub async fn set_whitelist(
+        pool: &PgPool,
+        guild_id: u64,
+        domains: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO link_filter_config (guild_id, whitelist_domains)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET whitelist_domains = EXCLUDED.whitelist_domains
+            "#,
+            guild_id as i64,
+            domains.join(","),
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn add_exempt_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO link_filter_roles_exempt (guild_id, role_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            guild_id as i64,
+            role_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove_exempt_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM link_filter_roles_exempt WHERE guild_id = $1 AND role_id = $2",
+            guild_id as i64,
+            role_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_exempt_roles(pool: &PgPool, guild_id: u64) -> Result<Vec<i64>, sqlx::Error> {
+        let roles = sqlx::query_scalar!(
+            "SELECT role_id FROM link_filter_roles_exempt WHERE guild_id = $1 ORDER BY role_id",
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(roles)
+    }
+}
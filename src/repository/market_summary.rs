@@ -0,0 +1,132 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MarketSummaryConfig {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    /// `HH:MM` in UTC
+    pub post_time: String,
+    pub last_sent_date: Option<String>,
+}
+
+pub struct MarketSummaryRepository;
+
+impl MarketSummaryRepository {
+    pub async fn set_config(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        post_time: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO market_summary_config (guild_id, channel_id, post_time)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id) DO UPDATE SET
+                channel_id = EXCLUDED.channel_id,
+                post_time = EXCLUDED.post_time
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+            post_time,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_config(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<MarketSummaryConfig>, sqlx::Error> {
+        let config = sqlx::query_as!(
+            MarketSummaryConfig,
+            r#"
+            SELECT guild_id, channel_id, post_time, last_sent_date
+            FROM market_summary_config
+            WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(config)
+    }
+
+    pub async fn get_all_configs(pool: &PgPool) -> Result<Vec<MarketSummaryConfig>, sqlx::Error> {
+        let configs = sqlx::query_as!(
+            MarketSummaryConfig,
+            r#"
+            SELECT guild_id, channel_id, post_time, last_sent_date
+            FROM market_summary_config
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(configs)
+    }
+
+    pub async fn mark_sent(pool: &PgPool, guild_id: u64, date: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE market_summary_config SET last_sent_date = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            date,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Add a symbol to a guild's summary. Returns false if it was already there
+    pub async fn add_symbol(
+        pool: &PgPool,
+        guild_id: u64,
+        symbol: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO market_summary_symbols (guild_id, symbol)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id, symbol) DO NOTHING
+            "#,
+            guild_id as i64,
+            symbol,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove_symbol(
+        pool: &PgPool,
+        guild_id: u64,
+        symbol: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM market_summary_symbols WHERE guild_id = $1 AND symbol = $2",
+            guild_id as i64,
+            symbol,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Symbols in a guild's summary, sorted alphabetically
+    pub async fn get_symbols(pool: &PgPool, guild_id: u64) -> Result<Vec<String>, sqlx::Error> {
+        let symbols = sqlx::query_scalar!(
+            "SELECT symbol FROM market_summary_symbols WHERE guild_id = $1 ORDER BY symbol",
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(symbols)
+    }
+}
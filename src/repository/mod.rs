@@ -1,11 +1,45 @@
+pub mod ai_config;
+pub mod birthday;
+pub mod chat_history;
 pub mod connection;
+pub mod crypto_alert;
+pub mod export;
 pub mod forex;
+pub mod giveaway;
+pub mod github;
+pub mod level;
+pub mod link_filter;
+pub mod market_summary;
 pub mod moderation;
+pub mod music;
+pub mod price_alert;
+pub mod price_board;
 pub mod redeem;
 pub mod reminder;
+pub mod starboard;
+pub mod tag;
+pub mod watchlist;
+pub mod welcome;
 
+pub use ai_config::AiConfigRepository;
+pub use birthday::{Birthday, BirthdayConfig, BirthdayRepository};
+pub use chat_history::{ChatHistoryRepository, ChatMessage};
 pub use connection::{DbPool, create_pool};
-pub use forex::{ForexChannel, ForexRepository};
-pub use moderation::{ModConfig, ModerationRepository, Warning};
+pub use crypto_alert::CryptoAlertRepository;
+pub use export::ExportRepository;
+pub use forex::{ForexChannel, ForexFilter, ForexRepository, ForexTranslation, SentNews};
+pub use giveaway::{Giveaway, GiveawayRepository};
+pub use github::{GithubRepository, GithubSubscription};
+pub use level::{LevelRepository, LevelRole, UserXp};
+pub use link_filter::{LinkFilterConfig, LinkFilterRepository};
+pub use market_summary::{MarketSummaryConfig, MarketSummaryRepository};
+pub use moderation::{ActionCount, ModAction, ModConfig, ModerationRepository, Warning};
+pub use music::{MusicConfigRepository, MusicQueueRepository, SavedQueue};
+pub use price_alert::{PriceAlertRepository, StoredAlert};
+pub use price_board::{PriceBoard, PriceBoardRepository};
 pub use redeem::{RedeemCode, RedeemRepository, RedeemServer};
 pub use reminder::{Reminder, ReminderRepository};
+pub use starboard::{StarMessage, StarboardConfig, StarboardRepository};
+pub use tag::{Tag, TagRepository};
+pub use watchlist::WatchlistRepository;
+pub use welcome::{WelcomeConfig, WelcomeRepository};
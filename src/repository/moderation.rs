@@ -1,5 +1,5 @@
 use chrono::Utc;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 /// Warning record for a user
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -12,12 +12,38 @@ pub struct Warning {
     pub created_at: chrono::DateTime<Utc>,
 }
 
-/// Moderation config for a guild (auto-role, log channel)
+/// Moderation config for a guild (auto-role, log channel, verification)
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct ModConfig {
     pub guild_id: i64,
     pub auto_role_id: Option<i64>,
     pub log_channel_id: Option<i64>,
+    pub verify_role_id: Option<i64>,
+    pub unverified_role_id: Option<i64>,
+    /// Warnings older than this are ignored; None keeps them forever
+    pub warn_expiry_days: Option<i32>,
+}
+
+/// One entry in the moderation audit log
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ModAction {
+    pub id: i64,
+    pub guild_id: i64,
+    /// `warn`, `mute`, `unmute`, `kick`, `ban` or `unban`
+    pub action_type: String,
+    pub target_user_id: i64,
+    pub moderator_id: i64,
+    pub reason: Option<String>,
+    /// Length of timed actions such as mutes
+    pub duration_secs: Option<i32>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+/// How many actions of one type a moderator has taken
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ActionCount {
+    pub action_type: String,
+    pub count: i64,
 }
 
 pub struct ModerationRepository;
@@ -50,7 +76,7 @@ impl ModerationRepository {
         Ok(result)
     }
 
-    /// Get all warnings for a user in a guild
+    /// Get a user's warnings in a guild, excluding expired ones
     pub async fn get_warnings(
         pool: &PgPool,
         guild_id: u64,
@@ -59,10 +85,13 @@ impl ModerationRepository {
         let warnings = sqlx::query_as!(
             Warning,
             r#"
-            SELECT id, guild_id, user_id, moderator_id, reason, created_at
-            FROM mod_warnings
-            WHERE guild_id = $1 AND user_id = $2
-            ORDER BY created_at DESC
+            SELECT w.id, w.guild_id, w.user_id, w.moderator_id, w.reason, w.created_at
+            FROM mod_warnings w
+            LEFT JOIN mod_config c ON c.guild_id = w.guild_id
+            WHERE w.guild_id = $1 AND w.user_id = $2
+              AND (c.warn_expiry_days IS NULL
+                   OR w.created_at >= NOW() - make_interval(days => c.warn_expiry_days))
+            ORDER BY w.created_at DESC
             "#,
             guild_id as i64,
             user_id as i64,
@@ -73,14 +102,21 @@ impl ModerationRepository {
         Ok(warnings)
     }
 
-    /// Get warning count for a user
+    /// Get warning count for a user, excluding expired warnings
     pub async fn get_warning_count(
         pool: &PgPool,
         guild_id: u64,
         user_id: u64,
     ) -> Result<i64, sqlx::Error> {
         let count = sqlx::query_scalar!(
-            r#"SELECT COUNT(*) as "count!" FROM mod_warnings WHERE guild_id = $1 AND user_id = $2"#,
+            r#"
+            SELECT COUNT(*) as "count!"
+            FROM mod_warnings w
+            LEFT JOIN mod_config c ON c.guild_id = w.guild_id
+            WHERE w.guild_id = $1 AND w.user_id = $2
+              AND (c.warn_expiry_days IS NULL
+                   OR w.created_at >= NOW() - make_interval(days => c.warn_expiry_days))
+            "#,
             guild_id as i64,
             user_id as i64,
         )
@@ -107,21 +143,53 @@ impl ModerationRepository {
         Ok(result.rows_affected())
     }
 
-    /// Delete a specific warning by ID
+    /// Delete warnings received by a user in every guild (moderator records are kept)
+    pub async fn delete_user_warnings(
+        conn: &mut PgConnection,
+        user_id: u64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM mod_warnings WHERE user_id = $1",
+            user_id as i64,
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete warnings past their guild's expiry window
+    pub async fn delete_expired_warnings(pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM mod_warnings w
+            USING mod_config c
+            WHERE c.guild_id = w.guild_id
+              AND c.warn_expiry_days IS NOT NULL
+              AND w.created_at < NOW() - make_interval(days => c.warn_expiry_days)
+            "#
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete a specific warning by ID. Returns the warned user's ID if a row was removed
     pub async fn delete_warning(
         pool: &PgPool,
         warning_id: i64,
         guild_id: u64,
-    ) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query!(
-            "DELETE FROM mod_warnings WHERE id = $1 AND guild_id = $2",
+    ) -> Result<Option<u64>, sqlx::Error> {
+        let user_id = sqlx::query_scalar!(
+            "DELETE FROM mod_warnings WHERE id = $1 AND guild_id = $2 RETURNING user_id",
             warning_id,
             guild_id as i64,
         )
-        .execute(pool)
+        .fetch_optional(pool)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(user_id.map(|id| id as u64))
     }
 
     // ==================== MOD CONFIG ====================
@@ -133,7 +201,12 @@ impl ModerationRepository {
     ) -> Result<Option<ModConfig>, sqlx::Error> {
         let config = sqlx::query_as!(
             ModConfig,
-            "SELECT guild_id, auto_role_id, log_channel_id FROM mod_config WHERE guild_id = $1",
+            r#"
+            SELECT guild_id, auto_role_id, log_channel_id, verify_role_id, unverified_role_id,
+                warn_expiry_days
+            FROM mod_config
+            WHERE guild_id = $1
+            "#,
             guild_id as i64,
         )
         .fetch_optional(pool)
@@ -196,6 +269,27 @@ impl ModerationRepository {
         Ok(())
     }
 
+    /// Set or clear (None) the warning expiry window for a guild
+    pub async fn set_warn_expiry(
+        pool: &PgPool,
+        guild_id: u64,
+        days: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO mod_config (guild_id, warn_expiry_days)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET warn_expiry_days = EXCLUDED.warn_expiry_days
+            "#,
+            guild_id as i64,
+            days,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Disable logging for a guild
     pub async fn disable_logging(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
         sqlx::query!(
@@ -207,4 +301,190 @@ impl ModerationRepository {
 
         Ok(())
     }
+
+    /// Set verification roles for a guild
+    pub async fn set_verification(
+        pool: &PgPool,
+        guild_id: u64,
+        verify_role_id: u64,
+        unverified_role_id: Option<u64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO mod_config (guild_id, verify_role_id, unverified_role_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id) DO UPDATE
+            SET verify_role_id = EXCLUDED.verify_role_id,
+                unverified_role_id = EXCLUDED.unverified_role_id
+            "#,
+            guild_id as i64,
+            verify_role_id as i64,
+            unverified_role_id.map(|id| id as i64),
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Disable verification for a guild
+    pub async fn disable_verification(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE mod_config SET verify_role_id = NULL, unverified_role_id = NULL WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // ==================== COLOR ROLES ====================
+
+    /// Add a self-assignable color role, returning false if it was already added
+    pub async fn add_color_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO color_roles (guild_id, role_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            guild_id as i64,
+            role_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a color role from the picker
+    pub async fn remove_color_role(
+        pool: &PgPool,
+        guild_id: u64,
+        role_id: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM color_roles WHERE guild_id = $1 AND role_id = $2",
+            guild_id as i64,
+            role_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Color role IDs configured for a guild
+    pub async fn get_color_roles(pool: &PgPool, guild_id: u64) -> Result<Vec<i64>, sqlx::Error> {
+        let roles = sqlx::query_scalar!(
+            "SELECT role_id FROM color_roles WHERE guild_id = $1 ORDER BY role_id",
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(roles)
+    }
+
+    // ==================== AUDIT LOG ====================
+
+    /// Record a moderation action in the audit log
+    pub async fn log_action(
+        pool: &PgPool,
+        guild_id: u64,
+        action_type: &str,
+        target_user_id: u64,
+        moderator_id: u64,
+        reason: Option<&str>,
+        duration_secs: Option<i32>,
+    ) -> Result<i64, sqlx::Error> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO mod_actions
+                (guild_id, action_type, target_user_id, moderator_id, reason, duration_secs)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+            guild_id as i64,
+            action_type,
+            target_user_id as i64,
+            moderator_id as i64,
+            reason,
+            duration_secs,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Every action taken against a user in a guild, oldest first
+    pub async fn get_user_actions(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<Vec<ModAction>, sqlx::Error> {
+        let actions = sqlx::query_as!(
+            ModAction,
+            r#"
+            SELECT id, guild_id, action_type, target_user_id, moderator_id, reason,
+                duration_secs, created_at
+            FROM mod_actions
+            WHERE guild_id = $1 AND target_user_id = $2
+            ORDER BY created_at, id
+            "#,
+            guild_id as i64,
+            user_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(actions)
+    }
+
+    /// Action counts by type for one moderator, most common first
+    pub async fn get_moderator_stats(
+        pool: &PgPool,
+        guild_id: u64,
+        moderator_id: u64,
+    ) -> Result<Vec<ActionCount>, sqlx::Error> {
+        let counts = sqlx::query_as!(
+            ActionCount,
+            r#"
+            SELECT action_type, COUNT(*) as "count!"
+            FROM mod_actions
+            WHERE guild_id = $1 AND moderator_id = $2
+            GROUP BY action_type
+            ORDER BY COUNT(*) DESC, action_type
+            "#,
+            guild_id as i64,
+            moderator_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Delete a user's audit log in a guild
+    pub async fn clear_user_actions(
+        pool: &PgPool,
+        guild_id: u64,
+        user_id: u64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM mod_actions WHERE guild_id = $1 AND target_user_id = $2",
+            guild_id as i64,
+            user_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
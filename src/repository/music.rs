@@ -0,0 +1,110 @@
+use sqlx::PgPool;
+
+pub struct MusicConfigRepository;
+
+impl MusicConfigRepository {
+    /// Get the channel music commands are restricted to, if any
+    pub async fn get_channel(pool: &PgPool, guild_id: u64) -> Result<Option<i64>, sqlx::Error> {
+        let channel_id = sqlx::query_scalar!(
+            "SELECT music_channel_id FROM music_config WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(channel_id.flatten())
+    }
+
+    /// Restrict music commands to a channel
+    pub async fn set_channel(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO music_config (guild_id, music_channel_id)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET music_channel_id = EXCLUDED.music_channel_id
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Allow music commands in any channel again
+    pub async fn clear_channel(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE music_config SET music_channel_id = NULL WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Queue saved on shutdown so playback can resume after a restart
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SavedQueue {
+    pub guild_id: i64,
+    pub voice_channel_id: i64,
+    pub text_channel_id: Option<i64>,
+    pub volume: i32,
+    /// JSON list of tracks, the one that was playing first
+    pub tracks: String,
+}
+
+pub struct MusicQueueRepository;
+
+impl MusicQueueRepository {
+    /// Replace every saved queue with `queues`
+    pub async fn replace_queues(pool: &PgPool, queues: &[SavedQueue]) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM music_saved_queues")
+            .execute(&mut *tx)
+            .await?;
+
+        for queue in queues {
+            sqlx::query!(
+                r#"
+                INSERT INTO music_saved_queues (guild_id, voice_channel_id, text_channel_id, volume, tracks, saved_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+                queue.guild_id,
+                queue.voice_channel_id,
+                queue.text_channel_id,
+                queue.volume,
+                queue.tracks,
+                now,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Remove and return all saved queues, so each is only restored once
+    pub async fn take_queues(pool: &PgPool) -> Result<Vec<SavedQueue>, sqlx::Error> {
+        let queues = sqlx::query_as!(
+            SavedQueue,
+            r#"
+            DELETE FROM music_saved_queues
+            RETURNING guild_id, voice_channel_id, text_channel_id, volume, tracks
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(queues)
+    }
+}
@@ -0,0 +1,98 @@
+use sqlx::{PgConnection, PgPool};
+
+/// Price alert as saved across restarts
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StoredAlert {
+    pub id: i64,
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub channel_id: i64,
+    pub symbol: String,
+    pub condition: String,
+    pub target_price: f64,
+    pub rearm_pips: f64,
+    pub created_at: i64,
+    pub repeat: bool,
+    pub expires_at: Option<i64>,
+    pub last_triggered: Option<i64>,
+    pub window_mins: Option<i64>,
+    pub notify_dm: bool,
+    pub last_side: Option<bool>,
+    pub armed: Option<bool>,
+}
+
+pub struct PriceAlertRepository;
+
+impl PriceAlertRepository {
+    /// Remove and return all saved alerts, so a stale snapshot is never
+    /// restored twice
+    pub async fn take_alerts(pool: &PgPool) -> Result<Vec<StoredAlert>, sqlx::Error> {
+        let mut alerts = sqlx::query_as!(
+            StoredAlert,
+            r#"
+            DELETE FROM price_alerts
+            RETURNING id, guild_id, user_id, channel_id, symbol, condition, target_price,
+                rearm_pips, created_at, repeat, expires_at, last_triggered, window_mins, notify_dm,
+                last_side, armed
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+        alerts.sort_by_key(|alert| alert.id);
+
+        Ok(alerts)
+    }
+
+    /// Replace every saved alert with `alerts`
+    pub async fn replace_alerts(pool: &PgPool, alerts: &[StoredAlert]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM price_alerts")
+            .execute(&mut *tx)
+            .await?;
+
+        for alert in alerts {
+            sqlx::query!(
+                r#"
+                INSERT INTO price_alerts (id, guild_id, user_id, channel_id, symbol, condition,
+                    target_price, rearm_pips, created_at, repeat, expires_at, last_triggered,
+                    window_mins, notify_dm, last_side, armed)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                "#,
+                alert.id,
+                alert.guild_id,
+                alert.user_id,
+                alert.channel_id,
+                alert.symbol,
+                alert.condition,
+                alert.target_price,
+                alert.rearm_pips,
+                alert.created_at,
+                alert.repeat,
+                alert.expires_at,
+                alert.last_triggered,
+                alert.window_mins,
+                alert.notify_dm,
+                alert.last_side,
+                alert.armed,
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Saved alerts of a user, for `/delete_my_data`
+    pub async fn delete_user(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM price_alerts WHERE user_id = $1",
+            user_id as i64
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
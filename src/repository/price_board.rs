@@ -0,0 +1,96 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PriceBoard {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub message_id: i64,
+    /// Comma separated, lowercase
+    pub symbols: String,
+}
+
+impl PriceBoard {
+    pub fn symbol_list(&self) -> Vec<String> {
+        self.symbols
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+pub struct PriceBoardRepository;
+
+impl PriceBoardRepository {
+    pub async fn set_board(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        message_id: u64,
+        symbols: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO price_boards (guild_id, channel_id, message_id, symbols)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id) DO UPDATE SET
+                channel_id = EXCLUDED.channel_id,
+                message_id = EXCLUDED.message_id,
+                symbols = EXCLUDED.symbols
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+            message_id as i64,
+            symbols.join(","),
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_board(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<PriceBoard>, sqlx::Error> {
+        let board = sqlx::query_as!(
+            PriceBoard,
+            "SELECT guild_id, channel_id, message_id, symbols FROM price_boards WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(board)
+    }
+
+    pub async fn get_all_boards(pool: &PgPool) -> Result<Vec<PriceBoard>, sqlx::Error> {
+        let boards = sqlx::query_as!(
+            PriceBoard,
+            "SELECT guild_id, channel_id, message_id, symbols FROM price_boards"
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(boards)
+    }
+
+    /// Delete a guild's board, returning it so the message can be removed
+    pub async fn delete_board(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<PriceBoard>, sqlx::Error> {
+        let board = sqlx::query_as!(
+            PriceBoard,
+            r#"
+            DELETE FROM price_boards WHERE guild_id = $1
+            RETURNING guild_id, channel_id, message_id, symbols
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(board)
+    }
+}
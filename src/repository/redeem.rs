@@ -9,6 +9,9 @@ pub struct RedeemServer {
     pub is_active: bool,
 }
 
+/// A code that has already been announced. `rewards` holds the description
+/// shown to users and `expiry` is the raw expiry text from the source, if any.
+/// `is_expired` is set by the daily cleanup once `expiry` has passed
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct RedeemCode {
     pub id: i64,
@@ -17,6 +20,7 @@ pub struct RedeemCode {
     pub rewards: Option<String>,
     pub expiry: Option<String>,
     pub created_at: i64,
+    pub is_expired: bool,
 }
 
 pub struct RedeemRepository;
@@ -44,6 +48,40 @@ impl RedeemRepository {
         Ok(())
     }
 
+    /// Replace the comma-separated game list for a guild
+    pub async fn set_games(pool: &PgPool, guild_id: u64, games: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE redeem_servers SET games = $2 WHERE guild_id = $1",
+            guild_id as i64,
+            games,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_server(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<RedeemServer>, sqlx::Error> {
+        let server = sqlx::query_as!(
+            RedeemServer,
+            r#"
+            SELECT id, channel_id, guild_id, games, is_active
+            FROM redeem_servers
+            WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(server)
+    }
+
+    /// Active servers subscribed to `game`. `games` is a comma-separated list
+    /// and is matched on exact entries, so `zzz` never matches `zzzother`
     pub async fn get_active_servers(
         pool: &PgPool,
         game: &str,
@@ -53,7 +91,8 @@ impl RedeemRepository {
             r#"
             SELECT id, channel_id, guild_id, games, is_active
             FROM redeem_servers
-            WHERE is_active = TRUE AND games LIKE '%' || $1 || '%'
+            WHERE is_active = TRUE
+              AND $1 = ANY(string_to_array(replace(lower(games), ' ', ''), ','))
             "#,
             game,
         )
@@ -85,23 +124,24 @@ impl RedeemRepository {
         Ok(())
     }
 
+    /// Record a code as announced. Returns false if it was already known
     pub async fn insert_code(
         pool: &PgPool,
         game: &str,
         code: &str,
         rewards: Option<&str>,
         expiry: Option<&str>,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<bool, sqlx::Error> {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs() as i64;
 
-        sqlx::query!(
+        let result = sqlx::query!(
             r#"
-            INSERT INTO redeem_codes (game, code, rewards, expiry, created_at)
-            VALUES ($1, $2, $3, $4, $5)
-            ON CONFLICT(code) DO NOTHING
+            INSERT INTO redeem_codes (game, code, rewards, expiry, created_at, last_seen_at)
+            VALUES ($1, $2, $3, $4, $5, $5)
+            ON CONFLICT(game, code) DO NOTHING
             "#,
             game,
             code,
@@ -112,12 +152,13 @@ impl RedeemRepository {
         .execute(pool)
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn is_code_sent(pool: &PgPool, code: &str) -> Result<bool, sqlx::Error> {
+    pub async fn is_code_sent(pool: &PgPool, game: &str, code: &str) -> Result<bool, sqlx::Error> {
         let count = sqlx::query_scalar!(
-            r#"SELECT COUNT(*) as "count!" FROM redeem_codes WHERE code = $1"#,
+            r#"SELECT COUNT(*) as "count!" FROM redeem_codes WHERE game = $1 AND code = $2"#,
+            game,
             code,
         )
         .fetch_one(pool)
@@ -133,7 +174,7 @@ impl RedeemRepository {
         let codes = sqlx::query_as!(
             RedeemCode,
             r#"
-            SELECT id, game, code, rewards, expiry, created_at
+            SELECT id, game, code, rewards, expiry, created_at, is_expired
             FROM redeem_codes
             WHERE game = $1
             ORDER BY created_at DESC
@@ -147,6 +188,55 @@ impl RedeemRepository {
         Ok(codes)
     }
 
+    /// Codes with an explicit expiry that haven't been marked expired yet
+    pub async fn get_codes_with_expiry(pool: &PgPool) -> Result<Vec<RedeemCode>, sqlx::Error> {
+        let codes = sqlx::query_as!(
+            RedeemCode,
+            r#"
+            SELECT id, game, code, rewards, expiry, created_at, is_expired
+            FROM redeem_codes
+            WHERE expiry IS NOT NULL AND is_expired = FALSE
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(codes)
+    }
+
+    pub async fn mark_expired(pool: &PgPool, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE redeem_codes SET is_expired = TRUE WHERE id = $1",
+            id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record that the source still lists these codes
+    pub async fn mark_seen(pool: &PgPool, game: &str, codes: &[String]) -> Result<(), sqlx::Error> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        sqlx::query!(
+            "UPDATE redeem_codes SET last_seen_at = $3 WHERE game = $1 AND code = ANY($2)",
+            game,
+            codes,
+            now,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete codes older than `days_old` that are expired or that the source
+    /// hasn't listed for that long. Codes still being listed are kept, since
+    /// a missing row makes the checker announce the code again
     pub async fn delete_expired_codes(pool: &PgPool, days_old: i64) -> Result<u64, sqlx::Error> {
         let cutoff = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -154,10 +244,218 @@ impl RedeemRepository {
             .as_secs() as i64
             - (days_old * 24 * 60 * 60);
 
-        let result = sqlx::query!("DELETE FROM redeem_codes WHERE created_at < $1", cutoff,)
-            .execute(pool)
-            .await?;
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM redeem_codes
+            WHERE created_at < $1 AND (is_expired OR last_seen_at < $1)
+            "#,
+            cutoff,
+        )
+        .execute(pool)
+        .await?;
 
         Ok(result.rows_affected())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_insert_and_read_code(pool: PgPool) {
+        RedeemRepository::insert_code(
+            &pool,
+            "genshin",
+            "GENSHINGIFT",
+            Some("50 Primogems"),
+            Some("2026-12-31"),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            RedeemRepository::is_code_sent(&pool, "genshin", "GENSHINGIFT")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !RedeemRepository::is_code_sent(&pool, "genshin", "UNKNOWN")
+                .await
+                .unwrap()
+        );
+
+        let codes = RedeemRepository::get_codes_by_game(&pool, "genshin")
+            .await
+            .unwrap();
+        assert_eq!(codes.len(), 1);
+        assert_eq!(codes[0].game, "genshin");
+        assert_eq!(codes[0].code, "GENSHINGIFT");
+        assert_eq!(codes[0].rewards.as_deref(), Some("50 Primogems"));
+        assert_eq!(codes[0].expiry.as_deref(), Some("2026-12-31"));
+
+        let other = RedeemRepository::get_codes_by_game(&pool, "hsr")
+            .await
+            .unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_insert_code_is_idempotent(pool: PgPool) {
+        let first = RedeemRepository::insert_code(&pool, "genshin", "GENSHINGIFT", None, None)
+            .await
+            .unwrap();
+        let second = RedeemRepository::insert_code(&pool, "genshin", "GENSHINGIFT", None, None)
+            .await
+            .unwrap();
+        assert!(first);
+        assert!(!second);
+
+        let codes = RedeemRepository::get_codes_by_game(&pool, "genshin")
+            .await
+            .unwrap();
+        assert_eq!(codes.len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_same_code_is_tracked_per_game(pool: PgPool) {
+        let genshin = RedeemRepository::insert_code(&pool, "genshin", "SHAREDCODE", None, None)
+            .await
+            .unwrap();
+        let hsr = RedeemRepository::insert_code(&pool, "hsr", "SHAREDCODE", None, None)
+            .await
+            .unwrap();
+        assert!(genshin);
+        assert!(hsr);
+
+        assert!(
+            RedeemRepository::is_code_sent(&pool, "hsr", "SHAREDCODE")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !RedeemRepository::is_code_sent(&pool, "zzz", "SHAREDCODE")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete_expired_codes_keeps_recent(pool: PgPool) {
+        RedeemRepository::insert_code(&pool, "genshin", "OLDCODE", None, None)
+            .await
+            .unwrap();
+        RedeemRepository::insert_code(&pool, "genshin", "NEWCODE", None, None)
+            .await
+            .unwrap();
+        sqlx::query(
+            r#"
+            UPDATE redeem_codes
+            SET created_at = created_at - $1, last_seen_at = last_seen_at - $1
+            WHERE code = $2
+            "#,
+        )
+        .bind(31 * 24 * 60 * 60i64)
+        .bind("OLDCODE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let deleted = RedeemRepository::delete_expired_codes(&pool, 30)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(
+            !RedeemRepository::is_code_sent(&pool, "genshin", "OLDCODE")
+                .await
+                .unwrap()
+        );
+        assert!(
+            RedeemRepository::is_code_sent(&pool, "genshin", "NEWCODE")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_delete_expired_codes_keeps_listed(pool: PgPool) {
+        for code in ["LISTED", "EXPIRED"] {
+            RedeemRepository::insert_code(&pool, "genshin", code, None, None)
+                .await
+                .unwrap();
+        }
+        sqlx::query("UPDATE redeem_codes SET created_at = created_at - $1")
+            .bind(31 * 24 * 60 * 60i64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE redeem_codes SET is_expired = TRUE WHERE code = 'EXPIRED'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        RedeemRepository::mark_seen(&pool, "genshin", &["LISTED".to_string()])
+            .await
+            .unwrap();
+
+        let deleted = RedeemRepository::delete_expired_codes(&pool, 30)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert!(
+            RedeemRepository::is_code_sent(&pool, "genshin", "LISTED")
+                .await
+                .unwrap()
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_active_servers_match_exact_game(pool: PgPool) {
+        RedeemRepository::insert_server(&pool, 1, 10, "genshin")
+            .await
+            .unwrap();
+        RedeemRepository::insert_server(&pool, 2, 20, "zzzother")
+            .await
+            .unwrap();
+
+        let genshin = RedeemRepository::get_active_servers(&pool, "genshin")
+            .await
+            .unwrap();
+        assert_eq!(genshin.len(), 1);
+        assert_eq!(genshin[0].guild_id, 1);
+
+        let hsr = RedeemRepository::get_active_servers(&pool, "hsr")
+            .await
+            .unwrap();
+        assert!(hsr.is_empty());
+
+        let zzz = RedeemRepository::get_active_servers(&pool, "zzz")
+            .await
+            .unwrap();
+        assert!(zzz.is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_active_servers_match_game_list(pool: PgPool) {
+        RedeemRepository::insert_server(&pool, 1, 10, "genshin, hsr")
+            .await
+            .unwrap();
+
+        for game in ["genshin", "hsr"] {
+            let servers = RedeemRepository::get_active_servers(&pool, game)
+                .await
+                .unwrap();
+            assert_eq!(servers.len(), 1, "{} should match", game);
+        }
+
+        let zzz = RedeemRepository::get_active_servers(&pool, "zzz")
+            .await
+            .unwrap();
+        assert!(zzz.is_empty());
+
+        RedeemRepository::disable_server(&pool, 1).await.unwrap();
+        let genshin = RedeemRepository::get_active_servers(&pool, "genshin")
+            .await
+            .unwrap();
+        assert!(genshin.is_empty());
+    }
+}
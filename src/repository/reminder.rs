@@ -1,4 +1,4 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct Reminder {
@@ -117,6 +117,18 @@ impl ReminderRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Delete every reminder belonging to a user
+    pub async fn delete_user_reminders(
+        conn: &mut PgConnection,
+        user_id: u64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM reminders WHERE user_id = $1", user_id as i64)
+            .execute(conn)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
     pub async fn cleanup_sent_reminders(pool: &PgPool, days_old: i64) -> Result<u64, sqlx::Error> {
         let cutoff = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -0,0 +1,161 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StarboardConfig {
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub threshold: i32,
+    pub emoji: String,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StarMessage {
+    pub guild_id: i64,
+    pub source_message_id: i64,
+    pub star_message_id: i64,
+    pub star_count: i32,
+}
+
+pub struct StarboardRepository;
+
+impl StarboardRepository {
+    pub async fn set_config(
+        pool: &PgPool,
+        guild_id: u64,
+        channel_id: u64,
+        threshold: i32,
+        emoji: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO starboard (guild_id, channel_id, threshold, emoji)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id) DO UPDATE SET
+                channel_id = EXCLUDED.channel_id,
+                threshold = EXCLUDED.threshold,
+                emoji = EXCLUDED.emoji
+            "#,
+            guild_id as i64,
+            channel_id as i64,
+            threshold,
+            emoji,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_config(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<StarboardConfig>, sqlx::Error> {
+        let config = sqlx::query_as!(
+            StarboardConfig,
+            "SELECT guild_id, channel_id, threshold, emoji FROM starboard WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(config)
+    }
+
+    /// Returns false if the guild had no starboard
+    pub async fn delete_config(pool: &PgPool, guild_id: u64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM starboard WHERE guild_id = $1", guild_id as i64)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_star_message(
+        pool: &PgPool,
+        guild_id: u64,
+        source_message_id: u64,
+    ) -> Result<Option<StarMessage>, sqlx::Error> {
+        let message = sqlx::query_as!(
+            StarMessage,
+            r#"
+            SELECT guild_id, source_message_id, star_message_id, star_count
+            FROM star_messages
+            WHERE guild_id = $1 AND source_message_id = $2
+            "#,
+            guild_id as i64,
+            source_message_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(message)
+    }
+
+    pub async fn set_star_message(
+        pool: &PgPool,
+        guild_id: u64,
+        source_message_id: u64,
+        star_message_id: u64,
+        star_count: i32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO star_messages (guild_id, source_message_id, star_message_id, star_count)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id, source_message_id) DO UPDATE SET
+                star_message_id = EXCLUDED.star_message_id,
+                star_count = EXCLUDED.star_count
+            "#,
+            guild_id as i64,
+            source_message_id as i64,
+            star_message_id as i64,
+            star_count,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Insert an entry only if the message has none yet. Returns false when
+    /// another entry already exists, so only one caller gets to post it
+    pub async fn claim_star_message(
+        pool: &PgPool,
+        guild_id: u64,
+        source_message_id: u64,
+        star_message_id: u64,
+        star_count: i32,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO star_messages (guild_id, source_message_id, star_message_id, star_count)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT(guild_id, source_message_id) DO NOTHING
+            "#,
+            guild_id as i64,
+            source_message_id as i64,
+            star_message_id as i64,
+            star_count,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_star_message(
+        pool: &PgPool,
+        guild_id: u64,
+        source_message_id: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM star_messages WHERE guild_id = $1 AND source_message_id = $2",
+            guild_id as i64,
+            source_message_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Tag {
+    pub id: i64,
+    pub guild_id: i64,
+    pub name: String,
+    pub content: String,
+    pub auto_trigger: bool,
+    pub created_by: i64,
+    pub created_at: i64,
+}
+
+pub struct TagRepository;
+
+impl TagRepository {
+    /// Create or overwrite a tag. Returns true if a new tag was created
+    pub async fn upsert_tag(
+        pool: &PgPool,
+        guild_id: u64,
+        name: &str,
+        content: &str,
+        auto_trigger: bool,
+        created_by: u64,
+    ) -> Result<bool, sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        let inserted = sqlx::query_scalar!(
+            r#"
+            INSERT INTO tags (guild_id, name, content, auto_trigger, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT(guild_id, name) DO UPDATE
+            SET content = EXCLUDED.content, auto_trigger = EXCLUDED.auto_trigger
+            RETURNING (xmax = 0) as "inserted!"
+            "#,
+            guild_id as i64,
+            name,
+            content,
+            auto_trigger,
+            created_by as i64,
+            now,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(inserted)
+    }
+
+    pub async fn get_tag(
+        pool: &PgPool,
+        guild_id: u64,
+        name: &str,
+    ) -> Result<Option<Tag>, sqlx::Error> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT id, guild_id, name, content, auto_trigger, created_by, created_at
+            FROM tags
+            WHERE guild_id = $1 AND name = $2
+            "#,
+            guild_id as i64,
+            name,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    /// Get a tag only if it is allowed to fire as a prefix command
+    pub async fn get_trigger_tag(
+        pool: &PgPool,
+        guild_id: u64,
+        name: &str,
+    ) -> Result<Option<Tag>, sqlx::Error> {
+        let tag = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT id, guild_id, name, content, auto_trigger, created_by, created_at
+            FROM tags
+            WHERE guild_id = $1 AND name = $2 AND auto_trigger = TRUE
+            "#,
+            guild_id as i64,
+            name,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    pub async fn get_tags(pool: &PgPool, guild_id: u64) -> Result<Vec<Tag>, sqlx::Error> {
+        let tags = sqlx::query_as!(
+            Tag,
+            r#"
+            SELECT id, guild_id, name, content, auto_trigger, created_by, created_at
+            FROM tags
+            WHERE guild_id = $1
+            ORDER BY name
+            "#,
+            guild_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    pub async fn delete_tag(pool: &PgPool, guild_id: u64, name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM tags WHERE guild_id = $1 AND name = $2",
+            guild_id as i64,
+            name,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Change a tag's content and optionally its auto-trigger flag.
+    /// Returns false if the tag does not exist
+    pub async fn update_tag(
+        pool: &PgPool,
+        guild_id: u64,
+        name: &str,
+        content: &str,
+        auto_trigger: Option<bool>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE tags
+            SET content = $3, auto_trigger = COALESCE($4, auto_trigger)
+            WHERE guild_id = $1 AND name = $2
+            "#,
+            guild_id as i64,
+            name,
+            content,
+            auto_trigger,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn count_tags(pool: &PgPool, guild_id: u64) -> Result<i64, sqlx::Error> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM tags WHERE guild_id = $1"#,
+            guild_id as i64,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(count)
+    }
+}
@@ -0,0 +1,67 @@
+use sqlx::{PgConnection, PgPool};
+
+pub struct WatchlistRepository;
+
+impl WatchlistRepository {
+    /// Add a symbol to a user's watchlist. Returns false if it was already there
+    pub async fn add_symbol(
+        pool: &PgPool,
+        user_id: u64,
+        symbol: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let now = chrono::Utc::now().timestamp();
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO user_watchlists (user_id, symbol, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(user_id, symbol) DO NOTHING
+            "#,
+            user_id as i64,
+            symbol,
+            now,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove_symbol(
+        pool: &PgPool,
+        user_id: u64,
+        symbol: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_watchlists WHERE user_id = $1 AND symbol = $2",
+            user_id as i64,
+            symbol,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn clear(conn: &mut PgConnection, user_id: u64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM user_watchlists WHERE user_id = $1",
+            user_id as i64,
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Symbols on a user's watchlist, sorted alphabetically
+    pub async fn get_symbols(pool: &PgPool, user_id: u64) -> Result<Vec<String>, sqlx::Error> {
+        let symbols = sqlx::query_scalar!(
+            "SELECT symbol FROM user_watchlists WHERE user_id = $1 ORDER BY symbol",
+            user_id as i64,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(symbols)
+    }
+}
@@ -0,0 +1,112 @@
+use sqlx::PgPool;
+
+/// Custom welcome/goodbye settings for a guild
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WelcomeConfig {
+    pub guild_id: i64,
+    pub welcome_message: Option<String>,
+    pub leave_message: Option<String>,
+    pub color: Option<i32>,
+    pub show_avatar: bool,
+}
+
+pub struct WelcomeRepository;
+
+impl WelcomeRepository {
+    pub async fn get_config(
+        pool: &PgPool,
+        guild_id: u64,
+    ) -> Result<Option<WelcomeConfig>, sqlx::Error> {
+        let config = sqlx::query_as!(
+            WelcomeConfig,
+            r#"
+            SELECT guild_id, welcome_message, leave_message, color, show_avatar
+            FROM welcome_config
+            WHERE guild_id = $1
+            "#,
+            guild_id as i64,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(config)
+    }
+
+    /// Set the welcome template (None restores the default message)
+    pub async fn set_welcome_message(
+        pool: &PgPool,
+        guild_id: u64,
+        message: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO welcome_config (guild_id, welcome_message)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET welcome_message = EXCLUDED.welcome_message
+            "#,
+            guild_id as i64,
+            message,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set the goodbye template (None restores the default message)
+    pub async fn set_leave_message(
+        pool: &PgPool,
+        guild_id: u64,
+        message: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO welcome_config (guild_id, leave_message)
+            VALUES ($1, $2)
+            ON CONFLICT(guild_id) DO UPDATE SET leave_message = EXCLUDED.leave_message
+            "#,
+            guild_id as i64,
+            message,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set embed color and avatar visibility
+    pub async fn set_style(
+        pool: &PgPool,
+        guild_id: u64,
+        color: Option<u32>,
+        show_avatar: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO welcome_config (guild_id, color, show_avatar)
+            VALUES ($1, $2, $3)
+            ON CONFLICT(guild_id) DO UPDATE
+            SET color = EXCLUDED.color, show_avatar = EXCLUDED.show_avatar
+            "#,
+            guild_id as i64,
+            color.map(|c| c as i32),
+            show_avatar,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove all customization for a guild
+    pub async fn reset(pool: &PgPool, guild_id: u64) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM welcome_config WHERE guild_id = $1",
+            guild_id as i64,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use super::{CodeData, CodeSource};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+const BASE_URL: &str = "https://api.ennead.cc/mihoyo";
+
+#[derive(Debug, Deserialize)]
+struct ApiResponse {
+    active: Vec<CodeInfo>,
+    #[allow(dead_code)]
+    #[serde(default)]
+    inactive: Vec<CodeInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeInfo {
+    code: String,
+    #[serde(default)]
+    rewards: Vec<String>,
+    #[serde(
+        default,
+        alias = "expires",
+        alias = "expires_at",
+        alias = "expiry_date"
+    )]
+    expiry: Option<String>,
+    #[serde(default, alias = "server")]
+    region: Option<String>,
+}
+
+/// Normalize the region names used by community APIs to the HI3 server names
+fn normalize_region(region: &str) -> Option<String> {
+    let region = region.trim().to_lowercase();
+    let name = match region.as_str() {
+        "" | "all" | "global" => return None,
+        "na" | "america" | "americas" | "north america" => "NA",
+        "eu" | "europe" => "EU",
+        "asia" | "sea" | "southeast asia" => "Asia",
+        "tw" | "tw/hk/mo" | "taiwan" | "cht" => "TW",
+        _ => return Some(region.to_uppercase()),
+    };
+    Some(name.to_string())
+}
+
+/// Codes for a HoYoverse game from api.ennead.cc. Every game shares one
+/// response format and only the path differs
+pub struct EnneadCodeScraper {
+    game: &'static str,
+    name: &'static str,
+    api_url: String,
+    fallback_env: Option<&'static str>,
+    client: reqwest::Client,
+}
+
+impl EnneadCodeScraper {
+    fn new(
+        game: &'static str,
+        name: &'static str,
+        path: &str,
+        fallback_env: Option<&'static str>,
+    ) -> Self {
+        Self {
+            game,
+            name,
+            api_url: format!("{}/{}/codes", BASE_URL, path),
+            fallback_env,
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn genshin() -> Self {
+        Self::new(
+            "genshin",
+            "Genshin",
+            "genshin",
+            Some("GENSHIN_FALLBACK_API"),
+        )
+    }
+
+    pub fn hsr() -> Self {
+        Self::new("hsr", "Honkai: Star Rail", "starrail", None)
+    }
+
+    pub fn zzz() -> Self {
+        Self::new("zzz", "Zenless Zone Zero", "zenless", None)
+    }
+
+    pub fn hi3() -> Self {
+        Self::new("hi3", "Honkai Impact 3rd", "hi3", None)
+    }
+}
+
+#[async_trait]
+impl CodeSource for EnneadCodeScraper {
+    fn game(&self) -> &'static str {
+        self.game
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn set_api_url(&mut self, api_url: String) {
+        self.api_url = api_url;
+    }
+
+    fn fallback_env(&self) -> Option<&'static str> {
+        self.fallback_env
+    }
+
+    async fn fetch_codes(&self) -> Result<Vec<CodeData>, Box<dyn std::error::Error + Send + Sync>> {
+        println!("Fetching {} codes from API: {}", self.name, self.api_url);
+
+        let response = self
+            .client
+            .get(&self.api_url)
+            .header(
+                "User-Agent",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("API request failed with status: {}", response.status()).into());
+        }
+
+        let api_response: ApiResponse = response.json().await?;
+
+        let codes: Vec<CodeData> = api_response
+            .active
+            .into_iter()
+            .map(|code_info| {
+                let rewards = if code_info.rewards.is_empty() {
+                    "Unknown rewards".to_string()
+                } else {
+                    code_info.rewards.join(", ")
+                };
+
+                CodeData {
+                    code: code_info.code,
+                    rewards,
+                    status: "Active".to_string(),
+                    expiry: code_info.expiry.filter(|e| !e.trim().is_empty()),
+                    region: code_info.region.as_deref().and_then(normalize_region),
+                }
+            })
+            .collect();
+
+        println!(
+            "Successfully fetched {} active {} codes",
+            codes.len(),
+            self.name
+        );
+
+        Ok(codes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_codes() {
+        let scraper = EnneadCodeScraper::genshin();
+        match scraper.fetch_codes().await {
+            Ok(codes) => {
+                println!("Fetched {} codes", codes.len());
+                for code in codes.iter().take(3) {
+                    println!("Code: {} - Rewards: {}", code.code, code.rewards);
+                }
+                assert!(!codes.is_empty(), "Should fetch at least one code");
+            }
+            Err(e) => {
+                eprintln!("Failed to fetch codes: {}", e);
+            }
+        }
+    }
+}
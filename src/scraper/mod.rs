@@ -1,2 +1,39 @@
-pub mod genshin;
+pub mod ennead;
 pub mod wuwa;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// An active code as returned by a code source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeData {
+    pub code: String,
+    pub rewards: String,
+    pub status: String,
+    /// Expiry date as given by the API, if any
+    pub expiry: Option<String>,
+    /// Server region the code is limited to (HI3 uses NA/EU/Asia/TW), when known
+    pub region: Option<String>,
+}
+
+/// A game's code API, polled by the code checker
+#[async_trait]
+pub trait CodeSource: Send + Sync {
+    /// Key stored in `redeem_codes.game` and matched against `redeem_servers.games`
+    fn game(&self) -> &'static str;
+
+    /// Name shown in logs, owner alerts and `/scraper_status`
+    fn name(&self) -> &'static str;
+
+    fn api_url(&self) -> &str;
+
+    /// Point the source at a different API with the same response format
+    fn set_api_url(&mut self, api_url: String);
+
+    /// Env var holding a fallback API URL to switch to after repeated failures
+    fn fallback_env(&self) -> Option<&'static str> {
+        None
+    }
+
+    async fn fetch_codes(&self) -> Result<Vec<CodeData>, Box<dyn std::error::Error + Send + Sync>>;
+}
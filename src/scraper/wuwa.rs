@@ -1,2 +1,168 @@
+use super::{CodeData, CodeSource};
+use async_trait::async_trait;
+use serde::Deserialize;
 
+/// Env var with the community codes API. There is no official one, so the
+/// source is only polled when this is set
+pub const API_ENV: &str = "WUWA_CODES_API";
 
+/// Community APIs differ in shape: a bare list, or an object holding the list
+/// under `active`, `codes` or `data`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ApiResponse {
+    List(Vec<CodeInfo>),
+    Object {
+        #[serde(alias = "codes", alias = "data")]
+        active: Vec<CodeInfo>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeInfo {
+    code: String,
+    #[serde(default, alias = "reward")]
+    rewards: Option<Rewards>,
+    #[serde(
+        default,
+        alias = "expires",
+        alias = "expires_at",
+        alias = "expiry_date"
+    )]
+    expiry: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Rewards {
+    List(Vec<String>),
+    Text(String),
+}
+
+fn parse_codes(body: &str) -> Result<Vec<CodeData>, serde_json::Error> {
+    let codes = match serde_json::from_str(body)? {
+        ApiResponse::List(codes) | ApiResponse::Object { active: codes } => codes,
+    };
+
+    Ok(codes
+        .into_iter()
+        .filter(|code_info| !code_info.code.trim().is_empty())
+        .map(|code_info| {
+            let rewards = match code_info.rewards {
+                Some(Rewards::List(rewards)) if !rewards.is_empty() => rewards.join(", "),
+                Some(Rewards::Text(rewards)) if !rewards.trim().is_empty() => rewards,
+                _ => "Unknown rewards".to_string(),
+            };
+
+            CodeData {
+                code: code_info.code.trim().to_string(),
+                rewards,
+                status: "Active".to_string(),
+                expiry: code_info.expiry.filter(|e| !e.trim().is_empty()),
+                region: None,
+            }
+        })
+        .collect())
+}
+
+pub struct WuwaCodeScraper {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl WuwaCodeScraper {
+    pub fn new(api_url: String) -> Self {
+        Self {
+            api_url,
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `None` when `WUWA_CODES_API` is unset or empty
+    pub fn from_env() -> Option<Self> {
+        std::env::var(API_ENV)
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .map(Self::new)
+    }
+}
+
+#[async_trait]
+impl CodeSource for WuwaCodeScraper {
+    fn game(&self) -> &'static str {
+        "wuwa"
+    }
+
+    fn name(&self) -> &'static str {
+        "Wuthering Waves"
+    }
+
+    fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    fn set_api_url(&mut self, api_url: String) {
+        self.api_url = api_url;
+    }
+
+    async fn fetch_codes(&self) -> Result<Vec<CodeData>, Box<dyn std::error::Error + Send + Sync>> {
+        println!("Fetching WuWa codes from API: {}", self.api_url);
+
+        let response = self
+            .client
+            .get(&self.api_url)
+            .header(
+                "User-Agent",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("API request failed with status: {}", response.status()).into());
+        }
+
+        let codes = parse_codes(&response.text().await?)?;
+
+        println!("Successfully fetched {} active WuWa codes", codes.len());
+
+        Ok(codes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list_with_missing_rewards() {
+        let codes = parse_codes(
+            r#"[
+                {"code": "WUTHERINGGIFT", "rewards": ["Astrite x50", "Shell Credit x10000"]},
+                {"code": "NOREWARDS"},
+                {"code": "TEXTREWARD", "reward": "Astrite x100", "expires": "2026-12-31"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(codes.len(), 3);
+        assert_eq!(codes[0].rewards, "Astrite x50, Shell Credit x10000");
+        assert_eq!(codes[1].rewards, "Unknown rewards");
+        assert_eq!(codes[2].rewards, "Astrite x100");
+        assert_eq!(codes[2].expiry.as_deref(), Some("2026-12-31"));
+    }
+
+    #[test]
+    fn test_parse_wrapped_list() {
+        let codes =
+            parse_codes(r#"{"codes": [{"code": "WUWA2026", "rewards": []}, {"code": " "}]}"#)
+                .unwrap();
+
+        assert_eq!(codes.len(), 1);
+        assert_eq!(codes[0].code, "WUWA2026");
+        assert_eq!(codes[0].rewards, "Unknown rewards");
+    }
+}
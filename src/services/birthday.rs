@@ -0,0 +1,111 @@
+use crate::repository::{BirthdayRepository, DbPool};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::{Datelike, NaiveDate, Utc};
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateEmbed, CreateMessage, Http, UserId};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "Birthdays";
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Whether `month`/`day` exists in some year, so Feb 29 is allowed
+pub fn is_valid_date(month: u32, day: u32) -> bool {
+    // 2000 was a leap year
+    NaiveDate::from_ymd_opt(2000, month, day).is_some()
+}
+
+/// Date the birthday is celebrated in `year`; Feb 29 falls back to Feb 28
+/// outside leap years
+fn celebration_in(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day).or_else(|| {
+        (month == 2 && day == 29)
+            .then(|| NaiveDate::from_ymd_opt(year, 2, 28))
+            .flatten()
+    })
+}
+
+/// Next celebration on or after `from`
+pub fn next_celebration(month: u32, day: u32, from: NaiveDate) -> Option<NaiveDate> {
+    celebration_in(from.year(), month, day)
+        .filter(|date| *date >= from)
+        .or_else(|| celebration_in(from.year() + 1, month, day))
+}
+
+/// Stored month/day pairs celebrated on `date`
+fn birthdays_on(date: NaiveDate) -> Vec<(i32, i32)> {
+    let mut dates = vec![(date.month() as i32, date.day() as i32)];
+    if date.month() == 2 && date.day() == 28 && !date.leap_year() {
+        dates.push((2, 29));
+    }
+    dates
+}
+
+/// Post today's birthdays once per UTC day in each configured channel
+async fn check(pool: &PgPool, http: &Http) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let now = Utc::now().date_naive();
+    let today = now.format("%Y-%m-%d").to_string();
+    let dates = birthdays_on(now);
+
+    for config in BirthdayRepository::get_all_configs(pool).await? {
+        if config.last_sent_date.as_deref() == Some(today.as_str()) {
+            continue;
+        }
+
+        let guild_id = config.guild_id as u64;
+        // Mark first so a failing channel is not retried every minute
+        BirthdayRepository::mark_sent(pool, guild_id, &today).await?;
+
+        let birthdays = BirthdayRepository::get_birthdays_on(pool, guild_id, &dates).await?;
+        if birthdays.is_empty() {
+            continue;
+        }
+
+        let users: Vec<UserId> = birthdays
+            .iter()
+            .map(|b| UserId::new(b.user_id as u64))
+            .collect();
+        let mentions: Vec<String> = users.iter().map(|u| format!("<@{}>", u)).collect();
+        let embed = CreateEmbed::new()
+            .title("🎂 Happy Birthday!")
+            .description(format!(
+                "Wishing a wonderful day to {} 🎉",
+                mentions.join(", ")
+            ))
+            .color(0xFF69B4)
+            .timestamp(serenity::all::Timestamp::now());
+        let message = CreateMessage::new()
+            .content(mentions.join(" "))
+            .embed(embed)
+            .allowed_mentions(CreateAllowedMentions::new().users(users));
+
+        if let Err(e) = ChannelId::new(config.channel_id as u64)
+            .send_message(http, message)
+            .await
+        {
+            eprintln!("[BIRTHDAY] Failed to send to {}: {}", config.channel_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn start_birthdays(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    tokio::spawn(async move {
+        let mut check_interval = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+        println!("[BIRTHDAY] Starting birthday announcements...");
+
+        loop {
+            check_interval.tick().await;
+            match check(db.as_ref(), &http).await {
+                Ok(_) => health::record_success(&health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&health, SERVICE_NAME);
+                    eprintln!("[BIRTHDAY] Error checking birthdays: {}", e);
+                }
+            }
+        }
+    })
+}
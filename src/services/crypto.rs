@@ -0,0 +1,265 @@
+use crate::repository::{CryptoAlertRepository, DbPool, StoredAlert};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::Utc;
+use reqwest::Client;
+use serde::Deserialize;
+use serenity::all::{ChannelId, CreateAllowedMentions, CreateEmbed, CreateMessage, Http, UserId};
+use sqlx::PgPool;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "Crypto Alerts";
+const POLL_INTERVAL_SECS: u64 = 60;
+const PRICE_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+/// Common tickers and their CoinGecko ids. Anything else is passed through as an id
+const TICKERS: &[(&str, &str)] = &[
+    ("btc", "bitcoin"),
+    ("eth", "ethereum"),
+    ("sol", "solana"),
+    ("bnb", "binancecoin"),
+    ("xrp", "ripple"),
+    ("ada", "cardano"),
+    ("doge", "dogecoin"),
+    ("ton", "the-open-network"),
+    ("trx", "tron"),
+    ("dot", "polkadot"),
+    ("avax", "avalanche-2"),
+    ("link", "chainlink"),
+    ("matic", "matic-network"),
+    ("ltc", "litecoin"),
+    ("usdt", "tether"),
+    ("usdc", "usd-coin"),
+];
+
+/// Map a ticker like `BTC` to its CoinGecko id, e.g. `bitcoin`
+pub fn coin_id(input: &str) -> String {
+    let input = input.trim().to_lowercase();
+    TICKERS
+        .iter()
+        .find(|(ticker, _)| *ticker == input)
+        .map(|(_, id)| id.to_string())
+        .unwrap_or(input)
+}
+
+/// Ticker for display, falling back to the CoinGecko id
+pub fn coin_label(id: &str) -> String {
+    TICKERS
+        .iter()
+        .find(|(_, coin)| *coin == id)
+        .map(|(ticker, _)| ticker.to_uppercase())
+        .unwrap_or_else(|| id.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum CryptoCondition {
+    #[name = "above"]
+    Above,
+    #[name = "below"]
+    Below,
+}
+
+impl CryptoCondition {
+    pub fn name(self) -> &'static str {
+        match self {
+            CryptoCondition::Above => "above",
+            CryptoCondition::Below => "below",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct CoinPrice {
+    pub usd: f64,
+    /// Missing for coins CoinGecko has no IDR rate for
+    #[serde(default)]
+    pub idr: Option<f64>,
+    #[serde(default)]
+    pub usd_24h_change: Option<f64>,
+    #[serde(default)]
+    pub usd_market_cap: Option<f64>,
+}
+
+/// Current prices keyed by CoinGecko id. Unknown ids are missing from the map
+pub async fn fetch_prices(
+    client: &Client,
+    ids: &[String],
+) -> Result<HashMap<String, CoinPrice>, Box<dyn std::error::Error + Send + Sync>> {
+    let ids = ids.join(",");
+    let prices = client
+        .get(PRICE_URL)
+        .query(&[
+            ("ids", ids.as_str()),
+            ("vs_currencies", "usd,idr"),
+            ("include_24hr_change", "true"),
+            ("include_market_cap", "true"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(prices)
+}
+
+pub fn client() -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .unwrap_or_default()
+}
+
+fn group_thousands(value: u64, separator: char) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Cents for whole-dollar coins, more decimals for cheap ones
+pub fn format_usd(price: f64) -> String {
+    if price >= 1.0 {
+        let cents = (price * 100.0).round() as u64;
+        format!("${}.{:02}", group_thousands(cents / 100, ','), cents % 100)
+    } else {
+        format!("${:.6}", price)
+    }
+}
+
+pub fn format_idr(price: f64) -> String {
+    if price >= 1.0 {
+        format!("Rp {}", group_thousands(price.round() as u64, '.'))
+    } else {
+        format!("Rp {:.4}", price)
+    }
+}
+
+pub fn format_market_cap(cap: f64) -> String {
+    match cap {
+        c if c >= 1e12 => format!("${:.2}T", c / 1e12),
+        c if c >= 1e9 => format!("${:.2}B", c / 1e9),
+        c if c >= 1e6 => format!("${:.2}M", c / 1e6),
+        c => format_usd(c),
+    }
+}
+
+fn fires(alert: &StoredAlert, usd: f64) -> bool {
+    match alert.condition.as_str() {
+        "above" => usd >= alert.target_price,
+        "below" => usd <= alert.target_price,
+        _ => false,
+    }
+}
+
+async fn notify(http: &Http, alert: &StoredAlert, price: &CoinPrice) {
+    let coin = coin_label(&alert.symbol);
+    let mut details = format!(
+        "Target: {}\nCurrent: {}",
+        format_usd(alert.target_price),
+        format_usd(price.usd)
+    );
+    if let Some(idr) = price.idr {
+        details.push_str(&format!(" ({})", format_idr(idr)));
+    }
+    if let Some(change) = price.usd_24h_change {
+        details.push_str(&format!("\n24h: {:+.2}%", change));
+    }
+
+    let embed = CreateEmbed::new()
+        .title("Crypto Alert Triggered!")
+        .description(format!(
+            "**{}** is now {} **{}**\n\n{}",
+            coin,
+            alert.condition,
+            format_usd(alert.target_price),
+            details
+        ))
+        .color(0x00ff00)
+        .timestamp(serenity::all::Timestamp::now());
+
+    let user_id = UserId::new(alert.user_id as u64);
+    let message = CreateMessage::new()
+        .content(format!("<@{}>", user_id))
+        .embed(embed)
+        .allowed_mentions(CreateAllowedMentions::new().users(vec![user_id]));
+
+    if let Err(e) = ChannelId::new(alert.channel_id as u64)
+        .send_message(http, message)
+        .await
+    {
+        eprintln!(
+            "[CRYPTO] Failed to send alert #{} to {}: {}",
+            alert.id, alert.channel_id, e
+        );
+    }
+}
+
+/// Fetch prices for every coin with an alert and fire the ones that crossed
+async fn check(
+    pool: &PgPool,
+    http: &Http,
+    client: &Client,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let now = Utc::now().timestamp();
+    let mut alerts = Vec::new();
+    for alert in CryptoAlertRepository::get_alerts(pool).await? {
+        if alert.expires_at.is_some_and(|expires| expires <= now) {
+            CryptoAlertRepository::delete_alert(pool, alert.id).await?;
+        } else {
+            alerts.push(alert);
+        }
+    }
+    if alerts.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<String> = alerts
+        .iter()
+        .map(|a| a.symbol.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let prices = fetch_prices(client, &ids).await?;
+
+    for alert in &alerts {
+        let Some(price) = prices.get(&alert.symbol) else {
+            continue;
+        };
+        if !fires(alert, price.usd) {
+            continue;
+        }
+        // Remove first so a failing channel is not retried every minute
+        CryptoAlertRepository::delete_alert(pool, alert.id).await?;
+        notify(http, alert, price).await;
+    }
+
+    Ok(())
+}
+
+pub fn start_crypto_alerts(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    tokio::spawn(async move {
+        let client = client();
+        let mut poll_interval = interval(Duration::from_secs(POLL_INTERVAL_SECS));
+        println!("[CRYPTO] Starting crypto alert polling...");
+
+        loop {
+            poll_interval.tick().await;
+            match check(db.as_ref(), &http, &client).await {
+                Ok(_) => health::record_success(&health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&health, SERVICE_NAME);
+                    eprintln!("[CRYPTO] Error checking alerts: {}", e);
+                }
+            }
+        }
+    })
+}
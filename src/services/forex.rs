@@ -1,11 +1,20 @@
 use crate::config::Config;
-use crate::repository::{DbPool, ForexRepository};
-use chrono::{DateTime, Utc};
+use crate::repository::{DbPool, ForexChannel, ForexFilter, ForexRepository};
+use crate::services::gemini::{GeminiModel, GeminiService};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc, Weekday};
 use chrono_tz::Asia::Jakarta;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serenity::all::{ChannelId, Color, CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
-use std::sync::Arc;
+use serenity::all::{
+    AutoArchiveDuration, Channel, ChannelId, ChannelType, Color, CreateAllowedMentions,
+    CreateEmbed, CreateEmbedFooter, CreateMessage, CreateThread, EditThread, Http, RoleId,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::{Duration, interval};
 
 const FXSTREET_RSS: &str = "https://www.fxstreet-id.com/rss/news";
@@ -13,6 +22,17 @@ const FXSTREET_ANALYSIS_RSS: &str = "https://www.fxstreet-id.com/rss/analysis";
 const DAILY_FOREX: &str = "https://www.dailyforex.com/rss/technicalanalysis.xml";
 const WSJ_WORLD_NEWS_RSS: &str = "https://feeds.content.dowjones.io/public/rss/RSSWorldNews";
 const WSJ_MARKETS_RSS: &str = "https://feeds.content.dowjones.io/public/rss/RSSMarketsMain";
+const SENTIMENT_CACHE_LIMIT: usize = 500;
+/// Discord's limit for an embed field value
+const SENTIMENT_MAX_CHARS: usize = 1024;
+const DUPLICATE_WINDOW_HOURS: i64 = 24;
+const DUPLICATE_THRESHOLD: f64 = 0.9;
+const FEED_BACKOFF_BASE_SECS: u64 = 60;
+const FEED_BACKOFF_MAX_SECS: u64 = 30 * 60;
+const AI_IMPACT_TIMEOUT_SECS: u64 = 20;
+const AI_RATIONALE_MAX_CHARS: usize = 120;
+
+pub const SERVICE_NAME: &str = "Forex News";
 
 #[derive(Serialize)]
 struct GeminiRequest {
@@ -60,6 +80,21 @@ pub struct ForexNews {
     pub id: String,
 }
 
+impl ForexNews {
+    /// Source key derived from the news ID prefix (e.g. "wsj_world")
+    pub fn source_key(&self) -> &'static str {
+        [
+            "fxstreet_analysis",
+            "dailyforex",
+            "wsj_world",
+            "wsj_markets",
+        ]
+        .into_iter()
+        .find(|key| self.id.starts_with(key))
+        .unwrap_or("fxstreet")
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Impact {
     High,
@@ -91,6 +126,162 @@ impl Impact {
             Impact::Low => "▰▱▱",
         }
     }
+
+    /// Value stored in `forex_channels.min_impact`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Impact::High => "high",
+            Impact::Medium => "medium",
+            Impact::Low => "low",
+        }
+    }
+
+    /// Parse a stored key, treating anything unknown as Low
+    pub fn from_key(key: &str) -> Self {
+        match key.to_lowercase().as_str() {
+            "high" => Impact::High,
+            "medium" => Impact::Medium,
+            _ => Impact::Low,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Impact::High => 3,
+            Impact::Medium => 2,
+            Impact::Low => 1,
+        }
+    }
+
+    /// Whether this impact is at or above the given threshold
+    pub fn meets(&self, min: Impact) -> bool {
+        self.rank() >= min.rank()
+    }
+}
+
+/// Weekly window when the forex market is closed, in UTC.
+/// Configured with `FOREX_MARKET_CLOSE` / `FOREX_MARKET_OPEN` (e.g. "Sat 00:00")
+#[derive(Debug, Clone, Copy)]
+pub struct MarketHours {
+    close_minute: u32,
+    open_minute: u32,
+}
+
+const MINUTES_PER_WEEK: u32 = 7 * 24 * 60;
+
+impl MarketHours {
+    pub fn from_env() -> Self {
+        let close = std::env::var("FOREX_MARKET_CLOSE")
+            .ok()
+            .and_then(|v| Self::parse_weekly_time(&v))
+            .unwrap_or(5 * 24 * 60);
+        let open = std::env::var("FOREX_MARKET_OPEN")
+            .ok()
+            .and_then(|v| Self::parse_weekly_time(&v))
+            .unwrap_or(6 * 24 * 60 + 21 * 60);
+
+        Self {
+            close_minute: close,
+            open_minute: open,
+        }
+    }
+
+    /// Parse "Sat 00:00" into minutes since Monday 00:00
+    fn parse_weekly_time(value: &str) -> Option<u32> {
+        let (day, time) = value.trim().split_once(' ')?;
+        let weekday: Weekday = day.parse().ok()?;
+        let time = NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()?;
+        Some(weekday.num_days_from_monday() * 24 * 60 + time.hour() * 60 + time.minute())
+    }
+
+    fn minute_of_week(now: DateTime<Utc>) -> u32 {
+        now.weekday().num_days_from_monday() * 24 * 60 + now.hour() * 60 + now.minute()
+    }
+
+    pub fn is_closed(&self, now: DateTime<Utc>) -> bool {
+        let minute = Self::minute_of_week(now);
+        if self.close_minute <= self.open_minute {
+            minute >= self.close_minute && minute < self.open_minute
+        } else {
+            minute >= self.close_minute || minute < self.open_minute
+        }
+    }
+
+    /// When the market next opens after `now`
+    pub fn next_open(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let minute = Self::minute_of_week(now);
+        let until_open = (self.open_minute + MINUTES_PER_WEEK - minute) % MINUTES_PER_WEEK;
+        let start_of_minute = now
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(now);
+        start_of_minute + chrono::Duration::minutes(until_open as i64)
+    }
+}
+
+/// Fetch state of one RSS feed. Validators are sent back as
+/// `If-None-Match`/`If-Modified-Since` so unchanged feeds return 304
+#[derive(Debug, Clone)]
+pub struct FeedStatus {
+    pub source: &'static str,
+    pub last_success: Option<Instant>,
+    pub last_status: Option<u16>,
+    pub consecutive_failures: u32,
+    /// Fetches are skipped until this time after repeated failures
+    pub retry_at: Option<Instant>,
+    pub not_modified_count: u64,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl FeedStatus {
+    fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            last_success: None,
+            last_status: None,
+            consecutive_failures: 0,
+            retry_at: None,
+            not_modified_count: 0,
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    fn record_failure(&mut self, status: Option<u16>) {
+        self.last_status = status;
+        self.consecutive_failures += 1;
+        let backoff = FEED_BACKOFF_BASE_SECS
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(10))
+            .min(FEED_BACKOFF_MAX_SECS);
+        self.retry_at = Some(Instant::now() + Duration::from_secs(backoff));
+    }
+
+    fn record_success(&mut self, status: u16) {
+        self.last_status = Some(status);
+        self.last_success = Some(Instant::now());
+        self.consecutive_failures = 0;
+        self.retry_at = None;
+    }
+}
+
+static FEED_STATUS: OnceLock<parking_lot::RwLock<HashMap<&'static str, FeedStatus>>> =
+    OnceLock::new();
+
+fn feed_status_map() -> &'static parking_lot::RwLock<HashMap<&'static str, FeedStatus>> {
+    FEED_STATUS.get_or_init(|| parking_lot::RwLock::new(HashMap::new()))
+}
+
+fn update_feed(source: &'static str, f: impl FnOnce(&mut FeedStatus)) {
+    let mut map = feed_status_map().write();
+    f(map.entry(source).or_insert_with(|| FeedStatus::new(source)));
+}
+
+/// Snapshot of every feed's fetch state, sorted by source
+pub fn feed_status() -> Vec<FeedStatus> {
+    let mut feeds: Vec<FeedStatus> = feed_status_map().read().values().cloned().collect();
+    feeds.sort_by(|a, b| a.source.cmp(b.source));
+    feeds
 }
 
 pub struct ForexService {
@@ -99,10 +290,41 @@ pub struct ForexService {
     http: Arc<Http>,
     check_interval_secs: u64,
     gemini_api_key: Option<String>,
+    gemini: Option<GeminiService>,
+    // Sentiment per news ID so the same item never hits Gemini twice
+    sentiment_cache: RwLock<HashMap<String, String>>,
+    health: HealthRegistry,
+    market_hours: MarketHours,
+    // Sources still fetched while the market is closed (e.g. "wsj_world")
+    weekend_sources: Vec<String>,
+    last_closed_log: parking_lot::Mutex<Option<Instant>>,
+    // Classify impact with Gemini unless a guild overrides it (FOREX_AI_IMPACT)
+    ai_impact_default: bool,
+}
+
+/// A guild's include/exclude filters against a currency and title. Excludes
+/// win over includes; an empty include list lets everything through
+pub fn filters_allow(currency: &str, title: &str, filters: &[ForexFilter]) -> bool {
+    let currency = currency.to_uppercase();
+    let title = title.to_uppercase();
+    let matches = |value: &str| {
+        let value = value.to_uppercase();
+        currency.contains(&value) || title.contains(&value)
+    };
+
+    if filters
+        .iter()
+        .any(|f| f.kind == "exclude" && matches(&f.value))
+    {
+        return false;
+    }
+
+    let mut includes = filters.iter().filter(|f| f.kind == "include").peekable();
+    includes.peek().is_none() || includes.any(|f| matches(&f.value))
 }
 
 impl ForexService {
-    pub fn new(db: DbPool, http: Arc<Http>) -> Self {
+    pub fn new(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> Self {
         let gemini_api_key = Config::from_env().ok().and_then(|c| {
             if c.gemini_api_key != "api_key" {
                 Some(c.gemini_api_key)
@@ -111,6 +333,10 @@ impl ForexService {
             }
         });
 
+        let gemini = gemini_api_key.clone().map(|key| {
+            GeminiService::new(key, "You are a concise forex market analyst.".to_string())
+        });
+
         Self {
             client: Client::builder()
                 .timeout(Duration::from_secs(30))
@@ -121,6 +347,22 @@ impl ForexService {
             http,
             check_interval_secs: 30,
             gemini_api_key,
+            gemini,
+            sentiment_cache: RwLock::new(HashMap::new()),
+            health,
+            market_hours: MarketHours::from_env(),
+            weekend_sources: std::env::var("FOREX_WEEKEND_SOURCES")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_lowercase())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            last_closed_log: parking_lot::Mutex::new(None),
+            ai_impact_default: std::env::var("FOREX_AI_IMPACT")
+                .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "on"))
+                .unwrap_or(false),
         }
     }
 
@@ -132,38 +374,76 @@ impl ForexService {
         loop {
             check_interval.tick().await;
 
-            if let Err(e) = self.check_for_news().await {
-                eprintln!("[FOREX] Error checking news: {}", e);
+            match self.check_for_news().await {
+                Ok(_) => health::record_success(&self.health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&self.health, SERVICE_NAME);
+                    eprintln!("[FOREX] Error checking news: {}", e);
+                }
             }
         }
     }
 
+    /// Log the market-closed skip at most once per hour
+    fn log_market_closed(&self) {
+        let mut last = self.last_closed_log.lock();
+        if last.is_none_or(|t| t.elapsed() >= Duration::from_secs(3600)) {
+            println!(
+                "[FOREX] Market closed, skipping until {}",
+                self.market_hours
+                    .next_open(Utc::now())
+                    .format("%a %H:%M UTC")
+            );
+            *last = Some(Instant::now());
+        }
+    }
+
     async fn check_for_news(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let closed = self.market_hours.is_closed(Utc::now());
+        if closed {
+            self.log_market_closed();
+            if self.weekend_sources.is_empty() {
+                return Ok(());
+            }
+        }
+        let should_fetch =
+            |source: &str| !closed || self.weekend_sources.iter().any(|s| s == source);
+
         let mut all_news = Vec::new();
 
-        match self.fetch_fxstreet().await {
-            Ok(news) => all_news.extend(news),
-            Err(e) => eprintln!("[FOREX] Error fetching FXStreet News: {}", e),
+        if should_fetch("fxstreet") {
+            match self.fetch_fxstreet().await {
+                Ok(news) => all_news.extend(news),
+                Err(e) => eprintln!("[FOREX] Error fetching FXStreet News: {}", e),
+            }
         }
 
-        match self.fetch_fxstreet_analysis().await {
-            Ok(news) => all_news.extend(news),
-            Err(e) => eprintln!("[FOREX] Error fetching FXStreet Analysis: {}", e),
+        if should_fetch("fxstreet_analysis") {
+            match self.fetch_fxstreet_analysis().await {
+                Ok(news) => all_news.extend(news),
+                Err(e) => eprintln!("[FOREX] Error fetching FXStreet Analysis: {}", e),
+            }
         }
 
-        match self.fetch_dailyforex().await {
-            Ok(news) => all_news.extend(news),
-            Err(e) => eprintln!("[FOREX] Error fetching DailyForex: {}", e),
+        if should_fetch("dailyforex") {
+            match self.fetch_dailyforex().await {
+                Ok(news) => all_news.extend(news),
+                Err(e) => eprintln!("[FOREX] Error fetching DailyForex: {}", e),
+            }
         }
 
-        match self.fetch_wsj_world_news().await {
-            Ok(news) => all_news.extend(news),
-            Err(e) => eprintln!("[FOREX] Error fetching WSJ World News: {}", e),
+        if should_fetch("wsj_world") {
+            match self.fetch_wsj_world_news().await {
+                Ok(news) => all_news.extend(news),
+                Err(e) => eprintln!("[FOREX] Error fetching WSJ World News: {}", e),
+            }
         }
 
-        match self.fetch_wsj_markets().await {
-            Ok(news) => all_news.extend(news),
-            Err(e) => eprintln!("[FOREX] Error fetching WSJ Markets: {}", e),
+        if should_fetch("wsj_markets") {
+            match self.fetch_wsj_markets().await {
+                Ok(news) => all_news.extend(news),
+                Err(e) => eprintln!("[FOREX] Error fetching WSJ Markets: {}", e),
+            }
         }
 
         if all_news.is_empty() {
@@ -182,7 +462,30 @@ impl ForexService {
         if !new_items.is_empty() {
             println!("[FOREX] Found {} new item(s)", new_items.len());
 
-            self.notify_news(&new_items).await?;
+            // Same story from another feed: still marked as sent below, just not announced
+            let mut seen: Vec<Vec<String>> =
+                ForexRepository::get_recent_titles(pool, DUPLICATE_WINDOW_HOURS)
+                    .await?
+                    .iter()
+                    .map(|t| Self::title_tokens(t))
+                    .collect();
+            let mut to_send = Vec::new();
+            for item in &new_items {
+                let tokens = Self::title_tokens(&item.title);
+                if seen
+                    .iter()
+                    .any(|other| Self::title_similarity(&tokens, other) >= DUPLICATE_THRESHOLD)
+                {
+                    println!("[FOREX] Skipping duplicate story: {}", item.title);
+                } else {
+                    to_send.push(item.clone());
+                }
+                seen.push(tokens);
+            }
+
+            if !to_send.is_empty() {
+                self.notify_news(&to_send).await?;
+            }
 
             for item in &new_items {
                 let source = if item.id.starts_with("wsj_world") {
@@ -196,18 +499,101 @@ impl ForexService {
                 } else {
                     "FXStreet"
                 };
-                ForexRepository::insert_news(pool, &item.id, source).await?;
+                ForexRepository::insert_news(
+                    pool,
+                    &item.id,
+                    source,
+                    &item.title,
+                    &item.currency,
+                    item.impact.key(),
+                    item.link.as_deref(),
+                )
+                .await?;
             }
         }
 
         Ok(())
     }
 
+    /// Download a feed. Returns None when it hasn't changed (304) or the source
+    /// is backing off after repeated failures
+    async fn fetch_feed(
+        &self,
+        source: &'static str,
+        url: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let (etag, last_modified) = {
+            let map = feed_status_map().read();
+            match map.get(source) {
+                Some(feed) if feed.retry_at.is_some_and(|t| Instant::now() < t) => {
+                    return Ok(None);
+                }
+                Some(feed) => (feed.etag.clone(), feed.last_modified.clone()),
+                None => (None, None),
+            }
+        };
+
+        let mut request = self.client.get(url);
+        if let Some(etag) = &etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                update_feed(source, |feed| feed.record_failure(None));
+                return Err(e.into());
+            }
+        };
+
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            update_feed(source, |feed| {
+                feed.record_success(status.as_u16());
+                feed.not_modified_count += 1;
+            });
+            return Ok(None);
+        }
+        if !status.is_success() {
+            update_feed(source, |feed| feed.record_failure(Some(status.as_u16())));
+            return Err(format!("HTTP {}", status).into());
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+
+        match response.text().await {
+            Ok(body) => {
+                update_feed(source, |feed| {
+                    feed.record_success(status.as_u16());
+                    feed.etag = etag;
+                    feed.last_modified = last_modified;
+                });
+                Ok(Some(body))
+            }
+            Err(e) => {
+                update_feed(source, |feed| feed.record_failure(Some(status.as_u16())));
+                Err(e.into())
+            }
+        }
+    }
+
     async fn fetch_fxstreet(
         &self,
     ) -> Result<Vec<ForexNews>, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(FXSTREET_RSS).send().await?;
-        let body = response.text().await?;
+        let Some(body) = self.fetch_feed("fxstreet", FXSTREET_RSS).await? else {
+            return Ok(Vec::new());
+        };
 
         let channel = rss::Channel::read_from(body.as_bytes())?;
         let mut news = Vec::new();
@@ -247,8 +633,12 @@ impl ForexService {
     async fn fetch_fxstreet_analysis(
         &self,
     ) -> Result<Vec<ForexNews>, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(FXSTREET_ANALYSIS_RSS).send().await?;
-        let body = response.text().await?;
+        let Some(body) = self
+            .fetch_feed("fxstreet_analysis", FXSTREET_ANALYSIS_RSS)
+            .await?
+        else {
+            return Ok(Vec::new());
+        };
 
         let channel = rss::Channel::read_from(body.as_bytes())?;
         let mut news = Vec::new();
@@ -288,8 +678,9 @@ impl ForexService {
     async fn fetch_dailyforex(
         &self,
     ) -> Result<Vec<ForexNews>, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(DAILY_FOREX).send().await?;
-        let body = response.text().await?;
+        let Some(body) = self.fetch_feed("dailyforex", DAILY_FOREX).await? else {
+            return Ok(Vec::new());
+        };
 
         let channel = rss::Channel::read_from(body.as_bytes())?;
         let mut news = Vec::new();
@@ -308,23 +699,10 @@ impl ForexService {
                 .and_then(|d| DateTime::parse_from_rfc2822(d).ok())
                 .map(|dt| dt.with_timezone(&Utc));
 
-            let (translated_title, translated_desc) = if self.gemini_api_key.is_some() {
-                let t_title = self
-                    .translate_to_indonesian(&title)
-                    .await
-                    .unwrap_or_else(|_| Self::clean_html(&title));
-                let t_desc = self
-                    .translate_to_indonesian(&description)
-                    .await
-                    .unwrap_or_else(|_| Self::clean_html(&description));
-                (t_title, t_desc)
-            } else {
-                (Self::clean_html(&title), Self::clean_html(&description))
-            };
-
+            // Translated per guild at notify time, see `localize`
             news.push(ForexNews {
-                title: translated_title,
-                description: translated_desc,
+                title: Self::clean_html(&title),
+                description: Self::clean_html(&description),
                 currency,
                 impact,
                 time,
@@ -340,8 +718,9 @@ impl ForexService {
     async fn fetch_wsj_world_news(
         &self,
     ) -> Result<Vec<ForexNews>, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(WSJ_WORLD_NEWS_RSS).send().await?;
-        let body = response.text().await?;
+        let Some(body) = self.fetch_feed("wsj_world", WSJ_WORLD_NEWS_RSS).await? else {
+            return Ok(Vec::new());
+        };
 
         let channel = rss::Channel::read_from(body.as_bytes())?;
         let mut news = Vec::new();
@@ -381,8 +760,9 @@ impl ForexService {
     async fn fetch_wsj_markets(
         &self,
     ) -> Result<Vec<ForexNews>, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(WSJ_MARKETS_RSS).send().await?;
-        let body = response.text().await?;
+        let Some(body) = self.fetch_feed("wsj_markets", WSJ_MARKETS_RSS).await? else {
+            return Ok(Vec::new());
+        };
 
         let channel = rss::Channel::read_from(body.as_bytes())?;
         let mut news = Vec::new();
@@ -419,19 +799,27 @@ impl ForexService {
         Ok(news)
     }
 
-    async fn translate_to_indonesian(
+    async fn translate(
         &self,
         text: &str,
+        language: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let api_key = self
             .gemini_api_key
             .as_ref()
             .ok_or("Gemini API key not configured")?;
 
-        let prompt = format!(
-            "Terjemahkan teks berikut ke Bahasa Indonesia. Hanya berikan hasil terjemahan, tanpa penjelasan tambahan:\n\n{}",
-            text
-        );
+        let prompt = if language == "id" {
+            format!(
+                "Terjemahkan teks berikut ke Bahasa Indonesia. Hanya berikan hasil terjemahan, tanpa penjelasan tambahan:\n\n{}",
+                text
+            )
+        } else {
+            format!(
+                "Translate the following text to the language with code \"{}\". Only return the translation, without any extra explanation:\n\n{}",
+                language, text
+            )
+        };
 
         let request = GeminiRequest {
             contents: vec![GeminiContent {
@@ -458,6 +846,65 @@ impl ForexService {
         Ok(Self::clean_html(&translated))
     }
 
+    /// DailyForex items in the channel's language. Translations are cached in the
+    /// database per (news_id, language) and in `cache` for the current batch
+    async fn localize(
+        &self,
+        item: &ForexNews,
+        language: &str,
+        cache: &mut HashMap<(String, String), ForexNews>,
+    ) -> ForexNews {
+        if language == "off"
+            || language == "en"
+            || self.gemini_api_key.is_none()
+            || item.source_key() != "dailyforex"
+        {
+            return item.clone();
+        }
+
+        let key = (item.id.clone(), language.to_string());
+        if let Some(localized) = cache.get(&key) {
+            return localized.clone();
+        }
+
+        let pool = self.db.as_ref();
+        let mut localized = item.clone();
+        match ForexRepository::get_translation(pool, &item.id, language).await {
+            Ok(Some(cached)) => {
+                localized.title = cached.title;
+                localized.description = cached.description;
+            }
+            Ok(None) => {
+                let title = self.translate(&item.title, language).await;
+                let description = self.translate(&item.description, language).await;
+                match (title, description) {
+                    (Ok(title), Ok(description)) => {
+                        if let Err(e) = ForexRepository::insert_translation(
+                            pool,
+                            &item.id,
+                            language,
+                            &title,
+                            &description,
+                        )
+                        .await
+                        {
+                            eprintln!("[FOREX] Failed to cache translation: {}", e);
+                        }
+                        localized.title = title;
+                        localized.description = description;
+                    }
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("[FOREX] Translation to {} failed: {}", language, e);
+                    }
+                }
+            }
+            Err(e) => eprintln!("[FOREX] Failed to read translation cache: {}", e),
+        }
+
+        cache.insert(key, localized.clone());
+        localized
+    }
+
     async fn notify_news(
         &self,
         news: &[ForexNews],
@@ -472,25 +919,276 @@ impl ForexService {
 
         println!("[FOREX] Sending to {} channel(s)", channels.len());
 
+        let mut filters: HashMap<i64, Vec<ForexFilter>> = HashMap::new();
+        for filter in ForexRepository::get_all_filters(pool).await? {
+            filters.entry(filter.guild_id).or_default().push(filter);
+        }
+
+        let ai_wanted = channels
+            .iter()
+            .any(|c| c.ai_impact.unwrap_or(self.ai_impact_default));
+        let ai_impacts = if ai_wanted {
+            self.classify_impacts(news).await
+        } else {
+            HashMap::new()
+        };
+
+        let mut sentiments = HashMap::new();
+        for item in news.iter().filter(|n| {
+            n.impact == Impact::High
+                || ai_impacts
+                    .get(&n.id)
+                    .is_some_and(|(impact, _)| *impact == Impact::High)
+        }) {
+            if let Some(sentiment) = self.get_sentiment(item).await {
+                sentiments.insert(item.id.clone(), sentiment);
+            }
+        }
+
+        let mut translations = HashMap::new();
         for channel in channels {
+            let min_impact = Impact::from_key(&channel.min_impact);
+            let use_ai = channel.ai_impact.unwrap_or(self.ai_impact_default);
+            let guild_filters = filters
+                .get(&channel.guild_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
             for item in news {
-                if let Err(e) = self
-                    .send_notification(channel.channel_id as u64, item)
-                    .await
+                let (item, rationale) = match ai_impacts.get(&item.id).filter(|_| use_ai) {
+                    Some((impact, rationale)) => (
+                        ForexNews {
+                            impact: *impact,
+                            ..item.clone()
+                        },
+                        Some(rationale.as_str()),
+                    ),
+                    None => (item.clone(), None),
+                };
+                if !(item.impact.meets(min_impact)
+                    && Self::matches_channel_scope(&item, &channel)
+                    && Self::passes_filters(&item, guild_filters))
                 {
-                    eprintln!("[FOREX] Failed to send to {}: {}", channel.channel_id, e);
+                    continue;
+                }
+
+                let sentiment = sentiments.get(&item.id).map(String::as_str);
+                let ping_role = channel
+                    .ping_role_id
+                    .filter(|_| {
+                        item.impact
+                            .meets(Impact::from_key(&channel.ping_min_impact))
+                    })
+                    .map(|id| id as u64);
+                let item = self
+                    .localize(&item, &channel.language, &mut translations)
+                    .await;
+
+                let parent_id = channel.channel_id as u64;
+                let mut targets = vec![(parent_id, ping_role)];
+                if channel.use_threads {
+                    match self.currency_thread(parent_id, &item.currency).await {
+                        // High impact news is also kept in the parent channel
+                        Ok(thread_id) if item.impact == Impact::High => {
+                            targets.push((thread_id, None));
+                        }
+                        Ok(thread_id) => targets = vec![(thread_id, ping_role)],
+                        Err(e) => eprintln!(
+                            "[FOREX] Failed to get {} thread in {}: {}",
+                            item.currency, parent_id, e
+                        ),
+                    }
+                }
+
+                for (target, ping_role) in targets {
+                    if let Err(e) = self
+                        .send_notification(target, &item, sentiment, ping_role, rationale)
+                        .await
+                    {
+                        eprintln!("[FOREX] Failed to send to {}: {}", target, e);
+                    }
+                    tokio::time::sleep(Duration::from_millis(800)).await;
                 }
-                tokio::time::sleep(Duration::from_millis(800)).await;
             }
         }
 
         Ok(())
     }
 
+    /// Thread for `currency` under a forex channel. Creates it on first use,
+    /// unarchives it, or replaces it if it was deleted or can't be reopened
+    async fn currency_thread(
+        &self,
+        channel_id: u64,
+        currency: &str,
+    ) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = self.db.as_ref();
+
+        if let Some(thread_id) = ForexRepository::get_thread(pool, channel_id, currency).await? {
+            let thread = ChannelId::new(thread_id as u64);
+            match thread.to_channel(&self.http).await {
+                Ok(Channel::Guild(existing)) => {
+                    let archived = existing.thread_metadata.is_some_and(|meta| meta.archived);
+                    if !archived {
+                        return Ok(thread.get());
+                    }
+                    match thread
+                        .edit_thread(&self.http, EditThread::new().archived(false))
+                        .await
+                    {
+                        Ok(_) => return Ok(thread.get()),
+                        Err(e) => eprintln!(
+                            "[FOREX] Could not unarchive thread {}, creating a new one: {}",
+                            thread_id, e
+                        ),
+                    }
+                }
+                _ => println!(
+                    "[FOREX] Thread {} for {} is gone, creating a new one",
+                    thread_id, currency
+                ),
+            }
+        }
+
+        let thread = ChannelId::new(channel_id)
+            .create_thread(
+                &self.http,
+                CreateThread::new(currency)
+                    .kind(ChannelType::PublicThread)
+                    .auto_archive_duration(AutoArchiveDuration::OneWeek),
+            )
+            .await?;
+        ForexRepository::set_thread(pool, channel_id, currency, thread.id.get()).await?;
+        println!("[FOREX] Created {} thread in {}", currency, channel_id);
+
+        Ok(thread.id.get())
+    }
+
+    /// Impact and rationale per news ID from one batched Gemini prompt. Items
+    /// missing from the reply, or all of them on error or timeout, keep the
+    /// keyword impact
+    async fn classify_impacts(&self, news: &[ForexNews]) -> HashMap<String, (Impact, String)> {
+        let Some(gemini) = self.gemini.as_ref() else {
+            return HashMap::new();
+        };
+
+        let headlines: Vec<String> = news
+            .iter()
+            .map(|n| {
+                let description: String = n.description.chars().take(200).collect();
+                format!("{} — {}", n.title, description)
+            })
+            .collect();
+
+        let response = match tokio::time::timeout(
+            Duration::from_secs(AI_IMPACT_TIMEOUT_SECS),
+            gemini.classify_impact(GeminiModel::default().id(), &headlines),
+        )
+        .await
+        {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                eprintln!("[FOREX] AI impact classification failed: {}", e);
+                return HashMap::new();
+            }
+            Err(_) => {
+                eprintln!("[FOREX] AI impact classification timed out");
+                return HashMap::new();
+            }
+        };
+
+        let classified: HashMap<String, (Impact, String)> = Self::parse_impact_lines(&response)
+            .into_iter()
+            .filter_map(|(number, impact, rationale)| {
+                let item = news.get(number.checked_sub(1)?)?;
+                Some((item.id.clone(), (impact, rationale)))
+            })
+            .collect();
+        println!(
+            "[FOREX] AI classified {}/{} item(s)",
+            classified.len(),
+            news.len()
+        );
+        classified
+    }
+
+    /// Parse "<number>|<HIGH|MEDIUM|LOW>|<rationale>" lines, skipping anything else
+    fn parse_impact_lines(response: &str) -> Vec<(usize, Impact, String)> {
+        response
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '|').map(str::trim);
+                let number = parts.next()?.trim_end_matches('.').parse::<usize>().ok()?;
+                let impact = match parts.next()?.to_uppercase().as_str() {
+                    "HIGH" => Impact::High,
+                    "MEDIUM" => Impact::Medium,
+                    "LOW" => Impact::Low,
+                    _ => return None,
+                };
+                let rationale: String = parts
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .take(AI_RATIONALE_MAX_CHARS)
+                    .collect();
+                Some((number, impact, rationale))
+            })
+            .collect()
+    }
+
+    /// Per-channel source and currency scope set with /forex_setup
+    fn matches_channel_scope(news: &ForexNews, channel: &ForexChannel) -> bool {
+        let source_ok = channel
+            .source
+            .as_deref()
+            .is_none_or(|source| news.source_key() == source);
+        let currency_ok = channel.currency.as_deref().is_none_or(|currency| {
+            let currency = currency.to_uppercase();
+            news.currency.to_uppercase().contains(&currency)
+                || news.title.to_uppercase().contains(&currency)
+        });
+        source_ok && currency_ok
+    }
+
+    fn passes_filters(news: &ForexNews, filters: &[ForexFilter]) -> bool {
+        filters_allow(&news.currency, &news.title, filters)
+    }
+
+    async fn get_sentiment(&self, news: &ForexNews) -> Option<String> {
+        let gemini = self.gemini.as_ref()?;
+
+        if let Some(cached) = self.sentiment_cache.read().await.get(&news.id) {
+            return Some(cached.clone());
+        }
+
+        let text = format!("{}\n\n{}", news.title, news.description);
+        match gemini
+            .analyze_sentiment(GeminiModel::default().id(), &text)
+            .await
+        {
+            Ok(sentiment) if !sentiment.is_empty() => {
+                let sentiment: String = sentiment.chars().take(SENTIMENT_MAX_CHARS).collect();
+                let mut cache = self.sentiment_cache.write().await;
+                if cache.len() >= SENTIMENT_CACHE_LIMIT {
+                    cache.clear();
+                }
+                cache.insert(news.id.clone(), sentiment.clone());
+                Some(sentiment)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                eprintln!("[FOREX] Sentiment analysis failed: {}", e);
+                None
+            }
+        }
+    }
+
     async fn send_notification(
         &self,
         channel_id: u64,
         news: &ForexNews,
+        sentiment: Option<&str>,
+        ping_role: Option<u64>,
+        ai_rationale: Option<&str>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let channel = ChannelId::new(channel_id);
 
@@ -530,21 +1228,37 @@ impl ForexService {
             .map(|l| format!("[Baca Selengkapnya]({})", l))
             .unwrap_or_else(|| source_name.to_string());
 
-        let embed = CreateEmbed::new()
+        let mut embed = CreateEmbed::new()
             .title(&news.title)
             .color(news.impact.color())
             .field(&news.currency, &news.title, false)
             .field("", &desc, false)
             .field("Time", &time_str, true)
-            .field("Impact", news.impact.bar(), true)
+            .field("Impact", news.impact.bar(), true);
+
+        if let Some(sentiment) = sentiment {
+            embed = embed.field("Sentiment", sentiment, false);
+        }
+
+        let footer = match ai_rationale {
+            Some(rationale) if !rationale.is_empty() => {
+                format!("Forex Alert • {} • AI impact: {}", source_name, rationale)
+            }
+            Some(_) => format!("Forex Alert • {} • AI impact", source_name),
+            None => format!("Forex Alert • {}", source_name),
+        };
+
+        let embed = embed
             .field("Source", &source_link, false)
-            .footer(CreateEmbedFooter::new(format!(
-                "Forex Alert • {}",
-                source_name
-            )))
+            .footer(CreateEmbedFooter::new(footer))
             .timestamp(serenity::all::Timestamp::now());
 
-        let message = CreateMessage::new().embed(embed);
+        let mut message = CreateMessage::new().embed(embed);
+        if let Some(role_id) = ping_role {
+            message = message
+                .content(format!("<@&{}>", role_id))
+                .allowed_mentions(CreateAllowedMentions::new().roles(vec![RoleId::new(role_id)]));
+        }
         channel.send_message(&self.http, message).await?;
 
         Ok(())
@@ -809,6 +1523,29 @@ impl ForexService {
             .to_string()
     }
 
+    /// Lowercased words of a title with punctuation stripped
+    fn title_tokens(title: &str) -> Vec<String> {
+        let mut tokens: Vec<String> = title
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect();
+        tokens.sort();
+        tokens.dedup();
+        tokens
+    }
+
+    /// Shared tokens over the size of the larger title, from 0.0 to 1.0
+    fn title_similarity(a: &[String], b: &[String]) -> f64 {
+        let larger = a.len().max(b.len());
+        if larger == 0 {
+            return 0.0;
+        }
+        let shared = a.iter().filter(|t| b.binary_search(t).is_ok()).count();
+        shared as f64 / larger as f64
+    }
+
     fn hash_string(s: &str) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
@@ -818,10 +1555,21 @@ impl ForexService {
     }
 }
 
-/// Start the forex news service
-pub async fn start_forex_service(db: DbPool, http: Arc<Http>) {
-    let service = Arc::new(ForexService::new(db, http));
-    tokio::spawn(async move {
+/// Start the forex news service and its calendar reminders
+pub async fn start_forex_service(
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+) -> Vec<JoinHandle<()>> {
+    health::register(&health, SERVICE_NAME);
+    let reminders = crate::services::forex_calendar::start_calendar_reminders(
+        db.clone(),
+        http.clone(),
+        health.clone(),
+    );
+    let service = Arc::new(ForexService::new(db, http, health));
+    let news = tokio::spawn(async move {
         service.start_monitoring().await;
     });
+    vec![reminders, news]
 }
@@ -0,0 +1,286 @@
+use crate::repository::{DbPool, ForexChannel, ForexFilter, ForexRepository};
+use crate::services::forex::{Impact, filters_allow};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Asia::Jakarta;
+use parking_lot::RwLock;
+use reqwest::Client;
+use serde::Deserialize;
+use serenity::all::{
+    ChannelId, CreateAllowedMentions, CreateEmbed, CreateEmbedFooter, CreateMessage, Http, RoleId,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const CALENDAR_URL: &str = "https://nfs.faireconomy.media/ff_calendar_thisweek.json";
+pub const SERVICE_NAME: &str = "Forex Calendar";
+/// How long before a High-impact event the reminder goes out
+const REMIND_BEFORE_MINS: i64 = 30;
+const CHECK_INTERVAL_SECS: u64 = 60;
+// The feed is rate limited, so the event list is only refreshed hourly
+const REFRESH_INTERVAL_SECS: u64 = 60 * 60;
+
+/// This week's events and when they were fetched, reused by `/forex_calendar` for an hour
+pub type CalendarCache = Arc<RwLock<Option<(Vec<CalendarEvent>, Instant)>>>;
+
+/// One entry of the Forex Factory weekly calendar
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalendarEvent {
+    pub title: String,
+    pub country: String,
+    pub date: DateTime<chrono::FixedOffset>,
+    #[serde(default)]
+    pub impact: String,
+    #[serde(default)]
+    pub forecast: String,
+    #[serde(default)]
+    pub previous: String,
+}
+
+impl CalendarEvent {
+    pub fn is_high_impact(&self) -> bool {
+        let impact = self.impact.to_lowercase();
+        impact.contains("high") || impact == "red"
+    }
+
+    /// `None` for holidays and other unrated entries
+    pub fn impact_level(&self) -> Option<Impact> {
+        match self.impact.to_lowercase().as_str() {
+            "high" | "red" => Some(Impact::High),
+            "medium" | "orange" => Some(Impact::Medium),
+            "low" | "yellow" => Some(Impact::Low),
+            _ => None,
+        }
+    }
+
+    /// Calendar day of the event in WIB
+    pub fn date_wib(&self) -> NaiveDate {
+        self.date.with_timezone(&Jakarta).date_naive()
+    }
+
+    pub fn time_utc(&self) -> DateTime<Utc> {
+        self.date.with_timezone(&Utc)
+    }
+
+    /// Event time in WIB, e.g. "Tue 14 Jan 20:30 WIB"
+    pub fn time_wib(&self) -> String {
+        self.date
+            .with_timezone(&Jakarta)
+            .format("%a %d %b %H:%M WIB")
+            .to_string()
+    }
+
+    /// ID used to track sent reminders in `forex_calendar_reminders`. Kept
+    /// readable rather than hashed so it stays the same across builds
+    fn reminder_id(&self) -> String {
+        format!(
+            "{}|{}|{}",
+            self.country,
+            self.title,
+            self.time_utc().timestamp()
+        )
+    }
+}
+
+/// Fetch this week's events from Forex Factory
+pub async fn fetch_calendar(
+    client: &Client,
+) -> Result<Vec<CalendarEvent>, Box<dyn std::error::Error + Send + Sync>> {
+    let events = client
+        .get(CALENDAR_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(events)
+}
+
+/// This week's events, fetched at most once per refresh interval
+pub async fn cached_calendar(
+    cache: &CalendarCache,
+    client: &Client,
+) -> Result<Vec<CalendarEvent>, Box<dyn std::error::Error + Send + Sync>> {
+    let cached = cache
+        .read()
+        .as_ref()
+        .filter(|(_, fetched)| fetched.elapsed().as_secs() < REFRESH_INTERVAL_SECS)
+        .map(|(events, _)| events.clone());
+    if let Some(events) = cached {
+        return Ok(events);
+    }
+
+    let events = fetch_calendar(client).await?;
+    *cache.write() = Some((events.clone(), Instant::now()));
+    Ok(events)
+}
+
+/// Posts a reminder to forex channels shortly before each High-impact event
+struct CalendarReminder {
+    client: Client,
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+    events: Vec<CalendarEvent>,
+    last_refresh: Option<std::time::Instant>,
+}
+
+impl CalendarReminder {
+    async fn run(mut self) {
+        let mut check_interval = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+
+        println!("[CALENDAR] Starting forex calendar reminders...");
+
+        loop {
+            check_interval.tick().await;
+
+            match self.check().await {
+                Ok(_) => health::record_success(&self.health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&self.health, SERVICE_NAME);
+                    eprintln!("[CALENDAR] Error checking events: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn check(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let stale = self
+            .last_refresh
+            .is_none_or(|t| t.elapsed() >= Duration::from_secs(REFRESH_INTERVAL_SECS));
+        if stale {
+            // Set first so a failing feed is retried hourly, not every check
+            self.last_refresh = Some(std::time::Instant::now());
+            self.events = fetch_calendar(&self.client)
+                .await?
+                .into_iter()
+                .filter(CalendarEvent::is_high_impact)
+                .collect();
+            println!(
+                "[CALENDAR] Loaded {} high impact event(s)",
+                self.events.len()
+            );
+        }
+
+        let now = Utc::now();
+        let due: Vec<&CalendarEvent> = self
+            .events
+            .iter()
+            .filter(|e| {
+                let until = e.time_utc() - now;
+                until > chrono::Duration::zero()
+                    && until <= chrono::Duration::minutes(REMIND_BEFORE_MINS)
+            })
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        let pool = self.db.as_ref();
+        let channels = ForexRepository::get_active_channels(pool).await?;
+        let mut filters: HashMap<i64, Vec<ForexFilter>> = HashMap::new();
+        for filter in ForexRepository::get_all_filters(pool).await? {
+            filters.entry(filter.guild_id).or_default().push(filter);
+        }
+
+        for event in due {
+            let id = event.reminder_id();
+            if ForexRepository::is_reminder_sent(pool, &id).await? {
+                continue;
+            }
+
+            println!("[CALENDAR] Reminder: {} {}", event.country, event.title);
+            let wanted = channels.iter().filter(|c| {
+                let guild_filters = filters.get(&c.guild_id).map(Vec::as_slice);
+                Self::wants(c, event, guild_filters.unwrap_or_default())
+            });
+            for channel in wanted {
+                if let Err(e) = self.send_reminder(channel, event).await {
+                    eprintln!("[CALENDAR] Failed to send to {}: {}", channel.channel_id, e);
+                }
+                tokio::time::sleep(Duration::from_millis(800)).await;
+            }
+
+            ForexRepository::insert_reminder(pool, &id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Channels limited to a single news source are skipped; a currency
+    /// scope has to match the event's currency, and the guild's
+    /// include/exclude filters apply as they do to news
+    fn wants(channel: &ForexChannel, event: &CalendarEvent, filters: &[ForexFilter]) -> bool {
+        channel.source.is_none()
+            && channel
+                .currency
+                .as_ref()
+                .is_none_or(|c| c.eq_ignore_ascii_case(&event.country))
+            && filters_allow(&event.country, &event.title, filters)
+    }
+
+    async fn send_reminder(
+        &self,
+        channel: &ForexChannel,
+        event: &CalendarEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let or_dash = |s: &str| {
+            if s.is_empty() {
+                "—".to_string()
+            } else {
+                s.to_string()
+            }
+        };
+
+        let embed = CreateEmbed::new()
+            .title(format!("⏰ {} {}", event.country, event.title))
+            .description(format!(
+                "High impact event starting <t:{}:R>",
+                event.time_utc().timestamp()
+            ))
+            .color(Impact::High.color())
+            .field("Time", event.time_wib(), true)
+            .field("Impact", Impact::High.bar(), true)
+            .field("Forecast", or_dash(&event.forecast), true)
+            .field("Previous", or_dash(&event.previous), true)
+            .footer(CreateEmbedFooter::new("Forex Calendar • Forex Factory"))
+            .timestamp(serenity::all::Timestamp::now());
+
+        let mut message = CreateMessage::new().embed(embed);
+        if let Some(role_id) = channel.ping_role_id {
+            let role_id = role_id as u64;
+            message = message
+                .content(format!("<@&{}>", role_id))
+                .allowed_mentions(CreateAllowedMentions::new().roles(vec![RoleId::new(role_id)]));
+        }
+        ChannelId::new(channel.channel_id as u64)
+            .send_message(&self.http, message)
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub fn start_calendar_reminders(
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    let reminder = CalendarReminder {
+        client: Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+            .build()
+            .unwrap_or_default(),
+        db,
+        http,
+        health,
+        events: Vec::new(),
+        last_refresh: None,
+    };
+    tokio::spawn(reminder.run())
+}
@@ -64,10 +64,54 @@ struct ResponsePart {
     text: Option<String>,
 }
 
+/// Models a guild can pick with `/ai_model set`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum GeminiModel {
+    #[default]
+    #[name = "gemini-3-flash-preview"]
+    Flash3Preview,
+    #[name = "gemini-2.0-flash"]
+    Flash20,
+    #[name = "gemini-1.5-flash"]
+    Flash15,
+    #[name = "gemini-1.5-pro"]
+    Pro15,
+}
+
+impl GeminiModel {
+    pub const ALL: [GeminiModel; 4] = [
+        GeminiModel::Flash3Preview,
+        GeminiModel::Flash20,
+        GeminiModel::Flash15,
+        GeminiModel::Pro15,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            GeminiModel::Flash3Preview => "gemini-3-flash-preview",
+            GeminiModel::Flash20 => "gemini-2.0-flash",
+            GeminiModel::Flash15 => "gemini-1.5-flash",
+            GeminiModel::Pro15 => "gemini-1.5-pro",
+        }
+    }
+
+    /// Only IDs on the allowlist are accepted
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.id() == id)
+    }
+
+    /// Requests a guild may make per minute. Pro costs more, so it gets less
+    pub fn requests_per_minute(self) -> usize {
+        match self {
+            GeminiModel::Pro15 => 5,
+            _ => 10,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GeminiService {
     api_key: String,
-    model: String,
     system_prompt: String,
     http_client: Client,
     // Conversation history per user (user_id -> Vec<(role, message)>)
@@ -75,34 +119,32 @@ pub struct GeminiService {
 }
 
 impl GeminiService {
-    pub fn new(api_key: String, model: Option<String>, system_prompt: String) -> Self {
-        let model = model.unwrap_or_else(|| "gemini-3-flash-preview".to_string());
-
+    pub fn new(api_key: String, system_prompt: String) -> Self {
         Self {
             api_key,
-            model,
             system_prompt,
             http_client: Client::new(),
             history: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    fn create_client(&self) -> Result<Gemini, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(Gemini::new(&self.api_key)?)
+    fn create_client(&self, model: &str) -> Result<Gemini, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Gemini::with_model(&self.api_key, format!("models/{}", model))?)
     }
-    
-    fn get_api_url(&self) -> String {
+
+    fn get_api_url(&self, model: &str) -> String {
         format!(
             "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-            self.model, self.api_key
+            model, self.api_key
         )
     }
 
     pub async fn generate(
         &self,
+        model: &str,
         prompt: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.create_client()?;
+        let client = self.create_client(model)?;
 
         let response = client
             .generate_content()
@@ -121,6 +163,7 @@ impl GeminiService {
 
     pub async fn analyze_market(
         &self,
+        model: &str,
         symbol: &str,
         timeframe: &str,
         context: &str,
@@ -130,15 +173,16 @@ impl GeminiService {
             symbol, timeframe, context
         );
 
-        self.generate(&prompt).await
+        self.generate(model, &prompt).await
     }
 
     pub async fn chat(
         &self,
+        model: &str,
         user_id: &str,
         message: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let client = self.create_client()?;
+        let client = self.create_client(model)?;
 
         let mut history = self.history.write().await;
         let user_history = history.entry(user_id.to_string()).or_insert_with(Vec::new);
@@ -213,6 +257,7 @@ impl GeminiService {
 
     pub async fn analyze_image(
         &self,
+        model: &str,
         image_url: &str,
         prompt: Option<&str>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
@@ -243,7 +288,7 @@ impl GeminiService {
         };
 
         let response = self.http_client
-            .post(&self.get_api_url())
+            .post(self.get_api_url(model))
             .json(&request)
             .send()
             .await?;
@@ -266,6 +311,7 @@ impl GeminiService {
 
     pub async fn analyze_market_image(
         &self,
+        model: &str,
         image_url: &str,
         symbol: Option<&str>,
         timeframe: Option<&str>,
@@ -320,11 +366,12 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
             if !context.is_empty() { format!("\nKonteks tambahan: {}", context) } else { String::new() }
         );
 
-        self.analyze_image(image_url, Some(&market_prompt)).await
+        self.analyze_image(model, image_url, Some(&market_prompt)).await
     }
 
     pub async fn summarize(
         &self,
+        model: &str,
         text: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let prompt = format!(
@@ -333,12 +380,56 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
             text
         );
 
-        self.generate(&prompt).await
+        self.generate(model, &prompt).await
+    }
+
+    /// One-sentence bullish/bearish/neutral read on a news item, with confidence
+    pub async fn analyze_sentiment(
+        &self,
+        model: &str,
+        text: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let prompt = format!(
+            "Assess the market sentiment of the following forex news for the affected currency. \
+            Reply with exactly one sentence in the form \
+            \"<Bullish|Bearish|Neutral> (<low|medium|high> confidence): <short reason>\". \
+            No other text:\n\n{}",
+            text
+        );
+
+        let response = self.generate(model, &prompt).await?;
+        Ok(response.lines().next().unwrap_or_default().trim().to_string())
+    }
+
+    /// Rate numbered headlines HIGH, MEDIUM or LOW impact. Returns the raw reply,
+    /// one "<number>|<impact>|<rationale>" line per headline
+    pub async fn classify_impact(
+        &self,
+        model: &str,
+        headlines: &[String],
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let list = headlines
+            .iter()
+            .enumerate()
+            .map(|(i, headline)| format!("{}. {}", i + 1, headline))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let prompt = format!(
+            "Rate the expected market impact of each numbered forex headline for currency traders. \
+            Reply with exactly one line per headline in the form \
+            \"<number>|<HIGH|MEDIUM|LOW>|<one-line rationale>\". \
+            No other text:\n\n{}",
+            list
+        );
+
+        self.generate(model, &prompt).await
     }
 
     /// Translate text
     pub async fn translate(
         &self,
+        model: &str,
         text: &str,
         target_language: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
@@ -347,12 +438,13 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
             target_language, text
         );
 
-        self.generate(&prompt).await
+        self.generate(model, &prompt).await
     }
 
     /// Generate code
     pub async fn generate_code(
         &self,
+        model: &str,
         description: &str,
         language: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
@@ -361,12 +453,13 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
             language, description
         );
 
-        self.generate(&prompt).await
+        self.generate(model, &prompt).await
     }
 
     /// Explain code
     pub async fn explain_code(
         &self,
+        model: &str,
         code: &str,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let prompt = format!(
@@ -375,6 +468,6 @@ Berikan analisis lengkap berdasarkan instruksi di atas."#,
             code
         );
 
-        self.generate(&prompt).await
+        self.generate(model, &prompt).await
     }
 }
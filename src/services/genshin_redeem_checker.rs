@@ -1,23 +1,103 @@
 use crate::repository::{DbPool, RedeemRepository};
-use crate::scraper::genshin::{GenshinCodeData, GenshinCodeScraper};
-use serenity::all::{ChannelId, Color, CreateEmbed, CreateMessage, Http};
-use std::sync::Arc;
+use crate::scraper::ennead::EnneadCodeScraper;
+use crate::scraper::wuwa::{self, WuwaCodeScraper};
+use crate::scraper::{CodeData, CodeSource};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serenity::all::{ChannelId, Color, CreateEmbed, CreateMessage, Http, UserId};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::{Duration, interval};
 
 pub struct CodeCheckerService {
-    scraper: GenshinCodeScraper,
+    /// Polled in order each cycle; locked so a source can switch to its fallback API
+    sources: Vec<RwLock<Box<dyn CodeSource>>>,
     db: DbPool,
     http: Arc<Http>,
     check_interval_secs: u64,
+    health: HealthRegistry,
+    owners: HashSet<UserId>,
+}
+
+pub const SERVICE_NAME: &str = "Code Checker";
+pub const HI3_GAME: &str = "hi3";
+
+/// Failures in a row before the bot owners get a DM
+const ALERT_AFTER_FAILURES: u32 = 3;
+/// Failures in a row before a source switches to its fallback API
+const FALLBACK_AFTER_FAILURES: u32 = 10;
+
+/// Fetch state of a single code scraper
+#[derive(Debug, Clone)]
+pub struct ScraperHealth {
+    pub name: &'static str,
+    pub consecutive_failures: u32,
+    pub last_success: Option<Instant>,
+    pub api_url: String,
+}
+
+static SCRAPER_HEALTH: OnceLock<parking_lot::RwLock<HashMap<&'static str, ScraperHealth>>> =
+    OnceLock::new();
+
+fn scraper_health_map() -> &'static parking_lot::RwLock<HashMap<&'static str, ScraperHealth>> {
+    SCRAPER_HEALTH.get_or_init(|| parking_lot::RwLock::new(HashMap::new()))
+}
+
+/// Snapshot of every scraper's health, sorted by name
+pub fn scraper_health() -> Vec<ScraperHealth> {
+    let mut scrapers: Vec<ScraperHealth> = scraper_health_map().read().values().cloned().collect();
+    scrapers.sort_by(|a, b| a.name.cmp(b.name));
+    scrapers
+}
+
+/// Record a fetch result and return the new consecutive failure count
+fn record_scraper_result(name: &'static str, api_url: &str, ok: bool) -> u32 {
+    let mut map = scraper_health_map().write();
+    let entry = map.entry(name).or_insert_with(|| ScraperHealth {
+        name,
+        consecutive_failures: 0,
+        last_success: None,
+        api_url: api_url.to_string(),
+    });
+    entry.api_url = api_url.to_string();
+    if ok {
+        entry.consecutive_failures = 0;
+        entry.last_success = Some(Instant::now());
+    } else {
+        entry.consecutive_failures += 1;
+    }
+    entry.consecutive_failures
 }
 
 impl CodeCheckerService {
-    pub fn new(db: DbPool, http: Arc<Http>) -> Self {
+    pub fn new(
+        db: DbPool,
+        http: Arc<Http>,
+        health: HealthRegistry,
+        owners: HashSet<UserId>,
+    ) -> Self {
+        let mut sources: Vec<RwLock<Box<dyn CodeSource>>> = vec![
+            RwLock::new(Box::new(EnneadCodeScraper::genshin())),
+            RwLock::new(Box::new(EnneadCodeScraper::hsr())),
+            RwLock::new(Box::new(EnneadCodeScraper::zzz())),
+            RwLock::new(Box::new(EnneadCodeScraper::hi3())),
+        ];
+        match WuwaCodeScraper::from_env() {
+            Some(scraper) => sources.push(RwLock::new(Box::new(scraper))),
+            None => println!("{} not set, skipping Wuthering Waves codes", wuwa::API_ENV),
+        }
+
         Self {
-            scraper: GenshinCodeScraper::new(),
+            sources,
             db,
             http,
             check_interval_secs: 300,
+            health,
+            owners,
         }
     }
 
@@ -27,132 +107,325 @@ impl CodeCheckerService {
         loop {
             check_interval.tick().await;
 
-            if let Err(e) = self.check_for_new_codes().await {
-                eprintln!("Error checking for new codes: {}", e);
+            let mut all_ok = true;
+            for source in &self.sources {
+                let result = self.check_for_new_codes(source).await;
+                let (name, url) = {
+                    let source = source.read().await;
+                    (source.name(), source.api_url().to_string())
+                };
+                if let Err(ref e) = result {
+                    eprintln!("Error checking for new {} codes: {}", name, e);
+                }
+                let failures = record_scraper_result(name, &url, result.is_ok());
+                self.handle_scraper_failures(
+                    source,
+                    failures,
+                    result.as_ref().err().map(|e| e.to_string()),
+                )
+                .await;
+                all_ok &= result.is_ok();
+            }
+
+            if all_ok {
+                health::record_success(&self.health, SERVICE_NAME);
+            } else {
+                health::record_failure(&self.health, SERVICE_NAME);
             }
         }
     }
 
-    async fn check_for_new_codes(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        println!("Checking for new Genshin codes...");
+    /// Alert owners after repeated failures and switch the source to its fallback API
+    async fn handle_scraper_failures(
+        &self,
+        source: &RwLock<Box<dyn CodeSource>>,
+        failures: u32,
+        error: Option<String>,
+    ) {
+        let (name, fallback_env) = {
+            let source = source.read().await;
+            (source.name(), source.fallback_env())
+        };
 
-        let current_codes = self.scraper.fetch_codes().await?;
+        if failures == ALERT_AFTER_FAILURES {
+            self.alert_owners(name, failures, &error.unwrap_or_default())
+                .await;
+        }
+
+        if failures == FALLBACK_AFTER_FAILURES
+            && let Some(fallback_env) = fallback_env
+            && let Ok(fallback) = std::env::var(fallback_env)
+        {
+            let mut source = source.write().await;
+            if source.api_url() != fallback {
+                println!("Switching {} scraper to fallback API: {}", name, fallback);
+                source.set_api_url(fallback);
+            }
+        }
+    }
+
+    async fn alert_owners(&self, name: &str, failures: u32, reason: &str) {
+        let embed = CreateEmbed::new()
+            .title("Scraper Health Alert")
+            .description(format!(
+                "The **{}** code scraper has failed {} times in a row.\n\n```{}```",
+                name,
+                failures,
+                reason.chars().take(500).collect::<String>()
+            ))
+            .color(Color::from_rgb(237, 66, 69))
+            .footer(serenity::all::CreateEmbedFooter::new(
+                "Use /scraper_status for details",
+            ))
+            .timestamp(serenity::model::Timestamp::now());
+
+        for owner in &self.owners {
+            let result = match owner.create_dm_channel(&self.http).await {
+                Ok(dm) => dm
+                    .send_message(&self.http, CreateMessage::new().embed(embed.clone()))
+                    .await
+                    .map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to DM scraper alert to owner {}: {}", owner, e);
+            }
+        }
+    }
+
+    async fn check_for_new_codes(
+        &self,
+        source: &RwLock<Box<dyn CodeSource>>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let source = source.read().await;
+        let (name, game) = (source.name(), source.game());
+        println!("Checking for new {} codes...", name);
+
+        let current_codes = source.fetch_codes().await?;
 
         if current_codes.is_empty() {
-            println!("No active codes found from API");
+            println!("No active {} codes found from API", name);
             return Ok(());
         }
 
         let pool = self.db.as_ref();
+        // Keeps listed codes from being cleaned up and announced again
+        let listed: Vec<String> = current_codes.iter().map(|c| c.code.clone()).collect();
+        RedeemRepository::mark_seen(pool, game, &listed).await?;
 
+        let now = Utc::now();
         let mut new_codes = Vec::new();
         for code_data in &current_codes {
-            if !RedeemRepository::is_code_sent(pool, &code_data.code).await? {
+            // Sources sometimes keep listing codes past their expiry
+            let expired = code_data
+                .expiry
+                .as_deref()
+                .and_then(parse_expiry)
+                .is_some_and(|expiry| expiry < now);
+            if !expired && !RedeemRepository::is_code_sent(pool, game, &code_data.code).await? {
                 new_codes.push(code_data);
             }
         }
 
         if !new_codes.is_empty() {
-            println!("Found {} new code(s)!", new_codes.len());
+            println!("Found {} new {} code(s)!", new_codes.len(), name);
 
-            self.notify_new_codes(&new_codes).await?;
+            notify_new_codes(&self.http, pool, game, &new_codes).await?;
 
             for code in &new_codes {
                 RedeemRepository::insert_code(
                     pool,
-                    "genshin",
+                    game,
                     &code.code,
                     Some(&code.rewards),
-                    None,
+                    code.expiry.as_deref(),
                 )
                 .await?;
-                println!("Saved code to database: {}", code.code);
+                println!("Saved {} code to database: {}", name, code.code);
             }
         } else {
-            println!("No new codes found.");
+            println!("No new {} codes found.", name);
         }
 
         Ok(())
     }
+}
 
-    async fn notify_new_codes(
-        &self,
-        new_codes: &[&GenshinCodeData],
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let pool = self.db.as_ref();
-        let servers = RedeemRepository::get_active_servers(pool, "genshin").await?;
+/// Display name, redemption page and embed color for a game key
+pub fn game_info(game: &str) -> (&'static str, &'static str, Color) {
+    match game {
+        "genshin" => (
+            "Genshin Impact",
+            "https://genshin.hoyoverse.com/en/gift",
+            Color::from_rgb(91, 206, 250),
+        ),
+        "hsr" => (
+            "Honkai: Star Rail",
+            "https://hsr.hoyoverse.com/gift",
+            Color::from_rgb(91, 206, 250),
+        ),
+        "zzz" => (
+            "Zenless Zone Zero",
+            "https://zenless.hoyoverse.com/redemption",
+            Color::from_rgb(91, 206, 250),
+        ),
+        "wuwa" => (
+            "Wuthering Waves",
+            "https://wutheringwaves.kurogames.com/en/main/gift",
+            Color::from_rgb(91, 206, 250),
+        ),
+        HI3_GAME => (
+            "Honkai Impact 3rd",
+            "https://honkaiimpact3.hoyoverse.com/global/en-us/gift",
+            Color::new(0x6B44D6),
+        ),
+        _ => ("Unknown", "", Color::from_rgb(91, 206, 250)),
+    }
+}
 
-        if servers.is_empty() {
-            println!("No active servers configured for notifications");
-            return Ok(());
-        }
+/// Send new codes to every active server subscribed to `game`.
+/// Returns the number of servers that were notified
+pub async fn notify_new_codes(
+    http: &Http,
+    pool: &PgPool,
+    game: &str,
+    new_codes: &[&CodeData],
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let servers = RedeemRepository::get_active_servers(pool, game).await?;
 
-        println!("Sending notifications to {} server(s)", servers.len());
-
-        for server in servers {
-            if let Err(e) = self
-                .send_notification(server.channel_id as u64, new_codes)
-                .await
-            {
-                eprintln!(
-                    "Failed to send notification to channel {} (guild {}): {}",
-                    server.channel_id, server.guild_id, e
-                );
-            } else {
-                println!(
-                    "Successfully sent notification to guild {} (channel {})",
-                    server.guild_id, server.channel_id
-                );
-            }
+    if servers.is_empty() {
+        println!("No active servers configured for notifications");
+        return Ok(0);
+    }
+
+    println!("Sending notifications to {} server(s)", servers.len());
+
+    let mut notified = 0;
+    for server in servers {
+        if let Err(e) = send_notification(http, server.channel_id as u64, game, new_codes).await {
+            eprintln!(
+                "Failed to send notification to channel {} (guild {}): {}",
+                server.channel_id, server.guild_id, e
+            );
+        } else {
+            notified += 1;
+            println!(
+                "Successfully sent notification to guild {} (channel {})",
+                server.guild_id, server.channel_id
+            );
         }
+    }
 
-        Ok(())
+    Ok(notified)
+}
+
+async fn send_notification(
+    http: &Http,
+    channel_id: u64,
+    game: &str,
+    codes: &[&CodeData],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let channel = ChannelId::new(channel_id);
+    let (name, url, color) = game_info(game);
+
+    for code in codes {
+        let mut embed = CreateEmbed::new()
+            .title(format!("Kode Redeem {} Baru!", name))
+            .description(format!(
+                "Kode baru telah ditemukan! Segera redeem sebelum kadaluarsa.\n\n\
+                **Kode:** `{}`\n\n\
+                **Cara Redeem:**\n\
+                1. Buka [{} Redeem]({})\n\
+                2. Login dengan akun Anda\n\
+                3. Masukkan kode di atas\n\
+                4. Klaim reward di in-game mail",
+                code.code, name, url
+            ))
+            .color(color)
+            .field("Rewards", &code.rewards, false)
+            .field("Status", &code.status, true);
+
+        // HI3 codes can be limited to one server, so always say which
+        embed = match (&code.region, game) {
+            (Some(region), _) => embed.field("Server", region, true),
+            (None, HI3_GAME) => embed.field("Server", "Semua server (NA/EU/Asia/TW)", true),
+            (None, _) => embed,
+        };
+
+        let embed = embed
+            .footer(serenity::all::CreateEmbedFooter::new(
+                "Auto-detected by Redeem Bot",
+            ))
+            .timestamp(serenity::model::Timestamp::now());
+
+        let message = CreateMessage::new().content("@here").embed(embed);
+
+        channel.send_message(http, message).await?;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
     }
 
-    async fn send_notification(
-        &self,
-        channel_id: u64,
-        codes: &[&GenshinCodeData],
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let channel = ChannelId::new(channel_id);
-
-        for code in codes {
-            let embed = CreateEmbed::new()
-                .title("Kode Redeem Genshin Impact Baru!")
-                .description(format!(
-                    "Kode baru telah ditemukan! Segera redeem sebelum kadaluarsa.\n\n\
-                    **Kode:** `{}`\n\n\
-                    **Cara Redeem:**\n\
-                    1. Buka [Genshin Impact Redeem](https://genshin.hoyoverse.com/en/gift)\n\
-                    2. Login dengan akun Anda\n\
-                    3. Masukkan kode di atas\n\
-                    4. Klaim reward di in-game mail",
-                    code.code
-                ))
-                .color(Color::from_rgb(91, 206, 250))
-                .field("Rewards", &code.rewards, false)
-                .field("Status", &code.status, true)
-                .footer(serenity::all::CreateEmbedFooter::new(
-                    "Auto-detected by Redeem Bot",
-                ))
-                .timestamp(serenity::model::Timestamp::now());
-
-            let message = CreateMessage::new().content("@here").embed(embed);
-
-            channel.send_message(&self.http, message).await?;
-
-            tokio::time::sleep(Duration::from_millis(500)).await;
-        }
+    Ok(())
+}
 
-        Ok(())
+/// Parse the expiry formats returned by the code APIs
+pub fn parse_expiry(expiry: &str) -> Option<DateTime<Utc>> {
+    let expiry = expiry.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(expiry) {
+        return Some(dt.with_timezone(&Utc));
     }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(expiry, "%Y-%m-%d %H:%M:%S") {
+        return Some(dt.and_utc());
+    }
+    NaiveDate::parse_from_str(expiry, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(23, 59, 59))
+        .map(|dt| dt.and_utc())
 }
 
-pub async fn start_code_checker(db: DbPool, http: Arc<Http>) {
-    let checker = Arc::new(CodeCheckerService::new(db, http));
+/// Flag codes whose explicit expiry has passed. Old codes are deleted by
+/// the daily maintenance task
+async fn cleanup_codes(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let now = Utc::now();
+    for code in RedeemRepository::get_codes_with_expiry(pool).await? {
+        let expired = code
+            .expiry
+            .as_deref()
+            .and_then(parse_expiry)
+            .is_some_and(|expiry| expiry < now);
+        if expired {
+            RedeemRepository::mark_expired(pool, code.id).await?;
+            println!("Marked redeem code {} as expired", code.code);
+        }
+    }
+
+    Ok(())
+}
 
-    tokio::spawn(async move {
+pub async fn start_code_checker(
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+    owners: HashSet<UserId>,
+) -> Vec<JoinHandle<()>> {
+    health::register(&health, SERVICE_NAME);
+    let db_for_cleanup = db.clone();
+    let checker = Arc::new(CodeCheckerService::new(db, http, health, owners));
+
+    let monitor = tokio::spawn(async move {
         println!("Code checker service started - monitoring every 5 minutes");
         checker.start_monitoring().await;
     });
+
+    let cleanup = tokio::spawn(async move {
+        let mut cleanup_interval = interval(Duration::from_secs(24 * 60 * 60));
+        loop {
+            cleanup_interval.tick().await;
+            if let Err(e) = cleanup_codes(db_for_cleanup.as_ref()).await {
+                eprintln!("Error cleaning up redeem codes: {}", e);
+            }
+        }
+    });
+
+    vec![monitor, cleanup]
 }
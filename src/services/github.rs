@@ -0,0 +1,222 @@
+use crate::repository::{DbPool, GithubRepository, GithubSubscription};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serenity::all::{
+    ChannelId, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedAuthor, CreateMessage, Http,
+};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "GitHub Releases";
+const POLL_INTERVAL_SECS: u64 = 15 * 60;
+const USER_AGENT: &str = "wr-bot (https://github.com/wignn/wr-bot)";
+/// Release notes longer than this are cut off with a link to the full page
+const BODY_PREVIEW_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub html_url: String,
+    pub body: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub author: Option<ReleaseAuthor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAuthor {
+    pub login: String,
+    pub avatar_url: String,
+    pub html_url: String,
+}
+
+/// `owner/repo` lowercased, or `None` if `input` isn't in that form
+pub fn normalize_repo(input: &str) -> Option<String> {
+    let repo = input
+        .trim()
+        .trim_start_matches("https://github.com/")
+        .trim_end_matches('/')
+        .to_lowercase();
+    let (owner, name) = repo.split_once('/')?;
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    (valid(owner) && valid(name)).then_some(repo)
+}
+
+pub fn client() -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .user_agent(USER_AGENT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Latest published release, or `None` when the repo has no releases.
+/// Uses `GITHUB_TOKEN` when set for the higher rate limit
+pub async fn latest_release(
+    client: &Client,
+    repo: &str,
+) -> Result<Option<Release>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut request = client
+        .get(format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            repo
+        ))
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN")
+        && !token.is_empty()
+    {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request.send().await?;
+    // GitHub answers 404 both for a missing repo and for one without releases
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?.json().await?))
+}
+
+fn body_preview(body: &str) -> String {
+    let body = body.trim();
+    if body.chars().count() <= BODY_PREVIEW_CHARS {
+        return body.to_string();
+    }
+    let preview: String = body.chars().take(BODY_PREVIEW_CHARS).collect();
+    format!("{}…", preview.trim_end())
+}
+
+pub fn release_message(repo: &str, release: &Release) -> CreateMessage {
+    let title = release
+        .name
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(&release.tag_name);
+
+    let mut embed = CreateEmbed::new()
+        .title(format!("🚀 {}", title))
+        .url(&release.html_url)
+        .description(
+            release
+                .body
+                .as_deref()
+                .map(body_preview)
+                .filter(|body| !body.is_empty())
+                .unwrap_or_else(|| "_No release notes_".to_string()),
+        )
+        .field("Repository", repo, true)
+        .field("Tag", format!("`{}`", release.tag_name), true)
+        .color(0x24292F);
+
+    if let Some(author) = &release.author {
+        embed = embed.author(
+            CreateEmbedAuthor::new(&author.login)
+                .icon_url(&author.avatar_url)
+                .url(&author.html_url),
+        );
+    }
+    if let Some(published_at) = release.published_at {
+        embed = embed
+            .field(
+                "Published",
+                format!("<t:{}:F>", published_at.timestamp()),
+                true,
+            )
+            .timestamp(published_at);
+    }
+
+    CreateMessage::new()
+        .embed(embed)
+        .components(vec![CreateActionRow::Buttons(vec![
+            CreateButton::new_link(&release.html_url).label("View release"),
+        ])])
+}
+
+/// Fetch each subscribed repo once and post releases that changed since the last check
+async fn check(
+    pool: &PgPool,
+    http: &Http,
+    client: &Client,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut by_repo: BTreeMap<String, Vec<GithubSubscription>> = BTreeMap::new();
+    for subscription in GithubRepository::get_all(pool).await? {
+        by_repo
+            .entry(subscription.repo.clone())
+            .or_default()
+            .push(subscription);
+    }
+
+    for (repo, subscriptions) in by_repo {
+        // One failing repo shouldn't hold up the rest
+        let release = match latest_release(client, &repo).await {
+            Ok(Some(release)) => release,
+            Ok(None) => continue,
+            Err(e) => {
+                eprintln!("[GITHUB] Failed to fetch releases for {}: {}", repo, e);
+                continue;
+            }
+        };
+        let release_id = release.id.to_string();
+
+        for subscription in subscriptions {
+            if subscription.last_release_id.as_deref() == Some(release_id.as_str()) {
+                continue;
+            }
+            // Mark first so a failing channel is not retried every poll
+            GithubRepository::set_last_release(
+                pool,
+                subscription.guild_id as u64,
+                &repo,
+                &release_id,
+            )
+            .await?;
+
+            if let Err(e) = ChannelId::new(subscription.channel_id as u64)
+                .send_message(http, release_message(&repo, &release))
+                .await
+            {
+                eprintln!(
+                    "[GITHUB] Failed to post {} {} to {}: {}",
+                    repo, release.tag_name, subscription.channel_id, e
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn start_github_releases(
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    tokio::spawn(async move {
+        let client = client();
+        let mut poll_interval = interval(Duration::from_secs(POLL_INTERVAL_SECS));
+        println!("[GITHUB] Starting release notifications...");
+
+        loop {
+            poll_interval.tick().await;
+            match check(db.as_ref(), &http, &client).await {
+                Ok(_) => health::record_success(&health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&health, SERVICE_NAME);
+                    eprintln!("[GITHUB] Error checking releases: {}", e);
+                }
+            }
+        }
+    })
+}
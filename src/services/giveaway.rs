@@ -0,0 +1,186 @@
+use crate::repository::{DbPool, Giveaway, GiveawayRepository};
+use crate::utils::health::{self, HealthRegistry};
+use chrono::Utc;
+use serenity::all::{
+    ChannelId, CreateAllowedMentions, CreateEmbed, CreateMessage, Http, MessageId, ReactionType,
+    UserId,
+};
+use sqlx::PgPool;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "Giveaways";
+pub const GIVEAWAY_EMOJI: &str = "🎉";
+const CHECK_INTERVAL_SECS: u64 = 30;
+const REACTION_PAGE_SIZE: u8 = 100;
+
+/// Everyone except bots who reacted with the giveaway emoji
+pub async fn entrants(
+    http: &Http,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> serenity::Result<Vec<u64>> {
+    let mut users = Vec::new();
+    let mut after: Option<UserId> = None;
+
+    loop {
+        let page = channel_id
+            .reaction_users(
+                http,
+                message_id,
+                ReactionType::Unicode(GIVEAWAY_EMOJI.to_string()),
+                Some(REACTION_PAGE_SIZE),
+                after,
+            )
+            .await?;
+        let full = page.len() == REACTION_PAGE_SIZE as usize;
+        after = page.last().map(|user| user.id);
+        users.extend(
+            page.iter()
+                .filter(|user| !user.bot)
+                .map(|user| user.id.get()),
+        );
+
+        if !full {
+            return Ok(users);
+        }
+    }
+}
+
+/// Pick up to `count` random entrants that are not in `exclude`
+pub fn draw(entrants: &[u64], count: usize, exclude: &[u64]) -> Vec<u64> {
+    let state = RandomState::new();
+    let mut eligible: Vec<u64> = entrants
+        .iter()
+        .copied()
+        .filter(|id| !exclude.contains(id))
+        .collect();
+    eligible.sort_by_key(|id| state.hash_one(id));
+    eligible.truncate(count);
+    eligible
+}
+
+/// Post the drawn winners in the giveaway channel
+pub async fn announce_winners(
+    http: &Http,
+    giveaway: &Giveaway,
+    winners: &[u64],
+    reroll: bool,
+) -> serenity::Result<()> {
+    let channel_id = ChannelId::new(giveaway.channel_id as u64);
+    let link = format!(
+        "https://discord.com/channels/{}/{}/{}",
+        giveaway.guild_id, giveaway.channel_id, giveaway.message_id
+    );
+
+    let message = if winners.is_empty() {
+        let embed = CreateEmbed::new()
+            .title("🎉 Giveaway Ended")
+            .description(format!(
+                "Nobody entered the giveaway for **{}**\n[Jump to giveaway]({})",
+                giveaway.prize, link
+            ))
+            .color(0x808080);
+        CreateMessage::new().embed(embed)
+    } else {
+        let users: Vec<UserId> = winners.iter().map(|id| UserId::new(*id)).collect();
+        let mentions: Vec<String> = users.iter().map(|u| format!("<@{}>", u)).collect();
+        let title = if reroll {
+            "🎉 Giveaway Rerolled"
+        } else {
+            "🎉 Giveaway Ended"
+        };
+        let embed = CreateEmbed::new()
+            .title(title)
+            .description(format!(
+                "Congratulations {}! You won **{}**\n[Jump to giveaway]({})",
+                mentions.join(", "),
+                giveaway.prize,
+                link
+            ))
+            .color(0xF1C40F);
+        CreateMessage::new()
+            .content(mentions.join(" "))
+            .embed(embed)
+            .allowed_mentions(CreateAllowedMentions::new().users(users))
+    };
+
+    channel_id.send_message(http, message).await?;
+    Ok(())
+}
+
+async fn end_giveaway(pool: &PgPool, http: &Http, giveaway: &Giveaway) -> Result<(), sqlx::Error> {
+    let message_id = giveaway.message_id as u64;
+    let entrants = match entrants(
+        http,
+        ChannelId::new(giveaway.channel_id as u64),
+        MessageId::new(message_id),
+    )
+    .await
+    {
+        Ok(entrants) => entrants,
+        Err(e) => {
+            // End it anyway so a deleted message or channel is not retried forever
+            eprintln!(
+                "[GIVEAWAY] Failed to read entrants for {}: {}",
+                message_id, e
+            );
+            return GiveawayRepository::set_winners(pool, message_id, &[]).await;
+        }
+    };
+
+    let winners = draw(&entrants, giveaway.winner_count.max(1) as usize, &[]);
+    GiveawayRepository::set_winners(pool, message_id, &winners).await?;
+
+    if let Err(e) = announce_winners(http, giveaway, &winners, false).await {
+        eprintln!("[GIVEAWAY] Failed to announce {}: {}", message_id, e);
+    }
+    Ok(())
+}
+
+async fn check(pool: &PgPool, http: &Http) -> Result<(), sqlx::Error> {
+    for giveaway in GiveawayRepository::get_due(pool, Utc::now().timestamp()).await? {
+        end_giveaway(pool, http, &giveaway).await?;
+    }
+    Ok(())
+}
+
+pub fn start_giveaways(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    tokio::spawn(async move {
+        let mut check_interval = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+        println!("[GIVEAWAY] Starting giveaway draws...");
+
+        loop {
+            check_interval.tick().await;
+            match check(db.as_ref(), &http).await {
+                Ok(_) => health::record_success(&health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&health, SERVICE_NAME);
+                    eprintln!("[GIVEAWAY] Error ending giveaways: {}", e);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_skips_excluded() {
+        let winners = draw(&[1, 2, 3], 3, &[2]);
+        assert_eq!(winners.len(), 2);
+        assert!(!winners.contains(&2));
+    }
+
+    #[test]
+    fn test_draw_caps_at_count() {
+        let winners = draw(&[1, 2, 3, 4], 2, &[]);
+        assert_eq!(winners.len(), 2);
+        assert_ne!(winners[0], winners[1]);
+    }
+}
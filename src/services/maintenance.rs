@@ -0,0 +1,45 @@
+use crate::repository::{ForexRepository, RedeemRepository, ReminderRepository};
+use sqlx::PgPool;
+
+const DEFAULT_NEWS_RETENTION_DAYS: i64 = 30;
+const DEFAULT_CODE_RETENTION_DAYS: i64 = 60;
+const DEFAULT_REMINDER_RETENTION_DAYS: i64 = 30;
+
+/// Rows removed by one maintenance run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaintenanceReport {
+    pub news: u64,
+    pub codes: u64,
+    pub reminders: u64,
+}
+
+/// Retention in days from `var`, falling back to `default` when unset or invalid
+fn retention_days(var: &str, default: i64) -> i64 {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(default)
+}
+
+/// Delete old sent forex news, announced redeem codes and sent reminders.
+/// Retention comes from `NEWS_RETENTION_DAYS`, `REDEEM_CODE_RETENTION_DAYS`
+/// and `REMINDER_RETENTION_DAYS`
+pub async fn run(pool: &PgPool) -> Result<MaintenanceReport, sqlx::Error> {
+    let news_days = retention_days("NEWS_RETENTION_DAYS", DEFAULT_NEWS_RETENTION_DAYS);
+    let code_days = retention_days("REDEEM_CODE_RETENTION_DAYS", DEFAULT_CODE_RETENTION_DAYS);
+    let reminder_days = retention_days("REMINDER_RETENTION_DAYS", DEFAULT_REMINDER_RETENTION_DAYS);
+
+    let report = MaintenanceReport {
+        news: ForexRepository::cleanup_old_news(pool, news_days).await?,
+        codes: RedeemRepository::delete_expired_codes(pool, code_days).await?,
+        reminders: ReminderRepository::cleanup_sent_reminders(pool, reminder_days).await?,
+    };
+
+    println!(
+        "[MAINTENANCE] Removed {} forex news, {} redeem code(s), {} reminder(s)",
+        report.news, report.codes, report.reminders
+    );
+
+    Ok(report)
+}
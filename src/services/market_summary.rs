@@ -0,0 +1,109 @@
+use crate::repository::{DbPool, MarketSummaryRepository};
+use crate::services::tiingo::get_global_tiingo;
+use crate::utils::health::{self, HealthRegistry};
+use chrono::Utc;
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "Market Summary";
+/// Used when a guild has not picked any symbols
+pub const DEFAULT_SYMBOLS: &[&str] = &["xauusd", "eurusd", "gbpusd", "usdjpy"];
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Symbols summarized for a guild, falling back to `DEFAULT_SYMBOLS`
+pub async fn guild_symbols(pool: &PgPool, guild_id: u64) -> Result<Vec<String>, sqlx::Error> {
+    let symbols = MarketSummaryRepository::get_symbols(pool, guild_id).await?;
+    if symbols.is_empty() {
+        return Ok(DEFAULT_SYMBOLS.iter().map(|s| s.to_string()).collect());
+    }
+    Ok(symbols)
+}
+
+/// Embed with last price, change since the previous close and the day's range
+pub fn build_summary(symbols: &[String]) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("📊 Daily Market Summary")
+        .color(0x1DB954)
+        .footer(CreateEmbedFooter::new(
+            "Change is measured from the previous UTC close • Tiingo",
+        ))
+        .timestamp(serenity::all::Timestamp::now());
+
+    let Some(tiingo) = get_global_tiingo() else {
+        return embed.description("Price service not available");
+    };
+
+    for symbol in symbols {
+        let value = match tiingo.get_daily_stats(symbol) {
+            Some(stats) => {
+                let change = stats
+                    .change_pct()
+                    .map(|pct| {
+                        let arrow = if pct >= 0.0 { "🟢" } else { "🔴" };
+                        format!("{} {:+.2}%", arrow, pct)
+                    })
+                    .unwrap_or_else(|| "—".to_string());
+                format!(
+                    "Last: **{:.5}**\nChange: {}\nHigh: {:.5}\nLow: {:.5}",
+                    stats.last, change, stats.high, stats.low
+                )
+            }
+            None => "No data yet".to_string(),
+        };
+        embed = embed.field(symbol.to_uppercase(), value, true);
+    }
+
+    embed
+}
+
+/// Post each guild's summary once per day at its configured UTC time
+async fn check(pool: &PgPool, http: &Http) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let now = Utc::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    let current_time = now.format("%H:%M").to_string();
+
+    for config in MarketSummaryRepository::get_all_configs(pool).await? {
+        let due = config.post_time <= current_time
+            && config.last_sent_date.as_deref() != Some(today.as_str());
+        if !due {
+            continue;
+        }
+
+        let guild_id = config.guild_id as u64;
+        // Mark first so a failing channel is not retried every minute
+        MarketSummaryRepository::mark_sent(pool, guild_id, &today).await?;
+
+        let symbols = guild_symbols(pool, guild_id).await?;
+        let message = CreateMessage::new().embed(build_summary(&symbols));
+        if let Err(e) = ChannelId::new(config.channel_id as u64)
+            .send_message(http, message)
+            .await
+        {
+            eprintln!("[SUMMARY] Failed to send to {}: {}", config.channel_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn start_market_summary(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    tokio::spawn(async move {
+        let mut check_interval = interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+        println!("[SUMMARY] Starting daily market summaries...");
+
+        loop {
+            check_interval.tick().await;
+            match check(db.as_ref(), &http).await {
+                Ok(_) => health::record_success(&health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&health, SERVICE_NAME);
+                    eprintln!("[SUMMARY] Error checking schedules: {}", e);
+                }
+            }
+        }
+    })
+}
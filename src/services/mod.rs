@@ -1,8 +1,16 @@
 pub mod ai;
+pub mod birthday;
+pub mod crypto;
 pub mod forex;
+pub mod forex_calendar;
 pub mod gemini;
 pub mod genshin_redeem_checker;
+pub mod giveaway;
+pub mod github;
 pub mod link;
+pub mod maintenance;
+pub mod market_summary;
+pub mod price_board;
 pub mod music;
 pub mod tiingo;
 pub mod youtube;
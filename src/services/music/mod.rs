@@ -1,3 +1,5 @@
+pub mod node;
+pub mod persist;
 pub mod player;
 pub mod queue;
 
@@ -0,0 +1,90 @@
+use parking_lot::RwLock;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Last successful `/v4/info` + `/v4/stats` response, shown when Lavalink is unreachable
+pub type NodeStatusCache = Arc<RwLock<Option<NodeStatus>>>;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeVersion {
+    pub semver: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    pub version: NodeVersion,
+    pub jvm: String,
+    pub lavaplayer: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeMemory {
+    pub free: u64,
+    pub used: u64,
+    pub allocated: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCpu {
+    pub cores: u32,
+    pub system_load: f64,
+    pub lavalink_load: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStats {
+    pub players: u32,
+    pub playing_players: u32,
+    /// Milliseconds since Lavalink started
+    pub uptime: u64,
+    pub memory: NodeMemory,
+    pub cpu: NodeCpu,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    pub info: NodeInfo,
+    pub stats: NodeStats,
+    pub fetched_at: Instant,
+}
+
+/// Fetch node info and stats from the Lavalink REST API using the
+/// `LAVALINK_HOST`/`LAVALINK_PORT`/`LAVALINK_PASSWORD` settings
+pub async fn fetch_node_status() -> Result<NodeStatus, Box<dyn std::error::Error + Send + Sync>> {
+    let host = std::env::var("LAVALINK_HOST").unwrap_or_else(|_| "localhost".to_string());
+    let port = std::env::var("LAVALINK_PORT").unwrap_or_else(|_| "2333".to_string());
+    let password =
+        std::env::var("LAVALINK_PASSWORD").unwrap_or_else(|_| "youshallnotpass".to_string());
+    let base = format!("http://{}:{}/v4", host, port);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+
+    let info: NodeInfo = client
+        .get(format!("{}/info", base))
+        .header("Authorization", &password)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let stats: NodeStats = client
+        .get(format!("{}/stats", base))
+        .header("Authorization", &password)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(NodeStatus {
+        info,
+        stats,
+        fetched_at: Instant::now(),
+    })
+}
@@ -0,0 +1,164 @@
+use crate::repository::{MusicQueueRepository, SavedQueue};
+use crate::services::music::player::MusicPlayer;
+use crate::services::music::queue::QueuedTrack;
+use crate::utils::embed;
+use lavalink_rs::model::player::ConnectionInfo as LavalinkConnectionInfo;
+use lavalink_rs::model::track::TrackData;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, CreateMessage, GuildId, Http};
+use songbird::Songbird;
+use sqlx::PgPool;
+
+#[derive(Serialize, Deserialize)]
+struct SavedTrack {
+    track: TrackData,
+    requester_id: u64,
+    requester_name: String,
+}
+
+/// Save every queue that is playing or has tracks waiting, then disconnect.
+/// Returns the number of queues saved
+pub async fn save_queues(
+    player: &MusicPlayer,
+    songbird: &Songbird,
+    pool: &PgPool,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let mut saved = Vec::new();
+
+    for (guild_id, queue) in player.active_queues() {
+        let voice_channel = match songbird.get(guild_id) {
+            Some(call) => call.lock().await.current_channel(),
+            None => None,
+        };
+
+        if let Some(voice_channel) = voice_channel {
+            let tracks: Vec<SavedTrack> = queue
+                .current
+                .iter()
+                .chain(queue.tracks.iter())
+                .map(|t| SavedTrack {
+                    track: t.track.clone(),
+                    requester_id: t.requester_id,
+                    requester_name: t.requester_name.clone(),
+                })
+                .collect();
+
+            saved.push(SavedQueue {
+                guild_id: guild_id.get() as i64,
+                voice_channel_id: voice_channel.0.get() as i64,
+                text_channel_id: queue.text_channel_id.map(|c| c.get() as i64),
+                volume: queue.volume as i32,
+                tracks: serde_json::to_string(&tracks)?,
+            });
+        }
+
+        if let Some(player_ctx) = player.get_player_context(guild_id) {
+            let _ = player_ctx.close();
+        }
+        let _ = songbird.leave(guild_id).await;
+    }
+
+    MusicQueueRepository::replace_queues(pool, &saved).await?;
+    Ok(saved.len())
+}
+
+/// Rejoin voice and resume every queue saved by `save_queues`. The playing
+/// track starts over from the beginning
+pub async fn restore_queues(player: &MusicPlayer, songbird: &Songbird, http: &Http, pool: &PgPool) {
+    let saved = match MusicQueueRepository::take_queues(pool).await {
+        Ok(saved) => saved,
+        Err(e) => {
+            eprintln!("[MUSIC] Failed to load saved queues: {}", e);
+            return;
+        }
+    };
+
+    for queue in saved {
+        let guild_id = GuildId::new(queue.guild_id as u64);
+        match restore_queue(player, songbird, guild_id, &queue).await {
+            Ok(count) => {
+                println!(
+                    "[MUSIC] Restored {} track(s) in guild {}",
+                    count,
+                    guild_id.get()
+                );
+                if let Some(channel_id) = queue.text_channel_id {
+                    let message = CreateMessage::new().embed(embed::info(
+                        "Queue Restored",
+                        &format!("Resumed {} track(s) after a restart", count),
+                    ));
+                    let _ = ChannelId::new(channel_id as u64)
+                        .send_message(http, message)
+                        .await;
+                }
+            }
+            Err(e) => {
+                eprintln!(
+                    "[MUSIC] Failed to restore queue in guild {}: {}",
+                    guild_id.get(),
+                    e
+                );
+                let _ = songbird.leave(guild_id).await;
+                player.remove_queue(guild_id);
+            }
+        }
+    }
+}
+
+async fn restore_queue(
+    player: &MusicPlayer,
+    songbird: &Songbird,
+    guild_id: GuildId,
+    saved: &SavedQueue,
+) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let tracks: Vec<SavedTrack> = serde_json::from_str(&saved.tracks)?;
+    if tracks.is_empty() {
+        return Ok(0);
+    }
+
+    let voice_channel = ChannelId::new(saved.voice_channel_id as u64);
+    let (connection_info, _handle) = songbird
+        .join_gateway(guild_id, voice_channel)
+        .await
+        .map_err(|e| format!("Failed to join voice channel: {:?}", e))?;
+
+    let player_ctx = player
+        .create_player_with_connection(
+            guild_id,
+            LavalinkConnectionInfo {
+                endpoint: connection_info.endpoint,
+                token: connection_info.token,
+                session_id: connection_info.session_id,
+            },
+        )
+        .await?;
+
+    let count = tracks.len();
+    player.ensure_queue(guild_id);
+    if let Some(channel_id) = saved.text_channel_id {
+        player.set_text_channel(guild_id, ChannelId::new(channel_id as u64));
+    }
+    for track in tracks {
+        player.add_to_queue(
+            guild_id,
+            QueuedTrack {
+                track: track.track,
+                requester_id: track.requester_id,
+                requester_name: track.requester_name,
+            },
+        );
+    }
+
+    let volume = saved.volume.clamp(0, 100) as u8;
+    player.set_volume(guild_id, volume);
+    player_ctx.set_volume(volume as u16).await?;
+
+    if let Some(first) = player.next_track(guild_id) {
+        player_ctx.play(&first.track).await?;
+        player.set_last_track_title(guild_id, Some(first.track.info.title.clone()));
+        player.set_current(guild_id, Some(first));
+        player.touch_activity(guild_id);
+    }
+
+    Ok(count)
+}
@@ -3,7 +3,7 @@ use lavalink_rs::client::LavalinkClient;
 use lavalink_rs::model::track::TrackData;
 use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
-use serenity::all::{ChannelId, GuildId, Http, UserId};
+use serenity::all::{ChannelId, GuildId, Http, MessageId, UserId};
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
@@ -105,6 +105,31 @@ impl MusicPlayer {
         self.queues.read().get(&guild_id)?.text_channel_id
     }
 
+    pub fn set_control_message(&self, guild_id: GuildId, message: Option<(ChannelId, MessageId)>) {
+        let mut queues = self.queues.write();
+        let queue = queues.entry(guild_id).or_default();
+        queue.control_message_id = message;
+    }
+
+    pub fn get_control_message(&self, guild_id: GuildId) -> Option<(ChannelId, MessageId)> {
+        self.queues.read().get(&guild_id)?.control_message_id
+    }
+
+    pub fn set_now_playing_message(
+        &self,
+        guild_id: GuildId,
+        message: Option<(ChannelId, MessageId)>,
+    ) {
+        let mut queues = self.queues.write();
+        if let Some(queue) = queues.get_mut(&guild_id) {
+            queue.now_playing_message_id = message;
+        }
+    }
+
+    pub fn get_now_playing_message(&self, guild_id: GuildId) -> Option<(ChannelId, MessageId)> {
+        self.queues.read().get(&guild_id)?.now_playing_message_id
+    }
+
     pub fn set_current(&self, guild_id: GuildId, track: Option<QueuedTrack>) {
         let mut queues = self.queues.write();
         if let Some(queue) = queues.get_mut(&guild_id) {
@@ -215,6 +240,11 @@ impl MusicPlayer {
         queues.get_mut(&guild_id)?.remove(index)
     }
 
+    pub fn jump_to(&self, guild_id: GuildId, position: usize) -> Option<QueuedTrack> {
+        let mut queues = self.queues.write();
+        queues.get_mut(&guild_id)?.jump(position)
+    }
+
     pub fn set_autoplay(&self, guild_id: GuildId, enabled: bool) {
         let mut queues = self.queues.write();
         if let Some(queue) = queues.get_mut(&guild_id) {
@@ -299,6 +329,16 @@ impl MusicPlayer {
             .collect()
     }
 
+    /// Guilds with a track playing or waiting in the queue
+    pub fn active_queues(&self) -> Vec<(GuildId, MusicQueue)> {
+        self.queues
+            .read()
+            .iter()
+            .filter(|(_, queue)| queue.current.is_some() || !queue.tracks.is_empty())
+            .map(|(guild_id, queue)| (*guild_id, queue.clone()))
+            .collect()
+    }
+
     /// Remove a guild's queue (after disconnect)
     pub fn remove_queue(&self, guild_id: GuildId) {
         self.queues.write().remove(&guild_id);
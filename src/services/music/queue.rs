@@ -1,5 +1,5 @@
 use lavalink_rs::model::track::TrackData;
-use serenity::all::ChannelId;
+use serenity::all::{ChannelId, MessageId};
 use std::collections::VecDeque;
 use std::time::Instant;
 
@@ -16,6 +16,17 @@ impl Default for LoopMode {
     }
 }
 
+impl LoopMode {
+    /// Shown in `/queue` and the `/player` control panel
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoopMode::Off => "Off",
+            LoopMode::Track => "🔂 Track",
+            LoopMode::Queue => "🔁 Queue",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MusicQueue {
     pub tracks: VecDeque<QueuedTrack>,
@@ -31,6 +42,10 @@ pub struct MusicQueue {
     pub played_video_ids: VecDeque<String>,
     pub text_channel_id: Option<ChannelId>,
     pub last_activity: Instant, // Track when music was last active
+    /// Message holding the `/player` control panel
+    pub control_message_id: Option<(ChannelId, MessageId)>,
+    /// Latest "Now Playing" message, edited in place by `/seek`
+    pub now_playing_message_id: Option<(ChannelId, MessageId)>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +77,8 @@ impl MusicQueue {
             played_video_ids: VecDeque::with_capacity(20),
             text_channel_id: None,
             last_activity: Instant::now(),
+            control_message_id: None,
+            now_playing_message_id: None,
         }
     }
 
@@ -119,6 +136,25 @@ impl MusicQueue {
         self.current = None;
     }
 
+    /// Skip ahead to a 1-based position in the upcoming queue
+    pub fn jump(&mut self, position: usize) -> Option<QueuedTrack> {
+        if position == 0 || position > self.tracks.len() {
+            return None;
+        }
+
+        let skipped: Vec<QueuedTrack> = self.tracks.drain(..position - 1).collect();
+        if self.loop_mode == LoopMode::Queue {
+            if let Some(current) = self.current.take() {
+                self.played_tracks.push_back(current);
+            }
+            self.played_tracks.extend(skipped);
+        }
+
+        let next = self.tracks.pop_front()?;
+        self.current = Some(next.clone());
+        Some(next)
+    }
+
     pub fn remove(&mut self, index: usize) -> Option<QueuedTrack> {
         self.tracks.remove(index)
     }
@@ -0,0 +1,154 @@
+use crate::repository::{DbPool, PriceBoard, PriceBoardRepository};
+use crate::services::tiingo::get_global_tiingo;
+use crate::utils::health::{self, HealthRegistry};
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, EditMessage, Http, MessageId};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, interval};
+
+pub const SERVICE_NAME: &str = "Price Board";
+pub const MAX_BOARD_SYMBOLS: usize = 15;
+const REFRESH_INTERVAL_SECS: u64 = 60;
+// Pause between message edits to stay clear of Discord rate limits
+const EDIT_DELAY_MS: u64 = 1000;
+
+/// Mid price per symbol from the previous refresh
+pub type BoardPrices = HashMap<String, f64>;
+
+/// Render the board, comparing mids to `previous` for the arrows. Returns the
+/// embed and the mids it shows
+pub fn render_board(
+    symbols: &[String],
+    previous: Option<&BoardPrices>,
+) -> (CreateEmbed, BoardPrices) {
+    let mut current = BoardPrices::new();
+    let mut table = format!(
+        "  {:<8} {:>11} {:>11} {:>11}\n",
+        "Symbol", "Bid", "Ask", "Mid"
+    );
+
+    for symbol in symbols {
+        match get_global_tiingo().and_then(|t| t.get_price(symbol)) {
+            Some(price) => {
+                let arrow = match previous.and_then(|p| p.get(symbol)) {
+                    Some(prev) if price.mid > *prev => "▲",
+                    Some(prev) if price.mid < *prev => "▼",
+                    _ => "•",
+                };
+                table.push_str(&format!(
+                    "{} {:<8} {:>11.5} {:>11.5} {:>11.5}\n",
+                    arrow,
+                    symbol.to_uppercase(),
+                    price.bid,
+                    price.ask,
+                    price.mid
+                ));
+                current.insert(symbol.clone(), price.mid);
+            }
+            None => table.push_str(&format!(
+                "  {:<8} {:>11} {:>11} {:>11}\n",
+                symbol.to_uppercase(),
+                "N/A",
+                "N/A",
+                "N/A"
+            )),
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    let embed = CreateEmbed::new()
+        .title("💱 Live Prices")
+        .description(format!("```\n{}```\nLast updated <t:{}:R>", table, now))
+        .footer(CreateEmbedFooter::new("Updates every minute • Tiingo"))
+        .color(0x1DB954)
+        .timestamp(serenity::all::Timestamp::now());
+
+    (embed, current)
+}
+
+/// Edits every board whose prices changed since the last refresh
+struct BoardUpdater {
+    db: DbPool,
+    http: Arc<Http>,
+    health: HealthRegistry,
+    last_prices: HashMap<i64, BoardPrices>,
+}
+
+impl BoardUpdater {
+    async fn run(mut self) {
+        let mut refresh_interval = interval(Duration::from_secs(REFRESH_INTERVAL_SECS));
+        println!("[BOARD] Starting live price boards...");
+
+        loop {
+            refresh_interval.tick().await;
+            match self.refresh().await {
+                Ok(_) => health::record_success(&self.health, SERVICE_NAME),
+                Err(e) => {
+                    health::record_failure(&self.health, SERVICE_NAME);
+                    eprintln!("[BOARD] Error refreshing boards: {}", e);
+                }
+            }
+        }
+    }
+
+    async fn refresh(&mut self) -> Result<(), sqlx::Error> {
+        let pool: &PgPool = self.db.as_ref();
+        let boards = PriceBoardRepository::get_all_boards(pool).await?;
+        self.last_prices
+            .retain(|guild_id, _| boards.iter().any(|b| b.guild_id == *guild_id));
+
+        for board in boards {
+            let previous = self.last_prices.get(&board.guild_id);
+            let (embed, current) = render_board(&board.symbol_list(), previous);
+            if previous == Some(&current) {
+                continue;
+            }
+
+            match self.edit(&board, embed).await {
+                Ok(()) => {
+                    self.last_prices.insert(board.guild_id, current);
+                }
+                Err(serenity::Error::Http(e))
+                    if e.status_code().map(|s| s.as_u16()) == Some(404) =>
+                {
+                    println!(
+                        "[BOARD] Message for guild {} is gone, removing board",
+                        board.guild_id
+                    );
+                    PriceBoardRepository::delete_board(pool, board.guild_id as u64).await?;
+                }
+                Err(e) => eprintln!(
+                    "[BOARD] Failed to edit board in guild {}: {}",
+                    board.guild_id, e
+                ),
+            }
+            tokio::time::sleep(Duration::from_millis(EDIT_DELAY_MS)).await;
+        }
+
+        Ok(())
+    }
+
+    async fn edit(&self, board: &PriceBoard, embed: CreateEmbed) -> Result<(), serenity::Error> {
+        ChannelId::new(board.channel_id as u64)
+            .edit_message(
+                &self.http,
+                MessageId::new(board.message_id as u64),
+                EditMessage::new().embed(embed),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+pub fn start_price_boards(db: DbPool, http: Arc<Http>, health: HealthRegistry) -> JoinHandle<()> {
+    health::register(&health, SERVICE_NAME);
+    let updater = BoardUpdater {
+        db,
+        http,
+        health,
+        last_prices: HashMap::new(),
+    };
+    tokio::spawn(updater.run())
+}
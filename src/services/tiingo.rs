@@ -1,13 +1,27 @@
-use chrono::{DateTime, Utc};
+use crate::repository::{PriceAlertRepository, StoredAlert};
+use crate::utils::health::{self, HealthRegistry};
+use crate::utils::time::format_elapsed;
+use chrono::{DateTime, NaiveDate, Utc};
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::RwLock;
-use serde::Serialize;
-use serenity::all::{ChannelId, CreateEmbed, CreateMessage, Http};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, Http, UserId};
+use sqlx::PgPool;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use tokio::sync::Notify;
 use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
 
 const TIINGO_WS_URL: &str = "wss://api.tiingo.com/fx";
+const TIINGO_REST_URL: &str = "https://api.tiingo.com/tiingo/fx/top";
+/// Failed WebSocket sessions in a row before prices are polled over REST
+const FALLBACK_AFTER_FAILURES: u32 = 3;
+const REST_POLL_INTERVAL_SECS: u64 = 30;
+/// Subscribed when neither `TIINGO_SYMBOLS` nor `TIINGO_REST_SYMBOLS` is set
+const DEFAULT_SYMBOLS: &str = "xauusd,eurusd,gbpusd,usdjpy,audusd,usdchf,usdcad,nzdusd";
+
+pub const SERVICE_NAME: &str = "Tiingo WebSocket";
 
 #[derive(Debug, Clone)]
 pub struct ForexPrice {
@@ -16,6 +30,8 @@ pub struct ForexPrice {
     pub ask: f64,
     pub mid: f64,
     pub timestamp: DateTime<Utc>,
+    /// Polled over REST while the WebSocket is down
+    pub delayed: bool,
 }
 
 impl ForexPrice {
@@ -24,21 +40,231 @@ impl ForexPrice {
     }
 
     pub fn spread_pips(&self) -> f64 {
-        let multiplier = if self.symbol.to_uppercase().contains("JPY") {
-            100.0
-        } else if self.symbol.to_uppercase().contains("XAU") {
-            10.0
-        } else {
-            10000.0
-        };
-        self.spread() * multiplier
+        self.spread() / pip_size(&self.symbol)
+    }
+}
+
+/// Display precision and pip size shared by a class of instruments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolMeta {
+    pub decimals: usize,
+    pub pip_size: f64,
+}
+
+const FX_STANDARD: SymbolMeta = SymbolMeta {
+    decimals: 5,
+    pip_size: 0.0001,
+};
+const FX_JPY: SymbolMeta = SymbolMeta {
+    decimals: 3,
+    pip_size: 0.01,
+};
+const GOLD: SymbolMeta = SymbolMeta {
+    decimals: 2,
+    pip_size: 0.1,
+};
+const SILVER: SymbolMeta = SymbolMeta {
+    decimals: 3,
+    pip_size: 0.01,
+};
+
+const SYMBOL_META: &[(&str, SymbolMeta)] = &[
+    ("eurusd", FX_STANDARD),
+    ("gbpusd", FX_STANDARD),
+    ("audusd", FX_STANDARD),
+    ("nzdusd", FX_STANDARD),
+    ("usdchf", FX_STANDARD),
+    ("usdcad", FX_STANDARD),
+    ("eurgbp", FX_STANDARD),
+    ("euraud", FX_STANDARD),
+    ("eurchf", FX_STANDARD),
+    ("eurcad", FX_STANDARD),
+    ("eurnzd", FX_STANDARD),
+    ("gbpaud", FX_STANDARD),
+    ("gbpchf", FX_STANDARD),
+    ("gbpcad", FX_STANDARD),
+    ("gbpnzd", FX_STANDARD),
+    ("audnzd", FX_STANDARD),
+    ("audcad", FX_STANDARD),
+    ("audchf", FX_STANDARD),
+    ("nzdcad", FX_STANDARD),
+    ("nzdchf", FX_STANDARD),
+    ("cadchf", FX_STANDARD),
+    ("usdjpy", FX_JPY),
+    ("eurjpy", FX_JPY),
+    ("gbpjpy", FX_JPY),
+    ("audjpy", FX_JPY),
+    ("nzdjpy", FX_JPY),
+    ("cadjpy", FX_JPY),
+    ("chfjpy", FX_JPY),
+    ("xauusd", GOLD),
+    ("xagusd", SILVER),
+];
+
+/// Precision and pip size for a symbol. Symbols missing from the table keep
+/// five decimals and a pip size guessed from the ticker
+pub fn symbol_meta(symbol: &str) -> SymbolMeta {
+    let symbol = symbol.to_lowercase();
+    if let Some((_, meta)) = SYMBOL_META.iter().find(|(s, _)| *s == symbol) {
+        return *meta;
+    }
+
+    let pip_size = if symbol.contains("jpy") {
+        0.01
+    } else if symbol.contains("xau") {
+        0.1
+    } else {
+        0.0001
+    };
+    SymbolMeta {
+        decimals: 5,
+        pip_size,
+    }
+}
+
+/// Price change of one pip for a symbol
+pub fn pip_size(symbol: &str) -> f64 {
+    symbol_meta(symbol).pip_size
+}
+
+/// A price with the symbol's usual number of decimals
+pub fn format_price(symbol: &str, price: f64) -> String {
+    format!("{:.*}", symbol_meta(symbol).decimals, price)
+}
+
+pub const DEFAULT_REARM_PIPS: f64 = 10.0;
+/// Minimum time between two notifications from the same repeating alert
+pub const REPEAT_COOLDOWN_MINS: i64 = 15;
+/// Longest window a Move alert can watch, and how much price history is kept
+pub const MAX_MOVE_WINDOW_MINS: i64 = 240;
+/// How long the spread must stay under the threshold before a Spread alert re-arms
+pub const SPREAD_REARM_SECS: i64 = 60;
+// Price history keeps at most one sample per symbol this often
+const HISTORY_SAMPLE_SECS: i64 = 15;
+
+/// Mid price statistics for the current UTC day
+#[derive(Debug, Clone)]
+pub struct DailyStats {
+    pub date: NaiveDate,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub last: f64,
+    /// Last price of the previous UTC day, if the bot was running then
+    pub prev_close: Option<f64>,
+}
+
+impl DailyStats {
+    fn new(date: NaiveDate, mid: f64, prev_close: Option<f64>) -> Self {
+        Self {
+            date,
+            open: mid,
+            high: mid,
+            low: mid,
+            last: mid,
+            prev_close,
+        }
+    }
+
+    /// Percent change since the previous close
+    pub fn change_pct(&self) -> Option<f64> {
+        self.prev_close
+            .map(|close| (self.last - close) / close * 100.0)
+    }
+}
+
+/// Candles kept per symbol and timeframe
+pub const MAX_CANDLES: usize = 120;
+
+/// Bar size for `/chart`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, poise::ChoiceParameter)]
+pub enum Timeframe {
+    #[name = "1m"]
+    M1,
+    #[name = "5m"]
+    M5,
+    #[name = "1h"]
+    H1,
+}
+
+impl Timeframe {
+    pub const ALL: [Timeframe; 3] = [Timeframe::M1, Timeframe::M5, Timeframe::H1];
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            Timeframe::M1 => 60,
+            Timeframe::M5 => 5 * 60,
+            Timeframe::H1 => 60 * 60,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Timeframe::M1 => "1m",
+            Timeframe::M5 => "5m",
+            Timeframe::H1 => "1h",
+        }
+    }
+
+    /// Start of the bar that contains `at`
+    fn bucket(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = at.timestamp();
+        DateTime::from_timestamp(secs - secs.rem_euclid(self.seconds()), 0).unwrap_or(at)
     }
 }
 
+/// One OHLC bar of mid prices
+#[derive(Debug, Clone, Copy)]
+pub struct Candle {
+    pub start: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+type CandleMap = HashMap<(String, Timeframe), VecDeque<Candle>>;
+
+#[derive(Debug, Clone, Copy)]
+struct PricePoint {
+    at: DateTime<Utc>,
+    mid: f64,
+}
+
+static ALERT_ID_COUNTER: AtomicI64 = AtomicI64::new(1);
+
+pub fn next_alert_id() -> i64 {
+    ALERT_ID_COUNTER.fetch_add(1, Ordering::SeqCst)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AlertCondition {
     Above,
     Below,
+    /// Fires once when price moves from one side of the target to the other
+    Cross,
+    /// Fires every time price reaches the target, re-arming after moving away
+    Touch,
+    /// Fires when price changes by `target_price` percent within `window_mins`
+    Move,
+    /// Fires when the spread widens past `target_price` pips, re-arming once
+    /// it has stayed under the threshold for `SPREAD_REARM_SECS`
+    Spread,
+}
+
+impl AlertCondition {
+    /// Parse the name written by `Display`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "above" => Some(Self::Above),
+            "below" => Some(Self::Below),
+            "cross" => Some(Self::Cross),
+            "touch" => Some(Self::Touch),
+            "move" => Some(Self::Move),
+            "spread" => Some(Self::Spread),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for AlertCondition {
@@ -46,10 +272,26 @@ impl std::fmt::Display for AlertCondition {
         match self {
             AlertCondition::Above => write!(f, "above"),
             AlertCondition::Below => write!(f, "below"),
+            AlertCondition::Cross => write!(f, "cross"),
+            AlertCondition::Touch => write!(f, "touch"),
+            AlertCondition::Move => write!(f, "move"),
+            AlertCondition::Spread => write!(f, "spread"),
         }
     }
 }
 
+/// Where a triggered alert is delivered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum AlertNotify {
+    /// The channel the alert was created in
+    #[default]
+    #[name = "channel"]
+    Channel,
+    /// Direct message, falling back to the channel when DMs are closed
+    #[name = "dm"]
+    Dm,
+}
+
 #[derive(Debug, Clone)]
 pub struct PriceAlert {
     pub id: i64,
@@ -60,6 +302,214 @@ pub struct PriceAlert {
     pub condition: AlertCondition,
     pub target_price: f64,
     pub created_at: DateTime<Utc>,
+    /// Side of the target the price was last seen on (true = at or above)
+    pub last_side: Option<bool>,
+    /// Distance in pips price must move away before a Touch alert re-arms
+    pub rearm_pips: f64,
+    pub armed: bool,
+    /// Keep the alert after it fires
+    pub repeat: bool,
+    /// Untriggered alerts are removed after this time
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_triggered: Option<DateTime<Utc>>,
+    /// Move alerts: length of the rolling window
+    pub window_mins: Option<i64>,
+    /// Move alerts: price at the start of the window when the alert last fired
+    pub move_start: Option<f64>,
+    /// Spread alerts: when the spread last dropped back under the threshold
+    pub calm_since: Option<DateTime<Utc>>,
+    pub notify: AlertNotify,
+}
+
+impl PriceAlert {
+    /// Update tracking state for a new price and return whether the alert fires.
+    /// `history` is the symbol's recent prices, oldest first
+    fn evaluate(
+        &mut self,
+        price: f64,
+        spread_pips: f64,
+        now: DateTime<Utc>,
+        history: Option<&VecDeque<PricePoint>>,
+    ) -> bool {
+        let side = price >= self.target_price;
+        let previous_side = self.last_side.replace(side);
+
+        let cooling_down = self.repeat
+            && self
+                .last_triggered
+                .is_some_and(|t| now - t < chrono::Duration::minutes(REPEAT_COOLDOWN_MINS));
+
+        let fired = match self.condition {
+            AlertCondition::Above | AlertCondition::Below => {
+                let met = match self.condition {
+                    AlertCondition::Above => side,
+                    _ => price <= self.target_price,
+                };
+                // A repeating alert re-arms once price is back on the other side
+                if !self.armed {
+                    self.armed = !met;
+                    return false;
+                }
+                let fired = met && !cooling_down;
+                if fired {
+                    self.armed = false;
+                }
+                fired
+            }
+            AlertCondition::Cross => {
+                previous_side.is_some_and(|prev| prev != side) && !cooling_down
+            }
+            AlertCondition::Touch => {
+                let distance = (price - self.target_price).abs();
+                let pip = pip_size(&self.symbol);
+                if !self.armed {
+                    self.armed = distance >= self.rearm_pips * pip;
+                    return false;
+                }
+
+                let reached = distance < pip || previous_side.is_some_and(|prev| prev != side);
+                if reached && !cooling_down {
+                    self.armed = false;
+                    true
+                } else {
+                    false
+                }
+            }
+            AlertCondition::Move => {
+                let since = now - chrono::Duration::minutes(self.window_mins.unwrap_or(60));
+                let Some(start) = history
+                    .and_then(|h| h.iter().find(|p| p.at >= since))
+                    .map(|p| p.mid)
+                else {
+                    return false;
+                };
+                let met = ((price - start) / start * 100.0).abs() >= self.target_price;
+                if !self.armed {
+                    self.armed = !met;
+                    return false;
+                }
+                let fired = met && !cooling_down;
+                if fired {
+                    self.armed = false;
+                    self.move_start = Some(start);
+                }
+                fired
+            }
+            AlertCondition::Spread => {
+                let wide = spread_pips > self.target_price;
+                if !self.armed {
+                    if wide {
+                        self.calm_since = None;
+                    } else {
+                        let since = *self.calm_since.get_or_insert(now);
+                        self.armed = now - since >= chrono::Duration::seconds(SPREAD_REARM_SECS);
+                    }
+                    return false;
+                }
+                if wide {
+                    self.armed = false;
+                    self.calm_since = None;
+                }
+                wide
+            }
+        };
+
+        if fired {
+            self.last_triggered = Some(now);
+        }
+        fired
+    }
+
+    /// Whether the alert is removed after firing
+    pub fn is_one_shot(&self) -> bool {
+        !self.repeat
+            && !matches!(
+                self.condition,
+                AlertCondition::Touch | AlertCondition::Spread
+            )
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| t <= now)
+    }
+
+    /// Short description for alert lists, e.g. "EURUSD above 1.08500"
+    pub fn describe(&self) -> String {
+        match self.condition {
+            AlertCondition::Move => format!(
+                "{} moves {:.2}% within {}m",
+                self.symbol.to_uppercase(),
+                self.target_price,
+                self.window_mins.unwrap_or(60)
+            ),
+            AlertCondition::Spread => format!(
+                "{} spread above {:.1} pips",
+                self.symbol.to_uppercase(),
+                self.target_price
+            ),
+            _ => format!(
+                "{} {} {}",
+                self.symbol.to_uppercase(),
+                self.condition,
+                format_price(&self.symbol, self.target_price)
+            ),
+        }
+    }
+
+    fn to_stored(&self) -> StoredAlert {
+        StoredAlert {
+            id: self.id,
+            guild_id: self.guild_id as i64,
+            user_id: self.user_id as i64,
+            channel_id: self.channel_id as i64,
+            symbol: self.symbol.clone(),
+            condition: self.condition.to_string(),
+            target_price: self.target_price,
+            rearm_pips: self.rearm_pips,
+            created_at: self.created_at.timestamp(),
+            repeat: self.repeat,
+            expires_at: self.expires_at.map(|t| t.timestamp()),
+            last_triggered: self.last_triggered.map(|t| t.timestamp()),
+            window_mins: self.window_mins,
+            notify_dm: self.notify == AlertNotify::Dm,
+            last_side: self.last_side,
+            armed: Some(self.armed),
+        }
+    }
+
+    /// Restore a saved alert, keeping its crossing state. Move and spread
+    /// tracking starts over as for a new alert
+    fn from_stored(stored: StoredAlert) -> Option<Self> {
+        let condition = AlertCondition::from_name(&stored.condition)?;
+        Some(Self {
+            id: stored.id,
+            guild_id: stored.guild_id as u64,
+            user_id: stored.user_id as u64,
+            channel_id: stored.channel_id as u64,
+            symbol: stored.symbol,
+            armed: stored.armed.unwrap_or(condition != AlertCondition::Touch),
+            condition,
+            target_price: stored.target_price,
+            created_at: DateTime::from_timestamp(stored.created_at, 0).unwrap_or_else(Utc::now),
+            last_side: stored.last_side,
+            rearm_pips: stored.rearm_pips,
+            repeat: stored.repeat,
+            expires_at: stored
+                .expires_at
+                .and_then(|t| DateTime::from_timestamp(t, 0)),
+            last_triggered: stored
+                .last_triggered
+                .and_then(|t| DateTime::from_timestamp(t, 0)),
+            window_mins: stored.window_mins,
+            move_start: None,
+            calm_since: None,
+            notify: if stored.notify_dm {
+                AlertNotify::Dm
+            } else {
+                AlertNotify::Channel
+            },
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -67,6 +517,28 @@ pub struct TiingoService {
     api_key: String,
     prices: Arc<RwLock<HashMap<String, ForexPrice>>>,
     alerts: Arc<RwLock<Vec<PriceAlert>>>,
+    /// Recent mid prices per symbol for Move alerts
+    history: Arc<RwLock<HashMap<String, VecDeque<PricePoint>>>>,
+    daily: Arc<RwLock<HashMap<String, DailyStats>>>,
+    /// OHLC bars per symbol and timeframe, built from incoming quotes
+    candles: Arc<RwLock<CandleMap>>,
+    /// Quotes received over the WebSocket since startup
+    stream_quotes: Arc<AtomicU64>,
+    /// Set while prices come from REST polling instead of the WebSocket
+    rest_fallback: Arc<AtomicBool>,
+    /// Symbols streamed and polled, on top of those with alerts
+    symbols: Arc<RwLock<BTreeSet<String>>>,
+    /// Woken when the symbol set grows so the live WebSocket re-subscribes.
+    /// The permit is kept if no connection is up
+    resubscribe: Arc<Notify>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TopQuote {
+    ticker: String,
+    bid_price: Option<f64>,
+    ask_price: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -82,6 +554,19 @@ struct SubscribeMessage {
 struct SubscribeEventData {
     #[serde(rename = "thresholdLevel")]
     threshold_level: u32,
+    tickers: Vec<String>,
+}
+
+/// Symbols from `TIINGO_SYMBOLS`, the older `TIINGO_REST_SYMBOLS`, or the defaults
+fn configured_symbols() -> BTreeSet<String> {
+    let configured = std::env::var("TIINGO_SYMBOLS")
+        .or_else(|_| std::env::var("TIINGO_REST_SYMBOLS"))
+        .unwrap_or_else(|_| DEFAULT_SYMBOLS.to_string());
+    configured
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
 }
 
 impl TiingoService {
@@ -90,7 +575,39 @@ impl TiingoService {
             api_key,
             prices: Arc::new(RwLock::new(HashMap::new())),
             alerts: Arc::new(RwLock::new(Vec::new())),
+            history: Arc::new(RwLock::new(HashMap::new())),
+            daily: Arc::new(RwLock::new(HashMap::new())),
+            candles: Arc::new(RwLock::new(HashMap::new())),
+            stream_quotes: Arc::new(AtomicU64::new(0)),
+            rest_fallback: Arc::new(AtomicBool::new(false)),
+            symbols: Arc::new(RwLock::new(configured_symbols())),
+            resubscribe: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Every symbol requested from Tiingo: the configured set plus alert symbols
+    pub fn subscribed_symbols(&self) -> Vec<String> {
+        let mut symbols = self.symbols.read().clone();
+        symbols.extend(self.alerts.read().iter().map(|a| a.symbol.to_lowercase()));
+        symbols.into_iter().collect()
+    }
+
+    pub fn is_subscribed(&self, symbol: &str) -> bool {
+        let symbol = symbol.to_lowercase();
+        self.symbols.read().contains(&symbol)
+            || self.alerts.read().iter().any(|a| a.symbol == symbol)
+    }
+
+    /// Add a symbol to the subscription, returning false if it was already
+    /// subscribed. The live WebSocket picks it up right away, otherwise the
+    /// next connection does
+    pub fn subscribe(&self, symbol: &str) -> bool {
+        let already = self.is_subscribed(symbol);
+        self.symbols.write().insert(symbol.to_lowercase());
+        if !already {
+            self.resubscribe.notify_one();
         }
+        !already
     }
 
     pub fn get_price(&self, symbol: &str) -> Option<ForexPrice> {
@@ -101,8 +618,25 @@ impl TiingoService {
         self.prices.read().clone()
     }
 
+    pub fn get_daily_stats(&self, symbol: &str) -> Option<DailyStats> {
+        self.daily.read().get(&symbol.to_lowercase()).cloned()
+    }
+
+    /// Candles collected for a symbol, oldest first
+    pub fn get_candles(&self, symbol: &str, timeframe: Timeframe) -> Vec<Candle> {
+        self.candles
+            .read()
+            .get(&(symbol.to_lowercase(), timeframe))
+            .map(|c| c.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
     pub fn add_alert(&self, alert: PriceAlert) {
+        let new_symbol = !self.is_subscribed(&alert.symbol);
         self.alerts.write().push(alert);
+        if new_symbol {
+            self.resubscribe.notify_one();
+        }
     }
 
     pub fn remove_alert(&self, alert_id: i64) -> bool {
@@ -115,83 +649,358 @@ impl TiingoService {
         }
     }
 
+    /// Apply `edit` to one of a user's alerts and return it before and after.
+    /// Price tracking starts over as for a new alert
+    pub fn edit_alert(
+        &self,
+        user_id: u64,
+        alert_id: i64,
+        edit: impl FnOnce(&mut PriceAlert),
+    ) -> Option<(PriceAlert, PriceAlert)> {
+        let mut alerts = self.alerts.write();
+        let alert = alerts
+            .iter_mut()
+            .find(|a| a.id == alert_id && a.user_id == user_id)?;
+        let before = alert.clone();
+
+        edit(alert);
+        alert.last_side = None;
+        alert.armed = alert.condition != AlertCondition::Touch;
+        alert.move_start = None;
+        alert.calm_since = None;
+
+        Some((before, alert.clone()))
+    }
+
+    /// Remove all alerts owned by a user, returning how many were removed
+    pub fn remove_user_alerts(&self, user_id: u64) -> usize {
+        let mut alerts = self.alerts.write();
+        let before = alerts.len();
+        alerts.retain(|a| a.user_id != user_id);
+        before - alerts.len()
+    }
+
+    /// Active alerts created in a guild, for admins
+    pub fn get_guild_alerts(&self, guild_id: u64) -> Vec<PriceAlert> {
+        let now = Utc::now();
+        self.alerts
+            .read()
+            .iter()
+            .filter(|a| a.guild_id == guild_id && !a.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove a user's alerts in one guild, returning how many were removed
+    pub fn remove_guild_user_alerts(&self, guild_id: u64, user_id: u64) -> usize {
+        let mut alerts = self.alerts.write();
+        let before = alerts.len();
+        alerts.retain(|a| a.guild_id != guild_id || a.user_id != user_id);
+        before - alerts.len()
+    }
+
     pub fn get_user_alerts(&self, user_id: u64) -> Vec<PriceAlert> {
+        let now = Utc::now();
         self.alerts
             .read()
             .iter()
-            .filter(|a| a.user_id == user_id)
+            .filter(|a| a.user_id == user_id && !a.is_expired(now))
             .cloned()
             .collect()
     }
 
-    fn update_price(&self, symbol: String, bid: f64, ask: f64) {
+    /// Write all alerts to the database, replacing what was saved before
+    pub async fn save_alerts(&self, pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let stored: Vec<StoredAlert> = self
+            .alerts
+            .read()
+            .iter()
+            .map(PriceAlert::to_stored)
+            .collect();
+        PriceAlertRepository::replace_alerts(pool, &stored).await?;
+        Ok(stored.len())
+    }
+
+    /// Load alerts saved by `save_alerts`. The saved rows are removed, so
+    /// after a crash the old snapshot doesn't come back on the next start
+    pub async fn load_alerts(&self, pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let loaded: Vec<PriceAlert> = PriceAlertRepository::take_alerts(pool)
+            .await?
+            .into_iter()
+            .filter_map(PriceAlert::from_stored)
+            .collect();
+
+        if let Some(max_id) = loaded.iter().map(|a| a.id).max() {
+            ALERT_ID_COUNTER.fetch_max(max_id + 1, Ordering::SeqCst);
+        }
+
+        let count = loaded.len();
+        self.alerts.write().extend(loaded);
+        Ok(count)
+    }
+
+    /// Store a quote and return the previous mid price for the symbol
+    fn update_price(&self, symbol: String, bid: f64, ask: f64, delayed: bool) -> Option<f64> {
         let mid = (bid + ask) / 2.0;
+        let now = Utc::now();
         let price = ForexPrice {
             symbol: symbol.clone(),
             bid,
             ask,
             mid,
-            timestamp: Utc::now(),
+            timestamp: now,
+            delayed,
         };
-        self.prices.write().insert(symbol.to_lowercase(), price);
+        let previous_mid = self
+            .prices
+            .write()
+            .insert(symbol.to_lowercase(), price)
+            .map(|p| p.mid);
+
+        let mut history = self.history.write();
+        let points = history.entry(symbol.to_lowercase()).or_default();
+        if points
+            .back()
+            .is_none_or(|p| (now - p.at).num_seconds() >= HISTORY_SAMPLE_SECS)
+        {
+            points.push_back(PricePoint { at: now, mid });
+        }
+        let oldest = now - chrono::Duration::minutes(MAX_MOVE_WINDOW_MINS);
+        while points.front().is_some_and(|p| p.at < oldest) {
+            points.pop_front();
+        }
+        drop(history);
+
+        let today = now.date_naive();
+        let mut daily = self.daily.write();
+        match daily.get_mut(&symbol.to_lowercase()) {
+            Some(stats) if stats.date == today => {
+                stats.high = stats.high.max(mid);
+                stats.low = stats.low.min(mid);
+                stats.last = mid;
+            }
+            Some(stats) => *stats = DailyStats::new(today, mid, Some(stats.last)),
+            None => {
+                daily.insert(symbol.to_lowercase(), DailyStats::new(today, mid, None));
+            }
+        }
+        drop(daily);
+
+        let mut candles = self.candles.write();
+        for timeframe in Timeframe::ALL {
+            let start = timeframe.bucket(now);
+            let bars = candles
+                .entry((symbol.to_lowercase(), timeframe))
+                .or_default();
+            match bars.back_mut() {
+                Some(bar) if bar.start == start => {
+                    bar.high = bar.high.max(mid);
+                    bar.low = bar.low.min(mid);
+                    bar.close = mid;
+                }
+                _ => {
+                    bars.push_back(Candle {
+                        start,
+                        open: mid,
+                        high: mid,
+                        low: mid,
+                        close: mid,
+                    });
+                    if bars.len() > MAX_CANDLES {
+                        bars.pop_front();
+                    }
+                }
+            }
+        }
+        drop(candles);
+
+        previous_mid
     }
 
-    fn check_alerts(&self, symbol: &str, price: f64) -> Vec<PriceAlert> {
-        let alerts = self.alerts.read();
+    fn check_alerts(&self, symbol: &str, price: f64, spread_pips: f64) -> Vec<PriceAlert> {
+        let now = Utc::now();
+        let mut alerts = self.alerts.write();
+
+        let before = alerts.len();
+        alerts.retain(|a| !a.is_expired(now));
+        if alerts.len() < before {
+            println!(
+                "[TIINGO] Removed {} expired alert(s)",
+                before - alerts.len()
+            );
+        }
+
+        let history = self.history.read();
+        let points = history.get(&symbol.to_lowercase());
         alerts
-            .iter()
-            .filter(|a| {
-                a.symbol.to_lowercase() == symbol.to_lowercase()
-                    && match a.condition {
-                        AlertCondition::Above => price >= a.target_price,
-                        AlertCondition::Below => price <= a.target_price,
-                    }
+            .iter_mut()
+            .filter(|a| a.symbol.to_lowercase() == symbol.to_lowercase())
+            .filter_map(|a| {
+                a.evaluate(price, spread_pips, now, points)
+                    .then(|| a.clone())
             })
-            .cloned()
             .collect()
     }
 
     fn remove_triggered_alerts(&self, triggered: &[PriceAlert]) {
         let mut alerts = self.alerts.write();
-        alerts.retain(|a| !triggered.iter().any(|t| t.id == a.id));
+        alerts.retain(|a| !a.is_one_shot() || !triggered.iter().any(|t| t.id == a.id));
+    }
+
+    pub async fn start_price_polling(self: Arc<Self>, http: Arc<Http>, health: HealthRegistry) {
+        health::register(&health, SERVICE_NAME);
+
+        // Both loops run in the caller's task so aborting it stops the fallback too
+        tokio::join!(
+            self.run_rest_fallback(http.clone()),
+            self.run_websocket(http, &health)
+        );
     }
 
-    pub async fn start_price_polling(self: Arc<Self>, http: Arc<Http>) {
+    /// Stream quotes over the WebSocket, reconnecting forever
+    async fn run_websocket(&self, http: Arc<Http>, health: &HealthRegistry) {
+        let mut failures = 0u32;
         loop {
             println!("[TIINGO] Connecting to WebSocket...");
-            match self.connect_and_run(http.clone()).await {
+            let quotes_before = self.stream_quotes.load(Ordering::Relaxed);
+            match self.connect_and_run(http.clone(), health).await {
                 Ok(_) => println!("[TIINGO] WebSocket closed normally"),
                 Err(e) => eprintln!("[TIINGO] WebSocket error: {}", e),
             }
+            health::record_failure(health, SERVICE_NAME);
+
+            // A session that never streamed a quote counts as a failure
+            if self.stream_quotes.load(Ordering::Relaxed) > quotes_before {
+                failures = 0;
+            }
+            failures += 1;
+            if failures >= FALLBACK_AFTER_FAILURES
+                && !self.rest_fallback.swap(true, Ordering::Relaxed)
+            {
+                println!(
+                    "[TIINGO] WebSocket failed {} times, polling REST every {}s",
+                    failures, REST_POLL_INTERVAL_SECS
+                );
+            }
             println!("[TIINGO] Reconnecting in 5 seconds...");
             tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
         }
     }
 
+    /// Poll `fx/top` while `rest_fallback` is set
+    async fn run_rest_fallback(&self, http: Arc<Http>) {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        let mut poll_interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(REST_POLL_INTERVAL_SECS));
+
+        loop {
+            poll_interval.tick().await;
+            if !self.rest_fallback.load(Ordering::Relaxed) {
+                continue;
+            }
+            if let Err(e) = self.poll_rest(&client, &http).await {
+                eprintln!("[TIINGO] REST polling error: {}", e);
+            }
+        }
+    }
+
+    async fn poll_rest(
+        &self,
+        client: &reqwest::Client,
+        http: &Arc<Http>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let symbols = self.subscribed_symbols();
+        if symbols.is_empty() {
+            return Ok(());
+        }
+
+        let quotes: Vec<TopQuote> = client
+            .get(TIINGO_REST_URL)
+            .query(&[("tickers", symbols.join(","))])
+            .header("Authorization", format!("Token {}", self.api_key))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        for quote in quotes {
+            if let (Some(bid), Some(ask)) = (quote.bid_price, quote.ask_price) {
+                self.process_quote(quote.ticker.to_lowercase(), bid, ask, true, http)
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Store a quote and notify any alerts it triggers
+    async fn process_quote(
+        &self,
+        symbol: String,
+        bid: f64,
+        ask: f64,
+        delayed: bool,
+        http: &Arc<Http>,
+    ) {
+        if symbol.is_empty() || bid <= 0.0 || ask <= 0.0 {
+            return;
+        }
+
+        let spread_pct = (ask - bid).abs() / bid * 100.0;
+        if spread_pct > 1.0 {
+            return;
+        }
+
+        let previous_mid = self.update_price(symbol.clone(), bid, ask, delayed);
+
+        let mid = (bid + ask) / 2.0;
+        let spread_pips = (ask - bid) / pip_size(&symbol);
+        let triggered = self.check_alerts(&symbol, mid, spread_pips);
+        if !triggered.is_empty() {
+            self.send_alert_notifications(&triggered, bid, ask, previous_mid, http)
+                .await;
+            self.remove_triggered_alerts(&triggered);
+        }
+    }
+
     async fn connect_and_run(
         &self,
         http: Arc<Http>,
+        health: &HealthRegistry,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let (ws_stream, _) = connect_async(TIINGO_WS_URL).await?;
         println!("[TIINGO] Connected to WebSocket");
 
         let (mut write, mut read) = ws_stream.split();
 
-        let subscribe_msg = SubscribeMessage {
-            event_name: "subscribe".to_string(),
-            authorization: self.api_key.clone(),
-            event_data: SubscribeEventData { threshold_level: 5 },
-        };
-
-        let msg_json = serde_json::to_string(&subscribe_msg)?;
-        write.send(WsMessage::Text(msg_json)).await?;
+        write
+            .send(WsMessage::Text(self.subscribe_message()?))
+            .await?;
         println!("[TIINGO] Sent subscription message");
 
         let mut log_count = 0u64;
 
-        while let Some(msg) = read.next().await {
+        loop {
+            let msg = tokio::select! {
+                msg = read.next() => msg,
+                _ = self.resubscribe.notified() => {
+                    write
+                        .send(WsMessage::Text(self.subscribe_message()?))
+                        .await?;
+                    println!("[TIINGO] Updated subscription");
+                    continue;
+                }
+            };
+            let Some(msg) = msg else {
+                break;
+            };
             match msg {
                 Ok(WsMessage::Text(text)) => {
+                    health::record_success(health, SERVICE_NAME);
                     self.handle_message(&text, &http, &mut log_count).await;
                 }
                 Ok(WsMessage::Ping(data)) => {
@@ -212,6 +1021,17 @@ impl TiingoService {
         Ok(())
     }
 
+    fn subscribe_message(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&SubscribeMessage {
+            event_name: "subscribe".to_string(),
+            authorization: self.api_key.clone(),
+            event_data: SubscribeEventData {
+                threshold_level: 5,
+                tickers: self.subscribed_symbols(),
+            },
+        })
+    }
+
     async fn handle_message(&self, text: &str, http: &Arc<Http>, log_count: &mut u64) {
         let json: serde_json::Value = match serde_json::from_str(text) {
             Ok(v) => v,
@@ -237,13 +1057,9 @@ impl TiingoService {
                         let bid = data.get(4).and_then(|v| v.as_f64()).unwrap_or(0.0);
                         let ask = data.get(7).and_then(|v| v.as_f64()).unwrap_or(0.0);
 
-                        if symbol.is_empty() || bid <= 0.0 || ask <= 0.0 {
-                            return;
-                        }
-
-                        let spread_pct = (ask - bid).abs() / bid * 100.0;
-                        if spread_pct > 1.0 {
-                            return;
+                        self.stream_quotes.fetch_add(1, Ordering::Relaxed);
+                        if self.rest_fallback.swap(false, Ordering::Relaxed) {
+                            println!("[TIINGO] WebSocket streaming again, stopped REST polling");
                         }
 
                         *log_count += 1;
@@ -251,14 +1067,7 @@ impl TiingoService {
                             println!("[TIINGO] {} bid={:.5} ask={:.5}", symbol, bid, ask);
                         }
 
-                        self.update_price(symbol.clone(), bid, ask);
-
-                        let mid = (bid + ask) / 2.0;
-                        let triggered = self.check_alerts(&symbol, mid);
-                        if !triggered.is_empty() {
-                            self.send_alert_notifications(&triggered, mid, http).await;
-                            self.remove_triggered_alerts(&triggered);
-                        }
+                        self.process_quote(symbol, bid, ask, false, http).await;
                     }
                 }
             }
@@ -275,22 +1084,117 @@ impl TiingoService {
     async fn send_alert_notifications(
         &self,
         alerts: &[PriceAlert],
-        current_price: f64,
+        bid: f64,
+        ask: f64,
+        previous_mid: Option<f64>,
         http: &Arc<Http>,
     ) {
+        let current_price = (bid + ask) / 2.0;
+        let now = Utc::now();
         for alert in alerts {
-            let embed = CreateEmbed::new()
-                .title("Price Alert Triggered!")
-                .description(format!(
-                    "**{}** is now {} **{:.5}**\n\nTarget: {:.5}\nCurrent: {:.5}",
+            let spread_pips = (ask - bid) / pip_size(&alert.symbol);
+            let price = |p: f64| format_price(&alert.symbol, p);
+            let headline = match alert.condition {
+                AlertCondition::Above | AlertCondition::Below => format!(
+                    "**{}** is now {} **{}**",
                     alert.symbol.to_uppercase(),
                     alert.condition,
+                    price(alert.target_price)
+                ),
+                AlertCondition::Cross => format!(
+                    "**{}** crossed **{}**",
+                    alert.symbol.to_uppercase(),
+                    price(alert.target_price)
+                ),
+                AlertCondition::Touch => format!(
+                    "**{}** touched **{}**",
+                    alert.symbol.to_uppercase(),
+                    price(alert.target_price)
+                ),
+                AlertCondition::Move => format!(
+                    "**{}** moved more than **{:.2}%**",
+                    alert.symbol.to_uppercase(),
+                    alert.target_price
+                ),
+                AlertCondition::Spread => format!(
+                    "**{}** spread widened to **{:.1} pips**",
+                    alert.symbol.to_uppercase(),
+                    spread_pips
+                ),
+            };
+
+            let details = match (&alert.condition, alert.move_start) {
+                (AlertCondition::Move, Some(start)) => format!(
+                    "Start: {}\nCurrent: {}\nChange: {:+.2}%\nWindow: {}m",
+                    price(start),
+                    price(current_price),
+                    (current_price - start) / start * 100.0,
+                    alert.window_mins.unwrap_or(60)
+                ),
+                (AlertCondition::Spread, _) => format!(
+                    "Threshold: {:.1} pips\nSpread: {:.1} pips\nMid: {}",
                     alert.target_price,
-                    alert.target_price,
-                    current_price
-                ))
+                    spread_pips,
+                    price(current_price)
+                ),
+                _ => format!(
+                    "Target: {}\nCurrent: {}",
+                    price(alert.target_price),
+                    price(current_price)
+                ),
+            };
+            let direction = match previous_mid {
+                Some(previous) if current_price > previous => {
+                    format!("📈 Rising from {}", price(previous))
+                }
+                Some(previous) if current_price < previous => {
+                    format!("📉 Falling from {}", price(previous))
+                }
+                Some(_) => "➡️ Unchanged".to_string(),
+                None => "First quote since startup".to_string(),
+            };
+            let active = (now - alert.created_at).to_std().unwrap_or_default();
+            let context = format!(
+                "Direction: {}\nBid: {} • Ask: {}\nActive for: {}",
+                direction,
+                price(bid),
+                price(ask),
+                format_elapsed(active)
+            );
+
+            let mut embed = CreateEmbed::new()
+                .title("Price Alert Triggered!")
+                .description(format!("{}\n\n{}\n\n{}", headline, details, context))
                 .color(0x00ff00);
 
+            if alert.condition == AlertCondition::Spread {
+                embed = embed.footer(CreateEmbedFooter::new(format!(
+                    "Fires again after the spread stays under {:.1} pips for {}s • /alertremove {} to stop",
+                    alert.target_price, SPREAD_REARM_SECS, alert.id
+                )));
+            } else if alert.condition == AlertCondition::Touch {
+                embed = embed.footer(CreateEmbedFooter::new(format!(
+                    "Re-arms after moving {:.0} pips away • /alertremove {} to stop",
+                    alert.rearm_pips, alert.id
+                )));
+            } else if alert.repeat {
+                embed = embed.footer(CreateEmbedFooter::new(format!(
+                    "Repeating alert, fires again after {} min • /alertremove {} to stop",
+                    REPEAT_COOLDOWN_MINS, alert.id
+                )));
+            }
+
+            if alert.notify == AlertNotify::Dm {
+                let dm = CreateMessage::new().embed(embed.clone());
+                match UserId::new(alert.user_id).direct_message(http, dm).await {
+                    Ok(_) => continue,
+                    Err(e) => eprintln!(
+                        "[TIINGO] Could not DM alert #{} to {}, using channel: {}",
+                        alert.id, alert.user_id, e
+                    ),
+                }
+            }
+
             let channel_id = ChannelId::new(alert.channel_id);
             let message = CreateMessage::new()
                 .content(format!("<@{}>", alert.user_id))
@@ -311,3 +1215,90 @@ pub fn init_global_tiingo(service: Arc<TiingoService>) {
 pub fn get_global_tiingo() -> Option<&'static Arc<TiingoService>> {
     GLOBAL_TIINGO.get()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(symbol: &str, bid: f64, ask: f64) -> ForexPrice {
+        ForexPrice {
+            symbol: symbol.to_string(),
+            bid,
+            ask,
+            mid: (bid + ask) / 2.0,
+            timestamp: Utc::now(),
+            delayed: false,
+        }
+    }
+
+    fn assert_pips(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {} pips, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_standard_pair_pips() {
+        assert_pips(quote("eurusd", 1.08501, 1.08513).spread_pips(), 1.2);
+        assert_pips(quote("GBPUSD", 1.26500, 1.26520).spread_pips(), 2.0);
+        assert_eq!(format_price("eurusd", 1.085), "1.08500");
+    }
+
+    #[test]
+    fn test_jpy_pair_pips() {
+        assert_pips(quote("usdjpy", 150.123, 150.145).spread_pips(), 2.2);
+        assert_pips(quote("gbpjpy", 190.100, 190.130).spread_pips(), 3.0);
+        assert_eq!(format_price("usdjpy", 150.1234), "150.123");
+    }
+
+    #[test]
+    fn test_metal_pips() {
+        assert_pips(quote("xauusd", 2300.10, 2300.45).spread_pips(), 3.5);
+        assert_pips(quote("xagusd", 28.500, 28.530).spread_pips(), 3.0);
+        assert_eq!(format_price("xauusd", 2300.456), "2300.46");
+        assert_eq!(format_price("xagusd", 28.5), "28.500");
+    }
+
+    #[test]
+    fn test_unknown_symbols_fall_back() {
+        assert_eq!(symbol_meta("usdsek").decimals, 5);
+        assert_pips(quote("usdsek", 10.5000, 10.5010).spread_pips(), 10.0);
+        assert_pips(quote("sekjpy", 14.00, 14.02).spread_pips(), 2.0);
+        assert_pips(quote("xaueur", 2100.0, 2100.5).spread_pips(), 5.0);
+    }
+    fn stored_cross_alert(last_side: Option<bool>) -> StoredAlert {
+        StoredAlert {
+            id: 1,
+            guild_id: 1,
+            user_id: 1,
+            channel_id: 1,
+            symbol: "eurusd".to_string(),
+            condition: "cross".to_string(),
+            target_price: 1.1,
+            rearm_pips: 0.0,
+            created_at: 0,
+            repeat: false,
+            expires_at: None,
+            last_triggered: None,
+            window_mins: None,
+            notify_dm: false,
+            last_side,
+            armed: None,
+        }
+    }
+
+    #[test]
+    fn test_restored_alert_keeps_crossing_state() {
+        let mut alert = PriceAlert::from_stored(stored_cross_alert(Some(false))).unwrap();
+        assert!(alert.evaluate(1.11, 1.0, Utc::now(), None));
+
+        // Already above the target when saved, so staying above is no crossing
+        let saved = alert.to_stored();
+        assert_eq!(saved.last_side, Some(true));
+        let mut restored = PriceAlert::from_stored(saved).unwrap();
+        assert!(!restored.evaluate(1.12, 1.0, Utc::now(), None));
+    }
+}
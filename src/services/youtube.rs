@@ -1,6 +1,15 @@
+use parking_lot::Mutex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Repeated searches within this window reuse the last results instead of spending quota
+const SEARCH_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+/// Least recently used searches are dropped past this many entries
+const SEARCH_CACHE_SIZE: usize = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YouTubeVideo {
@@ -11,9 +20,19 @@ pub struct YouTubeVideo {
     pub url: String,
 }
 
+/// One page of search results. `next_page_token` is `None` on the last page
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub videos: Vec<YouTubeVideo>,
+    pub next_page_token: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct YouTubeSearchResponse {
     items: Vec<YouTubeSearchItem>,
+    #[serde(default)]
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,10 +65,21 @@ struct YouTubeThumbnail {
     url: String,
 }
 
+struct CachedSearch {
+    page: SearchPage,
+    fetched_at: Instant,
+    last_used: Instant,
+}
+
+/// Keyed by lowercased query, result count and page token
+type SearchKey = (String, u32, Option<String>);
+type SearchCache = Arc<Mutex<HashMap<SearchKey, CachedSearch>>>;
+
 #[derive(Clone)]
 pub struct YouTubeSearch {
     client: Client,
     api_key: String,
+    cache: SearchCache,
 }
 
 impl YouTubeSearch {
@@ -62,18 +92,76 @@ impl YouTubeSearch {
         Some(Self {
             client: Client::new(),
             api_key,
+            cache: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
+    fn cached(&self, key: &SearchKey) -> Option<SearchPage> {
+        let mut cache = self.cache.lock();
+        let entry = cache.get_mut(key)?;
+        if entry.fetched_at.elapsed() >= SEARCH_CACHE_TTL {
+            cache.remove(key);
+            return None;
+        }
+        entry.last_used = Instant::now();
+        Some(entry.page.clone())
+    }
+
+    fn store(&self, key: SearchKey, page: SearchPage) {
+        let mut cache = self.cache.lock();
+        cache.retain(|_, entry| entry.fetched_at.elapsed() < SEARCH_CACHE_TTL);
+        if cache.len() >= SEARCH_CACHE_SIZE
+            && let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            cache.remove(&oldest);
+        }
+
+        let now = Instant::now();
+        cache.insert(
+            key,
+            CachedSearch {
+                page,
+                fetched_at: now,
+                last_used: now,
+            },
+        );
+    }
+
     pub async fn search(&self, query: &str, max_results: u32) -> Result<Vec<YouTubeVideo>, String> {
+        Ok(self.search_page(query, max_results, None).await?.videos)
+    }
+
+    /// Fetch the page after `page_token`, or the first page when it is `None`
+    pub async fn search_page(
+        &self,
+        query: &str,
+        max_results: u32,
+        page_token: Option<&str>,
+    ) -> Result<SearchPage, String> {
         let max_results = max_results.min(10);
 
-        let url = format!(
+        let key = (
+            query.trim().to_lowercase(),
+            max_results,
+            page_token.map(str::to_string),
+        );
+        if let Some(page) = self.cached(&key) {
+            println!("[YOUTUBE] Cache hit for \"{}\"", key.0);
+            return Ok(page);
+        }
+
+        let mut url = format!(
             "https://www.googleapis.com/youtube/v3/search?part=snippet&type=video&maxResults={}&q={}&key={}",
             max_results,
             urlencoding::encode(query),
             self.api_key
         );
+        if let Some(token) = page_token {
+            url.push_str(&format!("&pageToken={}", urlencoding::encode(token)));
+        }
 
         let response = self
             .client
@@ -106,9 +194,14 @@ impl YouTubeSearch {
                     thumbnail: item.snippet.thumbnails.default.url,
                 })
             })
-            .collect();
-
-        Ok(videos)
+            .collect::<Vec<_>>();
+
+        let page = SearchPage {
+            videos,
+            next_page_token: data.next_page_token,
+        };
+        self.store(key, page.clone());
+        Ok(page)
     }
 }
 
@@ -0,0 +1,71 @@
+use crate::services::tiingo::Candle;
+use plotters::prelude::*;
+
+const WIDTH: u32 = 900;
+const HEIGHT: u32 = 450;
+const BACKGROUND: RGBColor = RGBColor(0x2B, 0x2D, 0x31);
+const GRID: RGBColor = RGBColor(0x40, 0x44, 0x4B);
+const UP: RGBColor = RGBColor(0x2E, 0xCC, 0x71);
+const DOWN: RGBColor = RGBColor(0xE7, 0x4C, 0x3C);
+const GRID_LINES: usize = 5;
+
+/// Draw candles as a PNG. The image has no text (no fonts are bundled), so
+/// prices and times belong in the message that carries it
+pub fn render_candles(
+    candles: &[Candle],
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let low = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+    let high = candles
+        .iter()
+        .map(|c| c.high)
+        .fold(f64::NEG_INFINITY, f64::max);
+    // Keep a flat market from collapsing the y range
+    let pad = ((high - low) * 0.05).max(high.abs() * 1e-5);
+    let (low, high) = (low - pad, high + pad);
+
+    let mut pixels = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut pixels, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&BACKGROUND).map_err(|e| e.to_string())?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(20)
+            .build_cartesian_2d(-1i32..candles.len() as i32, low..high)
+            .map_err(|e| e.to_string())?;
+
+        chart
+            .draw_series((1..GRID_LINES).map(|i| {
+                let y = low + (high - low) * i as f64 / GRID_LINES as f64;
+                PathElement::new(vec![(-1, y), (candles.len() as i32, y)], GRID)
+            }))
+            .map_err(|e| e.to_string())?;
+
+        let width = ((WIDTH - 40) / (candles.len() as u32 + 1))
+            .saturating_sub(2)
+            .max(1);
+        chart
+            .draw_series(candles.iter().enumerate().map(|(i, c)| {
+                CandleStick::new(
+                    i as i32,
+                    c.open,
+                    c.high,
+                    c.low,
+                    c.close,
+                    UP.filled(),
+                    DOWN.filled(),
+                    width,
+                )
+            }))
+            .map_err(|e| e.to_string())?;
+
+        root.present().map_err(|e| e.to_string())?;
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&pixels)?;
+
+    Ok(png)
+}
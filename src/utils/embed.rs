@@ -1,10 +1,12 @@
-use poise::serenity_prelude::CreateEmbed;
+use poise::serenity_prelude::{CreateEmbed, User};
 
 pub const COLOR_SUCCESS: u32 = 0x2ECC71; // Green
 pub const COLOR_ERROR: u32 = 0xE74C3C; // Red
 pub const COLOR_WARNING: u32 = 0xF39C12; // Orange
 pub const COLOR_INFO: u32 = 0x3498DB; // Blue
 pub const COLOR_MUSIC: u32 = 0x1DB954; // Spotify Green
+pub const COLOR_WELCOME: u32 = 0x5865F2; // Blurple
+pub const COLOR_GOODBYE: u32 = 0xED4245; // Red
 
 pub fn success(title: &str, description: &str) -> CreateEmbed {
     CreateEmbed::new()
@@ -140,7 +142,7 @@ pub fn member_join(
 
     let mut embed = CreateEmbed::new()
         .description(description)
-        .color(0x5865F2)
+        .color(COLOR_WELCOME)
         .footer(serenity::all::CreateEmbedFooter::new(format!(
             "WELCOME • {}",
             username
@@ -167,7 +169,7 @@ pub fn member_leave(
 
     let mut embed = CreateEmbed::new()
         .description(description)
-        .color(0xED4245)
+        .color(COLOR_GOODBYE)
         .footer(serenity::all::CreateEmbedFooter::new(format!(
             "GOODBYE • {}",
             username
@@ -181,6 +183,59 @@ pub fn member_leave(
 }
 
 
+/// Fill the placeholders in a welcome/goodbye template. `{user}` is a mention
+/// when `mention` is set, otherwise the plain username
+pub fn render_member_template(
+    template: &str,
+    user: &User,
+    mention: bool,
+    server: &str,
+    count: u64,
+) -> String {
+    let user_text = if mention {
+        format!("<@{}>", user.id)
+    } else {
+        user.name.clone()
+    };
+    template
+        .replace("{username}", user.display_name())
+        .replace("{user}", &user_text)
+        .replace("{server}", server)
+        .replace("{count}", &count.to_string())
+        .replace(
+            "{created}",
+            &format!("<t:{}:D>", user.created_at().unix_timestamp()),
+        )
+        .replace("{id}", &user.id.to_string())
+}
+
+/// Templates wrapped in `{embed:...}` are sent as an embed, anything else as
+/// plain text. Returns the inner text and whether it is an embed
+pub fn split_member_template(template: &str) -> (&str, bool) {
+    match template.trim().strip_prefix("{embed:") {
+        Some(rest) => (rest.strip_suffix('}').unwrap_or(rest).trim(), true),
+        None => (template, false),
+    }
+}
+
+pub fn member_custom(
+    description: &str,
+    footer: &str,
+    color: u32,
+    avatar_url: Option<&str>,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .description(description)
+        .color(color)
+        .footer(serenity::all::CreateEmbedFooter::new(footer));
+
+    if let Some(avatar) = avatar_url {
+        embed = embed.thumbnail(avatar);
+    }
+
+    embed
+}
+
 pub fn voice_join(
     username: &str,
     _user_id: u64,
@@ -0,0 +1,99 @@
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub type HealthRegistry = Arc<RwLock<HashMap<&'static str, ServiceHealth>>>;
+
+const STALE_WARN: Duration = Duration::from_secs(5 * 60);
+const STALE_DOWN: Duration = Duration::from_secs(10 * 60);
+const MAX_FAILURES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Down,
+}
+
+impl HealthStatus {
+    pub fn indicator(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "🟢",
+            HealthStatus::Degraded => "🟡",
+            HealthStatus::Down => "🔴",
+        }
+    }
+}
+
+/// Health of a background service. `failure_count` counts failures since the last success
+#[derive(Debug, Clone)]
+pub struct ServiceHealth {
+    pub service_name: String,
+    pub last_success: Instant,
+    pub failure_count: u32,
+}
+
+impl ServiceHealth {
+    pub fn new(service_name: &str) -> Self {
+        Self {
+            service_name: service_name.to_string(),
+            last_success: Instant::now(),
+            failure_count: 0,
+        }
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        let since_success = self.last_success.elapsed();
+        if self.failure_count > MAX_FAILURES || since_success > STALE_DOWN {
+            HealthStatus::Down
+        } else if self.failure_count > 0 || since_success > STALE_WARN {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+pub fn new_registry() -> HealthRegistry {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+/// Add a service to the registry when it starts
+pub fn register(registry: &HealthRegistry, name: &'static str) {
+    registry
+        .write()
+        .entry(name)
+        .or_insert_with(|| ServiceHealth::new(name));
+}
+
+pub fn record_success(registry: &HealthRegistry, name: &'static str) {
+    let mut services = registry.write();
+    let health = services
+        .entry(name)
+        .or_insert_with(|| ServiceHealth::new(name));
+    health.last_success = Instant::now();
+    health.failure_count = 0;
+}
+
+pub fn record_failure(registry: &HealthRegistry, name: &'static str) {
+    let mut services = registry.write();
+    let health = services
+        .entry(name)
+        .or_insert_with(|| ServiceHealth::new(name));
+    health.failure_count += 1;
+}
+
+/// Format a duration as a short relative time, e.g. "3m ago"
+pub fn format_ago(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86400)
+    }
+}
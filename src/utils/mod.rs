@@ -1,2 +1,6 @@
+pub mod chart;
 pub mod embed;
+pub mod health;
+pub mod pagination;
 pub mod sys;
+pub mod time;
@@ -0,0 +1,93 @@
+use poise::serenity_prelude::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use std::time::Duration;
+
+/// Send `pages` with Previous/Next buttons. Only the invoking user can turn pages;
+/// the buttons are disabled after `timeout_secs` without a press
+pub async fn paginate<U: Send + Sync + 'static, E>(
+    ctx: poise::Context<'_, U, E>,
+    pages: Vec<CreateEmbed>,
+    timeout_secs: u64,
+) -> Result<(), poise::serenity_prelude::Error> {
+    let total = pages.len();
+    if total == 0 {
+        return Ok(());
+    }
+
+    let page_embed = |index: usize| {
+        pages[index].clone().footer(CreateEmbedFooter::new(format!(
+            "Page {}/{}",
+            index + 1,
+            total
+        )))
+    };
+
+    if total == 1 {
+        ctx.send(poise::CreateReply::default().embed(page_embed(0)))
+            .await?;
+        return Ok(());
+    }
+
+    let prev_id = format!("{}_page_prev", ctx.id());
+    let next_id = format!("{}_page_next", ctx.id());
+    let buttons = |index: usize, expired: bool| {
+        vec![CreateActionRow::Buttons(vec![
+            CreateButton::new(&prev_id)
+                .label("Previous")
+                .style(ButtonStyle::Secondary)
+                .disabled(expired || index == 0),
+            CreateButton::new(&next_id)
+                .label("Next")
+                .style(ButtonStyle::Secondary)
+                .disabled(expired || index + 1 == total),
+        ])]
+    };
+
+    let mut current = 0;
+    let reply = ctx
+        .send(
+            poise::CreateReply::default()
+                .embed(page_embed(current))
+                .components(buttons(current, false)),
+        )
+        .await?;
+    let msg = reply.message().await?;
+
+    while let Some(interaction) =
+        ComponentInteractionCollector::new(ctx.serenity_context().shard.clone())
+            .message_id(msg.id)
+            .author_id(ctx.author().id)
+            .timeout(Duration::from_secs(timeout_secs))
+            .await
+    {
+        if interaction.data.custom_id == prev_id {
+            current = current.saturating_sub(1);
+        } else if interaction.data.custom_id == next_id {
+            current = (current + 1).min(total - 1);
+        }
+
+        interaction
+            .create_response(
+                ctx.http(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(page_embed(current))
+                        .components(buttons(current, false)),
+                ),
+            )
+            .await?;
+    }
+
+    reply
+        .edit(
+            ctx,
+            poise::CreateReply::default()
+                .embed(page_embed(current))
+                .components(buttons(current, true)),
+        )
+        .await?;
+
+    Ok(())
+}
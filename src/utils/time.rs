@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+/// Parse a short duration like `30s`, `5m`, `1h` or `7d`
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim().to_lowercase();
+    let unit = input.chars().last()?;
+    let num: u64 = input[..input.len() - unit.len_utf8()].parse().ok()?;
+
+    let unit_secs = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86400,
+        _ => return None,
+    };
+    num.checked_mul(unit_secs).map(Duration::from_secs)
+}
+
+/// Compact elapsed time with the two largest units, like `3h 12m` or `2d 5h`
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (days, hours, mins) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Common abbreviations accepted wherever a timezone name is
+const TIMEZONE_ALIASES: &[(&str, &str)] = &[
+    ("WIB", "Asia/Jakarta"),
+    ("WITA", "Asia/Makassar"),
+    ("WIT", "Asia/Jayapura"),
+    ("UTC", "UTC"),
+    ("GMT", "Etc/GMT"),
+    ("EST", "America/New_York"),
+    ("EDT", "America/New_York"),
+    ("CST", "America/Chicago"),
+    ("CDT", "America/Chicago"),
+    ("MST", "America/Denver"),
+    ("MDT", "America/Denver"),
+    ("PST", "America/Los_Angeles"),
+    ("PDT", "America/Los_Angeles"),
+    ("BST", "Europe/London"),
+    ("CET", "Europe/Paris"),
+    ("CEST", "Europe/Paris"),
+    ("JST", "Asia/Tokyo"),
+    ("KST", "Asia/Seoul"),
+    ("SGT", "Asia/Singapore"),
+    ("IST", "Asia/Kolkata"),
+    ("AEST", "Australia/Sydney"),
+];
+
+/// Resolve an IANA name like `Asia/Jakarta` or an alias like `WIB`
+pub fn parse_timezone(input: &str) -> Option<chrono_tz::Tz> {
+    let input = input.trim();
+    let name = TIMEZONE_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(input))
+        .map(|(_, name)| *name)
+        .unwrap_or(input);
+    name.parse::<chrono_tz::Tz>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration(" 4H "), Some(Duration::from_secs(4 * 3600)));
+        assert_eq!(parse_duration("7d"), Some(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration("7w"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_overflow_and_multibyte_units() {
+        assert_eq!(parse_duration("18446744073709551615d"), None);
+        assert_eq!(parse_duration("5é"), None);
+        assert_eq!(parse_duration("日"), None);
+    }
+}